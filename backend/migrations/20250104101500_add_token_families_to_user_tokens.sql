@@ -0,0 +1,26 @@
+-- Add migration script here
+
+-- Every refresh token belongs to a family started at login. Rotating a refresh token
+-- keeps the old row (marked as rotated) so that a replay of it can be detected.
+ALTER TABLE user_tokens ADD COLUMN family_id UUID;
+UPDATE user_tokens SET family_id = token_id;
+ALTER TABLE user_tokens ALTER COLUMN family_id SET NOT NULL;
+
+ALTER TABLE user_tokens ADD COLUMN parent_token_id UUID;
+ALTER TABLE user_tokens ADD COLUMN rotated_at TIMESTAMPTZ;
+
+CREATE INDEX user_tokens_family_id_idx ON user_tokens (family_id);
+
+CREATE TABLE security_events (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(64) NOT NULL,
+    os TEXT,
+    is_mobile BOOLEAN,
+    browser TEXT,
+    app_version TEXT,
+    model TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX security_events_user_id_idx ON security_events (user_id);
@@ -0,0 +1,3 @@
+-- Add migration script here
+-- OTP secrets are encrypted by the application on startup, which no longer fits the plaintext length
+ALTER TABLE users ALTER COLUMN otp_base32 TYPE TEXT;
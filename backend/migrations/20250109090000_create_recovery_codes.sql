@@ -0,0 +1,20 @@
+-- Add migration script here
+
+-- One row per recovery code, used codes are kept with the time they were used
+CREATE TABLE recovery_codes (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash TEXT NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX recovery_codes_user_id_idx ON recovery_codes (user_id);
+
+-- The semicolon-joined column only held the codes that were still unused
+INSERT INTO recovery_codes (id, user_id, code_hash)
+SELECT gen_random_uuid(), users.id, code_hash
+FROM users, unnest(string_to_array(users.recovery_codes, ';')) AS code_hash
+WHERE code_hash <> '';
+
+ALTER TABLE users DROP COLUMN recovery_codes;
@@ -0,0 +1,5 @@
+-- Add migration script here
+ALTER TABLE user_tokens
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD COLUMN last_activity_at TIMESTAMPTZ,
+    ADD COLUMN last_ip TEXT;
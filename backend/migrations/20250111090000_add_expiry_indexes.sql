@@ -0,0 +1,5 @@
+-- Add migration script here
+
+-- Used by the maintenance to find the expired rows to purge
+CREATE INDEX user_tokens_expires_at_idx ON user_tokens (expires_at);
+CREATE INDEX mfa_challenges_expires_at_idx ON mfa_challenges (expires_at);
@@ -0,0 +1,47 @@
+-- Add migration script here
+
+-- What a user may do is granted through roles, each role bundling permissions. The
+-- permissions are the ones of the `Permission` enum.
+CREATE TABLE permissions (
+    name VARCHAR(64) PRIMARY KEY,
+    description TEXT NOT NULL
+);
+
+CREATE TABLE roles (
+    name VARCHAR(64) PRIMARY KEY,
+    description TEXT NOT NULL
+);
+
+CREATE TABLE role_permissions (
+    role VARCHAR(64) NOT NULL REFERENCES roles(name) ON DELETE CASCADE,
+    permission VARCHAR(64) NOT NULL REFERENCES permissions(name) ON DELETE CASCADE,
+    PRIMARY KEY (role, permission)
+);
+
+CREATE TABLE user_roles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(64) NOT NULL REFERENCES roles(name) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, role)
+);
+
+CREATE INDEX user_roles_role_idx ON user_roles (role);
+
+INSERT INTO permissions (name, description) VALUES
+    ('users:read', 'List users and view their account'),
+    ('users:write', 'Expire passwords and reset 2FA'),
+    ('sessions:read', 'View the sessions of users'),
+    ('sessions:write', 'Sign users out'),
+    ('roles:write', 'Grant and revoke roles'),
+    ('maintenance:run', 'Run the maintenance');
+
+INSERT INTO roles (name, description) VALUES
+    ('admin', 'Full access'),
+    ('support', 'Read-only access to user data');
+
+INSERT INTO role_permissions (role, permission)
+SELECT 'admin', name FROM permissions;
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('support', 'users:read'),
+    ('support', 'sessions:read');
@@ -0,0 +1,7 @@
+-- Add migration script here
+
+-- The admins keep their access through the admin role, which replaces the flag
+INSERT INTO user_roles (user_id, role)
+SELECT id, 'admin' FROM users WHERE is_admin;
+
+ALTER TABLE users DROP COLUMN is_admin;
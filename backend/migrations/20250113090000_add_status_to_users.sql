@@ -0,0 +1,8 @@
+-- Add migration script here
+
+-- A suspended or banned account is disabled until `status_until`, or until it is reactivated
+-- if no date is set.
+ALTER TABLE users ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'active'
+    CHECK (status IN ('active', 'suspended', 'banned'));
+ALTER TABLE users ADD COLUMN status_reason TEXT;
+ALTER TABLE users ADD COLUMN status_until TIMESTAMPTZ;
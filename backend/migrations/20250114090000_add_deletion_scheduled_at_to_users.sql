@@ -0,0 +1,8 @@
+-- Add migration script here
+
+-- Set when a user asks for their account to be deleted, which happens once this date is
+-- passed unless they log in again in the meantime
+ALTER TABLE users ADD COLUMN deletion_scheduled_at TIMESTAMPTZ;
+
+CREATE INDEX users_deletion_scheduled_at_idx ON users (deletion_scheduled_at)
+    WHERE deletion_scheduled_at IS NOT NULL;
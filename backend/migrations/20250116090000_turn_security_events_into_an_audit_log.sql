@@ -0,0 +1,33 @@
+-- Add migration script here
+
+-- The security events become the audit log of what happened to each account: who did it, from
+-- where, and whether it succeeded. The actor isn't a foreign key so that the events an admin
+-- caused are kept once the admin is deleted.
+ALTER TABLE security_events ADD COLUMN actor_id UUID;
+ALTER TABLE security_events ADD COLUMN outcome VARCHAR(16) NOT NULL DEFAULT 'success'
+    CHECK (outcome IN ('success', 'failure'));
+ALTER TABLE security_events ADD COLUMN ip TEXT;
+
+-- The events are listed from the most recent, page after page
+DROP INDEX security_events_user_id_idx;
+CREATE INDEX security_events_user_id_created_at_idx
+    ON security_events (user_id, created_at DESC, id DESC);
+CREATE INDEX security_events_created_at_idx ON security_events (created_at DESC, id DESC);
+
+-- Events can only be added, or deleted along with their user
+CREATE FUNCTION reject_security_event_update() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'security_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER security_events_append_only
+    BEFORE UPDATE ON security_events
+    FOR EACH ROW EXECUTE FUNCTION reject_security_event_update();
+
+INSERT INTO permissions (name, description) VALUES
+    ('security_events:read', 'View the security events of users');
+
+INSERT INTO role_permissions (role, permission) VALUES
+    ('admin', 'security_events:read'),
+    ('support', 'security_events:read');
@@ -0,0 +1,21 @@
+-- Add migration script here
+
+-- Events are written in the transaction of the change they describe, then delivered to the
+-- handlers by the dispatcher. They aren't tied to a user so that the events of a deleted
+-- account are still delivered.
+CREATE TABLE outbox_events (
+    id UUID PRIMARY KEY,
+    event_type VARCHAR(64) NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    available_at TIMESTAMPTZ NOT NULL,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+-- The dispatcher only looks at the events not delivered yet
+CREATE INDEX outbox_events_pending_idx ON outbox_events (available_at, created_at)
+    WHERE delivered_at IS NULL;
+CREATE INDEX outbox_events_delivered_at_idx ON outbox_events (delivered_at)
+    WHERE delivered_at IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Add migration script here
+
+-- The key holds the username the client sent, whose length isn't checked before the attempt
+-- is counted
+ALTER TABLE login_attempts ALTER COLUMN key TYPE TEXT;
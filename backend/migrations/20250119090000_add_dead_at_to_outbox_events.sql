@@ -0,0 +1,9 @@
+-- Add migration script here
+
+-- Events still failing after the maximum number of attempts are no longer retried. They are
+-- kept, with their last error, until someone looks into them.
+ALTER TABLE outbox_events ADD COLUMN dead_at TIMESTAMPTZ;
+
+DROP INDEX outbox_events_pending_idx;
+CREATE INDEX outbox_events_pending_idx ON outbox_events (available_at, created_at)
+    WHERE delivered_at IS NULL AND dead_at IS NULL;
@@ -0,0 +1,5 @@
+-- Add migration script here
+
+-- The archives are now stored encrypted. The ones built before were stored in plaintext and
+-- still hold the 2FA secret, so they are dropped and have to be requested again.
+UPDATE data_exports SET status = 'failed', archive = NULL WHERE archive IS NOT NULL;
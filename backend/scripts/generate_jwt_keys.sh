@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+# Generates the JWT keys the local and docker configurations sign with. They are not
+# committed, and existing keys are kept so the tokens already issued stay valid.
+set -euo pipefail
+
+keys_directory="$(dirname "$0")/../configuration/keys"
+mkdir -p "$keys_directory"
+cd "$keys_directory"
+
+if [ ! -f local-ed25519.pem ]; then
+  openssl genpkey -algorithm ed25519 -out local-ed25519.pem
+  openssl pkey -in local-ed25519.pem -pubout -out local-ed25519.pub.pem
+fi
+
+if [ ! -f local-rsa.pem ]; then
+  openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048 -out local-rsa.pem
+  openssl pkey -in local-rsa.pem -pubout -out local-rsa.pub.pem
+fi
@@ -7,6 +7,18 @@ use std::convert::{TryFrom, TryInto};
 pub struct Settings {
     pub database: DatabaseSettings,
     pub application: ApplicationSettings,
+    pub jwt: JwtSettings,
+    pub login_throttling: LoginThrottlingSettings,
+    pub rate_limits: RateLimitsSettings,
+    pub otp: OtpSettings,
+    pub encryption: EncryptionSettings,
+    pub session_activity: SessionActivitySettings,
+    pub token_cache: TokenCacheSettings,
+    pub maintenance: MaintenanceSettings,
+    pub account_deletion: AccountDeletionSettings,
+    pub data_export: DataExportSettings,
+    pub new_device_alerts: NewDeviceAlertSettings,
+    pub events: EventSettings,
 }
 
 #[derive(serde::Deserialize, Clone)]
@@ -14,7 +26,248 @@ pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
-    pub secret: String,
+    pub token_issuer: String,
+    pub token_audience: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub access_token_lifetime_minutes: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub refresh_token_lifetime_days: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub mfa_challenge_lifetime_seconds: i64,
+}
+
+/// Keys used to sign and verify JWTs.
+///
+/// Tokens are always signed with `signing_key_id`; every configured key is accepted for
+/// verification and published in the JWKS. To rotate, add the new key, point `signing_key_id`
+/// at it, and drop the private key of the previous one once it has no live tokens left.
+#[derive(serde::Deserialize, Clone)]
+pub struct JwtSettings {
+    pub signing_key_id: String,
+    pub keys: Vec<JwtKeySettings>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct JwtKeySettings {
+    pub id: String,
+    pub algorithm: jsonwebtoken::Algorithm,
+    pub public_key_path: String,
+    // Verification-only keys (e.g. retired signing keys) don't need a private key
+    pub private_key_path: Option<String>,
+}
+
+/// Failed login, OTP and recovery attempts allowed per account and per client IP within
+/// `window_seconds`, before the account or IP is locked out for `lockout_seconds`.
+#[derive(serde::Deserialize, Clone)]
+pub struct LoginThrottlingSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub account_max_attempts: i32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub ip_max_attempts: i32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub window_seconds: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lockout_seconds: i64,
+}
+
+/// Token-bucket limits applied to the route scopes wrapped in a `RateLimiter`.
+#[derive(serde::Deserialize, Clone)]
+pub struct RateLimitsSettings {
+    pub auth: RateLimitSettings,
+    pub is_otp_enabled: RateLimitSettings,
+    pub users: RateLimitSettings,
+    pub devices: RateLimitSettings,
+}
+
+/// Each client can send `burst` requests at once, then `requests_per_minute` on average.
+#[derive(serde::Deserialize, Clone)]
+pub struct RateLimitSettings {
+    pub key: RateLimitKey,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub burst: u32,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub requests_per_minute: u32,
+}
+
+impl RateLimitsSettings {
+    /// A bucket that never holds or regains a token would reject every request.
+    pub fn validate(&self) -> Result<(), String> {
+        let scopes = [
+            ("auth", &self.auth),
+            ("is_otp_enabled", &self.is_otp_enabled),
+            ("users", &self.users),
+            ("devices", &self.devices),
+        ];
+
+        for (scope, settings) in scopes {
+            if settings.burst == 0 || settings.requests_per_minute == 0 {
+                return Err(format!(
+                    "rate_limits.{}: burst and requests_per_minute must be greater than 0",
+                    scope
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    Ip,
+    // Falls back to the client IP on requests without validated claims
+    User,
+}
+
+/// TOTP parameters. Changing the algorithm, digits or step invalidates the authenticator apps
+/// already set up, `skew` is the number of steps accepted on each side of the current one.
+#[derive(serde::Deserialize, Clone)]
+pub struct OtpSettings {
+    pub algorithm: OtpAlgorithm,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub digits: usize,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub skew: u8,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub step_seconds: u64,
+}
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OtpAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl From<OtpAlgorithm> for totp_rs::Algorithm {
+    fn from(algorithm: OtpAlgorithm) -> Self {
+        match algorithm {
+            OtpAlgorithm::Sha1 => totp_rs::Algorithm::SHA1,
+            OtpAlgorithm::Sha256 => totp_rs::Algorithm::SHA256,
+            OtpAlgorithm::Sha512 => totp_rs::Algorithm::SHA512,
+        }
+    }
+}
+
+/// Keys used to encrypt secrets at rest. New values are encrypted with `current_key_version`,
+/// the other keys are kept to decrypt values that were not re-encrypted yet.
+#[derive(serde::Deserialize, Clone)]
+pub struct EncryptionSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub current_key_version: u32,
+    pub keys: Vec<EncryptionKeySettings>,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct EncryptionKeySettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub version: u32,
+    // Base64 encoded 256-bit key
+    pub key: Secret<String>,
+}
+
+/// Last activity and IP of each session. A session's activity is recorded at most once every
+/// `debounce_seconds`, and the recorded activity is written every `flush_interval_seconds` in
+/// batches of `batch_size` sessions.
+#[derive(serde::Deserialize, Clone)]
+pub struct SessionActivitySettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub debounce_seconds: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub flush_interval_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub batch_size: usize,
+}
+
+/// Where the validated access tokens are cached. With several instances, the `postgres`
+/// backend is needed for a revoked token to be rejected by all of them. Each instance caches
+/// at most `max_entries` tokens, and evicts the expired ones every `sweep_interval_seconds`.
+#[derive(serde::Deserialize, Clone)]
+pub struct TokenCacheSettings {
+    pub backend: TokenCacheBackendKind,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_entries: usize,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub sweep_interval_seconds: u64,
+}
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenCacheBackendKind {
+    // Only invalidates the tokens of the current instance
+    Memory,
+    Postgres,
+}
+
+/// Stale data, such as expired sessions, is purged every `interval_seconds`, deleting at most
+/// `batch_size` rows per query.
+#[derive(serde::Deserialize, Clone)]
+pub struct MaintenanceSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub interval_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub batch_size: i64,
+}
+
+/// An account is deleted `grace_period_days` after the user asked for it, the user can cancel
+/// the deletion by logging in again until then.
+#[derive(serde::Deserialize, Clone)]
+pub struct AccountDeletionSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub grace_period_days: i64,
+}
+
+/// An export of the data of a user can be downloaded once, within `lifetime_hours` after it
+/// was requested. An export still being built after `build_timeout_minutes` is considered
+/// failed, and the user can request a new one.
+#[derive(serde::Deserialize, Clone)]
+pub struct DataExportSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lifetime_hours: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub build_timeout_minutes: i64,
+}
+
+/// A sign-in from a device the user never used before is sent to the `notifier`, along with
+/// a link that revokes the session and stays valid for `report_link_lifetime_hours`. The
+/// `file` notifier appends the sign-ins to `outbox_path`, one JSON object per line.
+#[derive(serde::Deserialize, Clone)]
+pub struct NewDeviceAlertSettings {
+    pub notifier: NotifierKind,
+    pub outbox_path: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub report_link_lifetime_hours: i64,
+}
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifierKind {
+    // Only writes the sign-ins to the logs
+    Log,
+    File,
+}
+
+/// Events waiting in the outbox are dispatched every `dispatch_interval_seconds`, at most
+/// `batch_size` at a time. A batch is hidden from the other instances for `lease_seconds`
+/// while it is delivered, and an event that failed is retried after `retry_delay_seconds`
+/// times the number of attempts so far, up to `max_retry_delay_seconds`. An event is given up
+/// on after `max_attempts`.
+#[derive(serde::Deserialize, Clone)]
+pub struct EventSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub dispatch_interval_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub batch_size: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lease_seconds: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub retry_delay_seconds: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_retry_delay_seconds: i64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_attempts: i32,
 }
 
 #[derive(serde::Deserialize, Clone)]
@@ -75,7 +328,13 @@ pub fn get_configuration() -> Result<Settings, config::ConfigError> {
         )
         .build()?;
 
-    settings.try_deserialize::<Settings>()
+    let settings = settings.try_deserialize::<Settings>()?;
+    settings
+        .rate_limits
+        .validate()
+        .map_err(config::ConfigError::Message)?;
+
+    Ok(settings)
 }
 
 /// The possible runtime environment for our application.
@@ -1,10 +1,12 @@
 use crate::core::structs::responses::GenericResponse;
 
 pub enum AppError {
+    AccountDisabled,
     AccessTokenExpired,
     DatabaseConnection,
     DatabaseQuery,
     DatabaseTransaction,
+    Forbidden,
     InvalidAccessToken,
     InvalidOneTimePassword,
     InvalidRefreshToken,
@@ -16,6 +18,8 @@ pub enum AppError {
     PasswordTooShort,
     PasswordTooWeak,
     TokenGeneration,
+    TooManyAttempts,
+    TooManyRequests,
     TwoFactorAuthenticationNotEnabled,
     UsernameNotRespectingRules,
     UsernameWrongSize,
@@ -27,6 +31,10 @@ pub enum AppError {
 impl AppError {
     pub fn to_response(&self) -> GenericResponse {
         match self {
+            AppError::AccountDisabled => GenericResponse {
+                code: "ACCOUNT_DISABLED".to_string(),
+                message: "This account has been disabled".to_string(),
+            },
             AppError::AccessTokenExpired => GenericResponse {
                 code: "ACCESS_TOKEN_EXPIRED".to_string(),
                 message: "Token expired".to_string(),
@@ -43,6 +51,10 @@ impl AppError {
                 code: "DATABASE_TRANSACTION".to_string(),
                 message: "Failed to commit transaction".to_string(),
             },
+            AppError::Forbidden => GenericResponse {
+                code: "FORBIDDEN".to_string(),
+                message: "Not allowed to access this resource".to_string(),
+            },
             AppError::InvalidAccessToken => GenericResponse {
                 code: "INVALID_ACCESS_TOKEN".to_string(),
                 message: "Invalid access token".to_string(),
@@ -87,6 +99,14 @@ impl AppError {
                 code: "TOKEN_GENERATION".to_string(),
                 message: "Failed to generate and save token".to_string(),
             },
+            AppError::TooManyAttempts => GenericResponse {
+                code: "TOO_MANY_ATTEMPTS".to_string(),
+                message: "Too many attempts, please try again later".to_string(),
+            },
+            AppError::TooManyRequests => GenericResponse {
+                code: "TOO_MANY_REQUESTS".to_string(),
+                message: "Too many requests, please slow down".to_string(),
+            },
             AppError::TwoFactorAuthenticationNotEnabled => GenericResponse {
                 code: "TWO_FACTOR_AUTHENTICATION_NOT_ENABLED".to_string(),
                 message: "Two factor authentication is not enabled".to_string(),
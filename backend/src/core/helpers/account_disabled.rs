@@ -0,0 +1,7 @@
+use actix_web::HttpResponse;
+
+use crate::core::constants::errors::AppError;
+
+pub fn account_disabled_response() -> HttpResponse {
+    HttpResponse::Forbidden().json(AppError::AccountDisabled.to_response())
+}
@@ -0,0 +1,9 @@
+use actix_web::HttpRequest;
+
+/// The address of the peer the request came from.
+///
+/// Forwarding headers are deliberately ignored since any client can set them, and they would
+/// let an attacker pick the address its attempts are throttled against.
+pub fn get_client_ip(req: &HttpRequest) -> Option<String> {
+    req.peer_addr().map(|addr| addr.ip().to_string())
+}
@@ -0,0 +1,29 @@
+use sqlx::migrate::MigrateError;
+use sqlx::PgPool;
+
+use crate::core::structs::envelope_cipher::EnvelopeCipher;
+use crate::features::auth::infrastructure::otp_secrets::{
+    encrypt_otp_secrets, OtpSecretMigrationError,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("Failed to run the migrations: {0}")]
+    Schema(#[from] MigrateError),
+    #[error(transparent)]
+    OtpSecrets(#[from] OtpSecretMigrationError),
+}
+
+/// Runs the schema migrations, then the data migrations needing the application keys, which
+/// SQL alone can't do. Both steps are idempotent and the server only starts once they succeeded.
+pub async fn run_migrations(pool: &PgPool, cipher: &EnvelopeCipher) -> Result<(), MigrationError> {
+    sqlx::migrate!("./migrations").run(pool).await?;
+
+    // Secrets stored before encryption was enabled, or with a retired key
+    let encrypted = encrypt_otp_secrets(pool, cipher).await?;
+    if encrypted > 0 {
+        tracing::info!("Encrypted the OTP secrets of {} users", encrypted);
+    }
+
+    Ok(())
+}
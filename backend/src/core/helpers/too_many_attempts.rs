@@ -0,0 +1,10 @@
+use actix_web::http::header;
+use actix_web::HttpResponse;
+
+use crate::core::constants::errors::AppError;
+
+pub fn too_many_attempts_response(retry_after_seconds: i64) -> HttpResponse {
+    HttpResponse::TooManyRequests()
+        .insert_header((header::RETRY_AFTER, retry_after_seconds.to_string()))
+        .json(AppError::TooManyAttempts.to_response())
+}
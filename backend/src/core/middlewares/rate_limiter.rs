@@ -0,0 +1,114 @@
+use crate::configuration::{RateLimitKey, RateLimitSettings};
+use crate::core::constants::errors::AppError;
+use crate::core::helpers::client_ip::get_client_ip;
+use crate::core::helpers::mock_now::now;
+use crate::core::structs::rate_limits::RateLimitStore;
+use crate::features::auth::domain::entities::Claims;
+use actix_web::body::EitherBody;
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use actix_web::{HttpMessage, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+/// Token-bucket rate limiting for a scope. Buckets are kept per client and per `name`, so
+/// two scopes wrapped with different names never share a budget.
+///
+/// To key by user, wrap it before `TokenValidator` so the claims are already validated
+/// when it runs.
+pub struct RateLimiter {
+    name: &'static str,
+    settings: RateLimitSettings,
+}
+
+impl RateLimiter {
+    pub fn new(name: &'static str, settings: RateLimitSettings) -> Self {
+        Self { name, settings }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimiterMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimiterMiddleware {
+            service: Rc::new(service),
+            name: self.name,
+            settings: self.settings.clone(),
+        }))
+    }
+}
+
+pub struct RateLimiterMiddleware<S> {
+    service: Rc<S>,
+    name: &'static str,
+    settings: RateLimitSettings,
+}
+
+impl<S> RateLimiterMiddleware<S> {
+    fn client_key(&self, req: &ServiceRequest) -> Option<String> {
+        if self.settings.key == RateLimitKey::User {
+            if let Some(claims) = req.extensions().get::<Claims>() {
+                return Some(format!("{}:user:{}", self.name, claims.user_id));
+            }
+        }
+
+        get_client_ip(req.request()).map(|ip| format!("{}:ip:{}", self.name, ip))
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        let store = req.app_data::<Data<RateLimitStore>>().unwrap().clone();
+        let client_key = self.client_key(&req);
+        let capacity = f64::from(self.settings.burst);
+        let refill_per_second = f64::from(self.settings.requests_per_minute) / 60.0;
+
+        Box::pin(async move {
+            // Without a client to attribute the request to, there is no bucket to take from
+            if let Some(client_key) = client_key {
+                if let Err(retry_after) = store
+                    .try_acquire(client_key, capacity, refill_per_second, now())
+                    .await
+                {
+                    return Ok(req.into_response(
+                        HttpResponse::TooManyRequests()
+                            .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+                            .json(AppError::TooManyRequests.to_response())
+                            .map_into_right_body(),
+                    ));
+                }
+            }
+
+            let res = service.call(req).await?;
+            Ok(res.map_into_left_body())
+        })
+    }
+}
@@ -1,6 +1,12 @@
 use crate::core::constants::errors::AppError;
+use crate::core::helpers::client_ip::get_client_ip;
 use crate::core::helpers::mock_now::now;
+use crate::features::auth::helpers::account_status::is_account_disabled;
 use crate::features::auth::helpers::token::{get_user_token, retrieve_claims_for_token};
+use crate::features::auth::infrastructure::repositories::TokenServiceImpl;
+use crate::features::auth::infrastructure::session_activity::{
+    SessionActivity, SessionActivityWriter,
+};
 use crate::features::auth::structs::models::TokenCache;
 use actix_web::body::EitherBody;
 use actix_web::web::Data;
@@ -9,7 +15,7 @@ use actix_web::{
     Error,
 };
 use actix_web::{HttpMessage, HttpResponse};
-use chrono::{DateTime, Duration, Utc};
+use chrono::{DateTime, Utc};
 use futures_util::future::LocalBoxFuture;
 use sqlx::PgPool;
 use std::future::{ready, Ready};
@@ -56,14 +62,20 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
 
-        let secret = req.app_data::<Data<String>>().unwrap().to_string();
+        let token_service = req.app_data::<Data<TokenServiceImpl>>().unwrap().clone();
         let pool = req.app_data::<Data<PgPool>>().unwrap().clone();
         let cached_tokens = req.app_data::<Data<TokenCache>>().unwrap().clone();
+        let session_activity_writer = req
+            .app_data::<Data<SessionActivityWriter>>()
+            .unwrap()
+            .clone();
+        let client_ip = get_client_ip(req.request());
 
         Box::pin(async move {
-            match retrieve_claims_for_token(req.request().clone(), secret) {
+            match retrieve_claims_for_token(req.request().clone(), token_service.get_ref()) {
                 Ok(claims) => {
-                    if now() > DateTime::<Utc>::from_timestamp(claims.exp, 0).unwrap() {
+                    let expires_at = DateTime::<Utc>::from_timestamp(claims.exp, 0).unwrap();
+                    if now() > expires_at {
                         return Ok(req.into_response(
                             HttpResponse::Unauthorized()
                                 .json(AppError::AccessTokenExpired.to_response())
@@ -73,13 +85,20 @@ where
 
                     let last_activity_for_this_token =
                         cached_tokens.get_value_for_key(claims.jti).await;
+                    let activity = SessionActivity {
+                        last_activity_at: now(),
+                        last_ip: client_ip,
+                    };
 
                     match last_activity_for_this_token {
                         Some(last_activity) => {
-                            // Update only if last activity is more than 5 minutes ago
+                            // Update only once per debounce period
                             // To not update too often
-                            if now() - Duration::minutes(5) > last_activity {
-                                cached_tokens.update_or_insert_key(claims.jti, now()).await;
+                            if now() - session_activity_writer.debounce() > last_activity {
+                                cached_tokens
+                                    .update_or_insert_key(claims.jti, now(), expires_at)
+                                    .await;
+                                session_activity_writer.record(claims.jti, activity).await;
                             }
                         }
                         None => {
@@ -107,7 +126,31 @@ where
                                 }
                             };
 
-                            cached_tokens.update_or_insert_key(claims.jti, now()).await;
+                            // A suspension revokes the tokens, but the status may also have
+                            // been changed without going through the admin API
+                            match is_account_disabled(&**pool, claims.user_id, now()).await {
+                                Ok(false) => {}
+                                Ok(true) => {
+                                    return Ok(req.into_response(
+                                        HttpResponse::Forbidden()
+                                            .json(AppError::AccountDisabled.to_response())
+                                            .map_into_right_body(),
+                                    ));
+                                }
+                                Err(e) => {
+                                    error!("Error: {}", e);
+                                    return Ok(req.into_response(
+                                        HttpResponse::InternalServerError()
+                                            .json(AppError::DatabaseQuery.to_response())
+                                            .map_into_right_body(),
+                                    ));
+                                }
+                            }
+
+                            cached_tokens
+                                .update_or_insert_key(claims.jti, now(), expires_at)
+                                .await;
+                            session_activity_writer.record(claims.jti, activity).await;
                         }
                     }
 
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use secrecy::ExposeSecret;
+
+use crate::configuration::EncryptionSettings;
+
+const NONCE_LENGTH: usize = 12;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EnvelopeCipherError {
+    #[error("Invalid encryption key {0}: {1}")]
+    InvalidKey(u32, String),
+    #[error("Encryption key {0} is configured more than once")]
+    DuplicateKeyVersion(u32),
+    #[error("Encryption key {0} is not configured")]
+    UnknownKeyVersion(u32),
+    #[error("Malformed encrypted value")]
+    MalformedValue,
+    #[error("Failed to encrypt the value")]
+    EncryptionFailed,
+    #[error("Failed to decrypt the value")]
+    DecryptionFailed,
+}
+
+/// Envelope encryption with AES-256-GCM. Each value is encrypted with its own random data key,
+/// which is itself encrypted with a versioned key from the configuration. Values are stored as
+/// `v{key_version}:{encrypted_data_key}:{encrypted_value}`, so rotating the configured key only
+/// requires re-encrypting the data keys.
+#[derive(Clone)]
+pub struct EnvelopeCipher {
+    current_key_version: u32,
+    keys: HashMap<u32, Aes256Gcm>,
+}
+
+impl EnvelopeCipher {
+    pub fn new(settings: &EncryptionSettings) -> Result<Self, EnvelopeCipherError> {
+        let mut keys = HashMap::new();
+
+        for key in &settings.keys {
+            if keys.contains_key(&key.version) {
+                return Err(EnvelopeCipherError::DuplicateKeyVersion(key.version));
+            }
+
+            let bytes = STANDARD
+                .decode(key.key.expose_secret())
+                .map_err(|e| EnvelopeCipherError::InvalidKey(key.version, e.to_string()))?;
+            let cipher = Aes256Gcm::new_from_slice(&bytes).map_err(|_| {
+                EnvelopeCipherError::InvalidKey(key.version, "expected 32 bytes".to_string())
+            })?;
+
+            keys.insert(key.version, cipher);
+        }
+
+        if !keys.contains_key(&settings.current_key_version) {
+            return Err(EnvelopeCipherError::UnknownKeyVersion(
+                settings.current_key_version,
+            ));
+        }
+
+        Ok(Self {
+            current_key_version: settings.current_key_version,
+            keys,
+        })
+    }
+
+    /// Encrypts `plaintext` with the current key. The `associated_data` is authenticated but
+    /// not stored, the same has to be given to decrypt the value.
+    pub fn encrypt(
+        &self,
+        plaintext: &str,
+        associated_data: &[u8],
+    ) -> Result<String, EnvelopeCipherError> {
+        let data_key = Aes256Gcm::generate_key(&mut OsRng);
+        let data_cipher = Aes256Gcm::new(&data_key);
+
+        let encrypted_value = seal(&data_cipher, plaintext.as_bytes(), associated_data)?;
+        let encrypted_data_key = seal(self.current_key(), data_key.as_slice(), &[])?;
+
+        Ok(format!(
+            "v{}:{}:{}",
+            self.current_key_version,
+            URL_SAFE_NO_PAD.encode(encrypted_data_key),
+            URL_SAFE_NO_PAD.encode(encrypted_value)
+        ))
+    }
+
+    pub fn decrypt(
+        &self,
+        value: &str,
+        associated_data: &[u8],
+    ) -> Result<String, EnvelopeCipherError> {
+        let (key_version, encrypted_data_key, encrypted_value) = parse(value)?;
+
+        let data_key = open(self.key(key_version)?, &encrypted_data_key, &[])?;
+        let data_cipher = Aes256Gcm::new_from_slice(&data_key)
+            .map_err(|_| EnvelopeCipherError::MalformedValue)?;
+        let plaintext = open(&data_cipher, &encrypted_value, associated_data)?;
+
+        String::from_utf8(plaintext).map_err(|_| EnvelopeCipherError::MalformedValue)
+    }
+
+    /// Re-encrypts the data key of a value with the current key, the value itself is kept.
+    pub fn rewrap(&self, value: &str) -> Result<String, EnvelopeCipherError> {
+        let (key_version, encrypted_data_key, encrypted_value) = parse(value)?;
+
+        let data_key = open(self.key(key_version)?, &encrypted_data_key, &[])?;
+        let encrypted_data_key = seal(self.current_key(), &data_key, &[])?;
+
+        Ok(format!(
+            "v{}:{}:{}",
+            self.current_key_version,
+            URL_SAFE_NO_PAD.encode(encrypted_data_key),
+            URL_SAFE_NO_PAD.encode(encrypted_value)
+        ))
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        parse(value).is_ok()
+    }
+
+    pub fn is_encrypted_with_current_key(&self, value: &str) -> bool {
+        matches!(parse(value), Ok((key_version, _, _)) if key_version == self.current_key_version)
+    }
+
+    fn current_key(&self) -> &Aes256Gcm {
+        &self.keys[&self.current_key_version]
+    }
+
+    fn key(&self, key_version: u32) -> Result<&Aes256Gcm, EnvelopeCipherError> {
+        self.keys
+            .get(&key_version)
+            .ok_or(EnvelopeCipherError::UnknownKeyVersion(key_version))
+    }
+}
+
+// The random nonce is prepended to the ciphertext
+fn seal(cipher: &Aes256Gcm, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, EnvelopeCipherError> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg, aad })
+        .map_err(|_| EnvelopeCipherError::EncryptionFailed)?;
+
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+fn open(cipher: &Aes256Gcm, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>, EnvelopeCipherError> {
+    if sealed.len() < NONCE_LENGTH {
+        return Err(EnvelopeCipherError::MalformedValue);
+    }
+    let (nonce, msg) = sealed.split_at(NONCE_LENGTH);
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg, aad })
+        .map_err(|_| EnvelopeCipherError::DecryptionFailed)
+}
+
+fn parse(value: &str) -> Result<(u32, Vec<u8>, Vec<u8>), EnvelopeCipherError> {
+    let mut parts = value.splitn(3, ':');
+
+    let (Some(key_version), Some(encrypted_data_key), Some(encrypted_value)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(EnvelopeCipherError::MalformedValue);
+    };
+
+    let key_version = key_version
+        .strip_prefix('v')
+        .and_then(|version| version.parse().ok())
+        .ok_or(EnvelopeCipherError::MalformedValue)?;
+    let encrypted_data_key = URL_SAFE_NO_PAD
+        .decode(encrypted_data_key)
+        .map_err(|_| EnvelopeCipherError::MalformedValue)?;
+    let encrypted_value = URL_SAFE_NO_PAD
+        .decode(encrypted_value)
+        .map_err(|_| EnvelopeCipherError::MalformedValue)?;
+
+    Ok((key_version, encrypted_data_key, encrypted_value))
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::core::structs::transaction::TransactionError;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::SecurityEvent;
+use crate::features::auth::domain::entities::{
+    LoginAttempts, MfaChallenge, Permission, RecoveryCode, User, UserToken,
+};
+use crate::features::events::domain::entities::OutboxMessage;
+use crate::features::profile::domain::entities::DataExport;
+
+#[derive(Error, Debug)]
+#[error("Writes to {0} are rejected")]
+pub struct WriteRejected(pub String);
+
+#[derive(Debug, Clone)]
+pub struct DataExportRow {
+    pub export: DataExport,
+    pub archive: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxEventRow {
+    pub message: OutboxMessage,
+    pub last_error: Option<String>,
+    pub available_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub dead_at: Option<DateTime<Utc>>,
+}
+
+/// The rows of the tables the repositories use. The users and their sessions are shared by
+/// the features, each repository only sees what its own queries would select.
+#[derive(Debug, Clone)]
+pub struct Tables {
+    pub users: Vec<User>,
+    pub role_permissions: Vec<(String, Permission)>,
+    pub user_roles: Vec<(Uuid, String)>,
+    pub user_tokens: Vec<UserToken>,
+    pub recovery_codes: Vec<RecoveryCode>,
+    pub mfa_challenges: Vec<MfaChallenge>,
+    pub login_attempts: Vec<LoginAttempts>,
+    pub security_events: Vec<SecurityEvent>,
+    pub data_exports: Vec<DataExportRow>,
+    pub outbox_events: Vec<OutboxEventRow>,
+}
+
+impl Default for Tables {
+    // The roles are the ones created by the migrations
+    fn default() -> Self {
+        let admin = [
+            Permission::ReadUsers,
+            Permission::WriteUsers,
+            Permission::ReadSessions,
+            Permission::WriteSessions,
+            Permission::WriteRoles,
+            Permission::RunMaintenance,
+            Permission::ReadSecurityEvents,
+        ];
+        let support = [
+            Permission::ReadUsers,
+            Permission::ReadSessions,
+            Permission::ReadSecurityEvents,
+        ];
+
+        Self {
+            users: Vec::new(),
+            role_permissions: admin
+                .into_iter()
+                .map(|permission| ("admin".to_string(), permission))
+                .chain(
+                    support
+                        .into_iter()
+                        .map(|permission| ("support".to_string(), permission)),
+                )
+                .collect(),
+            user_roles: Vec::new(),
+            user_tokens: Vec::new(),
+            recovery_codes: Vec::new(),
+            mfa_challenges: Vec::new(),
+            login_attempts: Vec::new(),
+            security_events: Vec::new(),
+            data_exports: Vec::new(),
+            outbox_events: Vec::new(),
+        }
+    }
+}
+
+impl Tables {
+    /// Deletes the user along with the rows that reference it, as the foreign keys do.
+    pub fn delete_user(&mut self, user_id: Uuid) {
+        self.users.retain(|user| user.id != user_id);
+        self.user_roles.retain(|(id, _)| *id != user_id);
+        self.user_tokens.retain(|token| token.user_id != user_id);
+        self.recovery_codes.retain(|code| code.user_id != user_id);
+        self.mfa_challenges
+            .retain(|challenge| challenge.user_id != user_id);
+        self.security_events
+            .retain(|event| event.user_id != user_id);
+        self.data_exports
+            .retain(|row| row.export.user_id != user_id);
+    }
+}
+
+/// Stands in for Postgres in the tests of the use cases. Clones share the same tables, like
+/// the clones of a pool share the same database.
+#[derive(Clone, Default)]
+pub struct InMemoryDatabase {
+    tables: Arc<Mutex<Tables>>,
+    rejected_writes: Arc<Mutex<HashSet<String>>>,
+    // Tables as they were when the outermost unit of work started
+    snapshot: Arc<Mutex<Option<Tables>>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The tables, to read them or to set them up in a test.
+    pub fn tables(&self) -> MutexGuard<'_, Tables> {
+        self.tables.lock().unwrap()
+    }
+
+    /// The tables, unless the test made writes to `table` fail.
+    pub fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, WriteRejected> {
+        if self.rejected_writes.lock().unwrap().contains(table) {
+            return Err(WriteRejected(table.to_string()));
+        }
+
+        Ok(self.tables())
+    }
+
+    /// Makes the writes to `table` fail, as if the database failed midway through a use case.
+    pub fn reject_writes_to(&self, table: &str) {
+        self.rejected_writes
+            .lock()
+            .unwrap()
+            .insert(table.to_string());
+    }
+
+    pub fn accept_writes_to(&self, table: &str) {
+        self.rejected_writes.lock().unwrap().remove(table);
+    }
+}
+
+/// Restores the tables as they were before a unit of work that failed. The work isn't
+/// isolated from the one of other tasks, which the tests don't run concurrently.
+#[derive(Clone)]
+pub struct InMemoryUnitOfWork {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryUnitOfWork {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    async fn scope<'a>(&self, work: BoxFuture<'a, bool>) -> Result<(), TransactionError> {
+        let outermost = {
+            let mut snapshot = self.database.snapshot.lock().unwrap();
+            if snapshot.is_none() {
+                *snapshot = Some(self.database.tables().clone());
+                true
+            } else {
+                false
+            }
+        };
+
+        let succeeded = work.await;
+        if !outermost {
+            return Ok(());
+        }
+
+        let snapshot = self.database.snapshot.lock().unwrap().take();
+        if let (false, Some(mut snapshot)) = (succeeded, snapshot) {
+            let mut tables = self.database.tables();
+            // Their Postgres repositories don't join the transaction
+            snapshot.login_attempts = std::mem::take(&mut tables.login_attempts);
+            snapshot.security_events = std::mem::take(&mut tables.security_events);
+            snapshot.data_exports = std::mem::take(&mut tables.data_exports);
+            *tables = snapshot;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use crate::core::helpers::mock_now::now;
+
+// Past this many tracked clients, the least recently seen one is dropped
+pub const MAX_TRACKED_BUCKETS: usize = 10_000;
+
+struct TokenBucket {
+    tokens: f64,
+    updated_at: DateTime<Utc>,
+    full_at: DateTime<Utc>,
+    recency: u64,
+}
+
+#[derive(Default)]
+struct Buckets {
+    by_key: HashMap<String, TokenBucket>,
+    by_recency: BTreeMap<u64, String>,
+    next_recency: u64,
+}
+
+/// Token buckets of every rate limited client, shared by all the workers. Holds at most
+/// `max_buckets` buckets, the least recently used one being dropped to make room.
+#[derive(Clone)]
+pub struct RateLimitStore {
+    max_buckets: usize,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl Default for RateLimitStore {
+    fn default() -> Self {
+        Self::new(MAX_TRACKED_BUCKETS)
+    }
+}
+
+impl RateLimitStore {
+    pub fn new(max_buckets: usize) -> Self {
+        Self {
+            max_buckets: max_buckets.max(1),
+            buckets: Arc::default(),
+        }
+    }
+
+    /// Takes a token from the bucket of `key`, or returns the number of seconds until one
+    /// becomes available.
+    pub async fn try_acquire(
+        &self,
+        key: String,
+        capacity: f64,
+        refill_per_second: f64,
+        now: DateTime<Utc>,
+    ) -> Result<(), i64> {
+        let mut buckets = self.buckets.lock().await;
+        let Buckets {
+            by_key,
+            by_recency,
+            next_recency,
+        } = &mut *buckets;
+
+        let recency = *next_recency;
+        *next_recency += 1;
+
+        if !by_key.contains_key(&key) {
+            while by_key.len() >= self.max_buckets {
+                let Some((_, least_recently_used)) = by_recency.pop_first() else {
+                    break;
+                };
+                by_key.remove(&least_recently_used);
+            }
+        }
+
+        let bucket = by_key.entry(key.clone()).or_insert(TokenBucket {
+            tokens: capacity,
+            updated_at: now,
+            full_at: now,
+            recency,
+        });
+        by_recency.remove(&bucket.recency);
+        by_recency.insert(recency, key);
+        bucket.recency = recency;
+
+        let elapsed = (now - bucket.updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_second).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens < 1.0 {
+            let retry_after = ((1.0 - bucket.tokens) / refill_per_second).ceil() as i64;
+            return Err(retry_after.max(1));
+        }
+
+        bucket.tokens -= 1.0;
+        bucket.full_at = now
+            + Duration::milliseconds(
+                ((capacity - bucket.tokens) / refill_per_second * 1000.0).ceil() as i64,
+            );
+
+        Ok(())
+    }
+
+    /// Drops the buckets that have refilled completely at `now`, which behave like a new
+    /// bucket. Returns the number dropped.
+    pub async fn remove_full(&self, now: DateTime<Utc>) -> usize {
+        let mut buckets = self.buckets.lock().await;
+        let Buckets {
+            by_key, by_recency, ..
+        } = &mut *buckets;
+        let before = by_key.len();
+
+        by_key.retain(|_, bucket| {
+            let is_live = bucket.full_at > now;
+            if !is_live {
+                by_recency.remove(&bucket.recency);
+            }
+            is_live
+        });
+
+        before - by_key.len()
+    }
+
+    pub async fn tracked_buckets(&self) -> usize {
+        self.buckets.lock().await.by_key.len()
+    }
+
+    /// Drops the full buckets every `interval`.
+    pub fn spawn_sweeper(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+                let removed = self.remove_full(now()).await;
+                tracing::debug!(removed, "Swept the rate limit buckets");
+            }
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use thiserror::Error;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::core::structs::unit_of_work::UnitOfWork;
+
+tokio::task_local! {
+    // Transaction of the unit of work being run
+    static CURRENT_TRANSACTION: Arc<Mutex<Transaction<'static, Postgres>>>;
+}
+
+#[derive(Error, Debug)]
+pub enum TransactionError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("The transaction is still borrowed after the work")]
+    StillBorrowed,
+}
+
+/// Connection to run a query on: the one of the current transaction, or one of the pool
+/// outside of a transaction.
+pub enum Connection {
+    Pooled(Box<PoolConnection<Postgres>>),
+    Transaction(OwnedMutexGuard<Transaction<'static, Postgres>>),
+}
+
+impl Deref for Connection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            Connection::Pooled(connection) => connection,
+            Connection::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+impl DerefMut for Connection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            Connection::Pooled(connection) => connection,
+            Connection::Transaction(transaction) => transaction,
+        }
+    }
+}
+
+/// Returns the connection of the transaction the caller runs in, if any, so repositories take
+/// part in it without knowing. The connection must be dropped before acquiring another one.
+pub async fn acquire(pool: &PgPool) -> Result<Connection, sqlx::Error> {
+    match CURRENT_TRANSACTION.try_with(Arc::clone) {
+        Ok(transaction) => Ok(Connection::Transaction(transaction.lock_owned().await)),
+        Err(_) => Ok(Connection::Pooled(Box::new(pool.acquire().await?))),
+    }
+}
+
+/// Runs each unit of work in a Postgres transaction, that the repositories join through
+/// `acquire`.
+#[derive(Clone)]
+pub struct PgUnitOfWork {
+    pool: PgPool,
+}
+
+impl PgUnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl UnitOfWork for PgUnitOfWork {
+    async fn scope<'a>(&self, work: BoxFuture<'a, bool>) -> Result<(), TransactionError> {
+        if CURRENT_TRANSACTION.try_with(|_| ()).is_ok() {
+            work.await;
+            return Ok(());
+        }
+
+        let transaction = Arc::new(Mutex::new(self.pool.begin().await?));
+        let succeeded = CURRENT_TRANSACTION.scope(transaction.clone(), work).await;
+        let transaction = Arc::try_unwrap(transaction)
+            .map_err(|_| TransactionError::StillBorrowed)?
+            .into_inner();
+
+        if succeeded {
+            transaction.commit().await?;
+        } else if let Err(e) = transaction.rollback().await {
+            tracing::error!("Rollback error: {}", e);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use std::future::Future;
+
+use futures_util::future::BoxFuture;
+
+use crate::core::structs::transaction::TransactionError;
+
+/// Makes the writes of a use case atomic, whatever the repositories they go through.
+#[async_trait::async_trait]
+pub trait UnitOfWork: Send + Sync {
+    /// Commits the writes of `work` if it returns true, and rolls them back otherwise. Work
+    /// started inside another one joins it, and is committed along with it.
+    async fn scope<'a>(&self, work: BoxFuture<'a, bool>) -> Result<(), TransactionError>;
+}
+
+impl dyn UnitOfWork {
+    /// Commits the writes of `operation` if it succeeds, and rolls them back otherwise.
+    pub async fn run<T, E, F, Fut>(&self, operation: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: Future<Output = Result<T, E>> + Send,
+        T: Send,
+        E: From<TransactionError> + Send,
+    {
+        let mut result = None;
+        self.scope(Box::pin(async {
+            let outcome = operation().await;
+            let succeeded = outcome.is_ok();
+            result = Some(outcome);
+            succeeded
+        }))
+        .await?;
+
+        result.expect("The work of a unit of work always runs before it ends")
+    }
+}
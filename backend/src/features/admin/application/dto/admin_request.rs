@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::features::auth::domain::entities::AccountStatus;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListUsersQuery {
+    pub search: Option<String>,
+    // Starts at 1
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SetUserStatusRequest {
+    pub status: AccountStatus,
+    pub reason: Option<String>,
+    // Without it, a suspension or a ban lasts until the account is reactivated
+    pub until: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::features::admin::domain::entities::User;
+use crate::features::auth::domain::entities::AccountStatus;
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct AdminUserData {
+    pub id: Uuid,
+    pub username: String,
+    pub roles: Vec<String>,
+    pub otp_verified: bool,
+    pub password_is_expired: bool,
+    pub status: AccountStatus,
+    pub status_reason: Option<String>,
+    pub status_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for AdminUserData {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            roles: user.roles,
+            otp_verified: user.otp_verified,
+            password_is_expired: user.password_is_expired,
+            status: user.status,
+            status_reason: user.status_reason,
+            status_until: user.status_until,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct UsersResponse {
+    pub code: String,
+    pub users: Vec<AdminUserData>,
+    pub page: i64,
+    pub per_page: i64,
+    pub total: i64,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct SessionDeviceInfo {
+    pub os: Option<String>,
+    pub is_mobile: Option<bool>,
+    pub browser: Option<String>,
+    pub app_version: Option<String>,
+    pub model: Option<String>,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct SessionData {
+    pub token_id: Uuid,
+    pub parsed_device_info: SessionDeviceInfo,
+    pub last_activity_date: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct SessionsResponse {
+    pub code: String,
+    pub sessions: Vec<SessionData>,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct AdminActionResponse {
+    pub code: String,
+}
@@ -0,0 +1,8 @@
+pub mod admin_request;
+pub mod admin_response;
+
+pub use admin_request::{ListUsersQuery, SetUserStatusRequest};
+pub use admin_response::{
+    AdminActionResponse, AdminUserData, SessionData, SessionDeviceInfo, SessionsResponse,
+    UsersResponse,
+};
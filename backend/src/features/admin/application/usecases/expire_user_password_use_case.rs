@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::features::admin::application::dto::AdminActionResponse;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::UserRepository;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+
+pub struct ExpireUserPasswordUseCase {
+    user_repository: Box<dyn UserRepository>,
+    audit_log: AuditLog,
+}
+
+impl ExpireUserPasswordUseCase {
+    pub fn new(user_repository: Box<dyn UserRepository>, audit_log: AuditLog) -> Self {
+        Self {
+            user_repository,
+            audit_log,
+        }
+    }
+
+    /// The user can't log in with the password anymore, and has to recover the account to set
+    /// a new one.
+    pub async fn execute(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        context: RequestContext,
+    ) -> Result<AdminActionResponse, AdminDomainError> {
+        self.user_repository.expire_password(user_id).await?;
+        self.audit_log
+            .record(
+                SecurityEvent::new(
+                    user_id,
+                    SecurityEventType::PasswordExpired,
+                    SecurityEventOutcome::Success,
+                    &context,
+                )
+                .with_actor(Some(admin_id)),
+            )
+            .await;
+
+        Ok(AdminActionResponse {
+            code: "PASSWORD_EXPIRED".to_string(),
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use uuid::Uuid;
+
+use crate::features::admin::application::dto::{SessionData, SessionDeviceInfo, SessionsResponse};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::{SessionRepository, UserRepository};
+use crate::features::auth::infrastructure::session_activity::SessionActivityWriter;
+
+pub struct GetUserSessionsUseCase {
+    user_repository: Box<dyn UserRepository>,
+    session_repository: Box<dyn SessionRepository>,
+    session_activity_writer: SessionActivityWriter,
+}
+
+impl GetUserSessionsUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        session_repository: Box<dyn SessionRepository>,
+        session_activity_writer: SessionActivityWriter,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_repository,
+            session_activity_writer,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<SessionsResponse, AdminDomainError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AdminDomainError::UserNotFound)?;
+
+        let sessions = self.session_repository.find_all_by_user_id(user_id).await?;
+
+        let mut session_data = Vec::new();
+        for session in sessions {
+            // Activity not written yet is more recent than the stored one
+            let pending_activity = self
+                .session_activity_writer
+                .pending_activity(session.token_id)
+                .await;
+            let (last_activity, last_ip) = match pending_activity {
+                Some(activity) => (
+                    Some(activity.last_activity_at),
+                    activity.last_ip.or(session.last_ip),
+                ),
+                None => (session.last_activity, session.last_ip),
+            };
+
+            session_data.push(SessionData {
+                token_id: session.token_id,
+                parsed_device_info: SessionDeviceInfo {
+                    os: session.os,
+                    is_mobile: session.is_mobile,
+                    browser: session.browser,
+                    app_version: session.app_version,
+                    model: session.model,
+                },
+                last_activity_date: last_activity,
+                last_ip,
+                created_at: session.created_at,
+                expires_at: session.expires_at,
+            });
+        }
+
+        Ok(SessionsResponse {
+            code: "SESSIONS_FETCHED".to_string(),
+            sessions: session_data,
+        })
+    }
+}
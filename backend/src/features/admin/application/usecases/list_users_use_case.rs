@@ -0,0 +1,42 @@
+use crate::features::admin::application::dto::{ListUsersQuery, UsersResponse};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::UserRepository;
+
+const DEFAULT_PER_PAGE: i64 = 20;
+const MAX_PER_PAGE: i64 = 100;
+
+pub struct ListUsersUseCase {
+    user_repository: Box<dyn UserRepository>,
+}
+
+impl ListUsersUseCase {
+    pub fn new(user_repository: Box<dyn UserRepository>) -> Self {
+        Self { user_repository }
+    }
+
+    pub async fn execute(&self, query: ListUsersQuery) -> Result<UsersResponse, AdminDomainError> {
+        let page = query.page.unwrap_or(1).max(1);
+        let per_page = query
+            .per_page
+            .unwrap_or(DEFAULT_PER_PAGE)
+            .clamp(1, MAX_PER_PAGE);
+        let search = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|search| !search.is_empty());
+
+        let users_page = self
+            .user_repository
+            .find_page(search, per_page, (page - 1) * per_page)
+            .await?;
+
+        Ok(UsersResponse {
+            code: "USERS_FETCHED".to_string(),
+            users: users_page.users.into_iter().map(|u| u.into()).collect(),
+            page,
+            per_page,
+            total: users_page.total,
+        })
+    }
+}
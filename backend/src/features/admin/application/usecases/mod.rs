@@ -0,0 +1,15 @@
+pub mod expire_user_password_use_case;
+pub mod get_user_sessions_use_case;
+pub mod list_users_use_case;
+pub mod reset_user_otp_use_case;
+pub mod revoke_user_sessions_use_case;
+pub mod set_user_role_use_case;
+pub mod set_user_status_use_case;
+
+pub use expire_user_password_use_case::ExpireUserPasswordUseCase;
+pub use get_user_sessions_use_case::GetUserSessionsUseCase;
+pub use list_users_use_case::ListUsersUseCase;
+pub use reset_user_otp_use_case::ResetUserOtpUseCase;
+pub use revoke_user_sessions_use_case::RevokeUserSessionsUseCase;
+pub use set_user_role_use_case::SetUserRoleUseCase;
+pub use set_user_status_use_case::SetUserStatusUseCase;
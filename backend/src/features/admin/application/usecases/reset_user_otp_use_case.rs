@@ -0,0 +1,70 @@
+use uuid::Uuid;
+
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::admin::application::dto::AdminActionResponse;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::UserRepository;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct ResetUserOtpUseCase {
+    user_repository: Box<dyn UserRepository>,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
+}
+
+impl ResetUserOtpUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
+    }
+
+    /// Disables 2FA for a user who lost their authenticator, so they can log in with the
+    /// password alone and set it up again.
+    pub async fn execute(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        context: RequestContext,
+    ) -> Result<AdminActionResponse, AdminDomainError> {
+        self.unit_of_work
+            .run(|| async {
+                self.user_repository.reset_otp(user_id).await?;
+                self.outbox
+                    .publish(vec![DomainEvent::OtpDisabled { user_id }])
+                    .await?;
+
+                Ok::<_, AdminDomainError>(())
+            })
+            .await?;
+        self.audit_log
+            .record(
+                SecurityEvent::new(
+                    user_id,
+                    SecurityEventType::OtpDisabled,
+                    SecurityEventOutcome::Success,
+                    &context,
+                )
+                .with_actor(Some(admin_id)),
+            )
+            .await;
+
+        Ok(AdminActionResponse {
+            code: "OTP_RESET".to_string(),
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use uuid::Uuid;
+
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::admin::application::dto::AdminActionResponse;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::{SessionRepository, UserRepository};
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct RevokeUserSessionsUseCase {
+    user_repository: Box<dyn UserRepository>,
+    session_repository: Box<dyn SessionRepository>,
+    token_cache: TokenCache,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
+}
+
+impl RevokeUserSessionsUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        session_repository: Box<dyn SessionRepository>,
+        token_cache: TokenCache,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_repository,
+            token_cache,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
+    }
+
+    /// Signs the user out of every device.
+    pub async fn execute(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        context: RequestContext,
+    ) -> Result<AdminActionResponse, AdminDomainError> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AdminDomainError::UserNotFound)?;
+
+        let token_ids = self
+            .unit_of_work
+            .run(|| async {
+                let token_ids = self
+                    .session_repository
+                    .delete_all_by_user_id(user_id)
+                    .await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(user_id, &token_ids))
+                    .await?;
+
+                Ok::<_, AdminDomainError>(token_ids)
+            })
+            .await?;
+
+        for token_id in token_ids {
+            self.token_cache.remove_key(token_id).await;
+        }
+        self.audit_log
+            .record(
+                SecurityEvent::new(
+                    user_id,
+                    SecurityEventType::SessionsRevoked,
+                    SecurityEventOutcome::Success,
+                    &context,
+                )
+                .with_actor(Some(admin_id)),
+            )
+            .await;
+
+        Ok(AdminActionResponse {
+            code: "SESSIONS_REVOKED".to_string(),
+        })
+    }
+}
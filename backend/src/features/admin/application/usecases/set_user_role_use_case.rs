@@ -0,0 +1,111 @@
+use uuid::Uuid;
+
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::admin::application::dto::AdminActionResponse;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::{SessionRepository, UserRepository};
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct SetUserRoleUseCase {
+    user_repository: Box<dyn UserRepository>,
+    session_repository: Box<dyn SessionRepository>,
+    token_cache: TokenCache,
+    unit_of_work: Box<dyn UnitOfWork>,
+    audit_log: AuditLog,
+    outbox: Outbox,
+}
+
+impl SetUserRoleUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        session_repository: Box<dyn SessionRepository>,
+        token_cache: TokenCache,
+        unit_of_work: Box<dyn UnitOfWork>,
+        audit_log: AuditLog,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_repository,
+            token_cache,
+            unit_of_work,
+            audit_log,
+            outbox,
+        }
+    }
+
+    /// Grants or revokes a role. The permissions are carried by the tokens, so a granted role
+    /// applies from the next refresh, while a revocation signs the user out right away.
+    pub async fn execute(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        role: &str,
+        is_granted: bool,
+        context: RequestContext,
+    ) -> Result<AdminActionResponse, AdminDomainError> {
+        // Keeps an admin from locking themselves out by mistake
+        if !is_granted && admin_id == user_id {
+            return Err(AdminDomainError::CannotRevokeOwnRole);
+        }
+
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AdminDomainError::UserNotFound)?;
+
+        if !self.user_repository.role_exists(role).await? {
+            return Err(AdminDomainError::RoleNotFound);
+        }
+
+        if is_granted {
+            self.user_repository.add_role(user_id, role).await?;
+        } else {
+            let token_ids = self
+                .unit_of_work
+                .run(|| async {
+                    self.user_repository.remove_role(user_id, role).await?;
+                    let token_ids = self
+                        .session_repository
+                        .delete_all_by_user_id(user_id)
+                        .await?;
+                    self.outbox
+                        .publish(DomainEvent::sessions_revoked(user_id, &token_ids))
+                        .await?;
+
+                    Ok::<_, AdminDomainError>(token_ids)
+                })
+                .await?;
+
+            for token_id in token_ids {
+                self.token_cache.remove_key(token_id).await;
+            }
+        }
+
+        let event_type = if is_granted {
+            SecurityEventType::RoleGranted
+        } else {
+            SecurityEventType::RoleRevoked
+        };
+        self.audit_log
+            .record(
+                SecurityEvent::new(user_id, event_type, SecurityEventOutcome::Success, &context)
+                    .with_actor(Some(admin_id)),
+            )
+            .await;
+
+        Ok(AdminActionResponse {
+            code: if is_granted {
+                "ROLE_GRANTED".to_string()
+            } else {
+                "ROLE_REVOKED".to_string()
+            },
+        })
+    }
+}
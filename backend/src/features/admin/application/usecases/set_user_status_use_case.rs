@@ -0,0 +1,111 @@
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::admin::application::dto::{AdminActionResponse, SetUserStatusRequest};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::{SessionRepository, UserRepository};
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::domain::entities::AccountStatus;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct SetUserStatusUseCase {
+    user_repository: Box<dyn UserRepository>,
+    session_repository: Box<dyn SessionRepository>,
+    token_cache: TokenCache,
+    unit_of_work: Box<dyn UnitOfWork>,
+    audit_log: AuditLog,
+    outbox: Outbox,
+}
+
+impl SetUserStatusUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        session_repository: Box<dyn SessionRepository>,
+        token_cache: TokenCache,
+        unit_of_work: Box<dyn UnitOfWork>,
+        audit_log: AuditLog,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_repository,
+            token_cache,
+            unit_of_work,
+            audit_log,
+            outbox,
+        }
+    }
+
+    /// Suspends, bans or reactivates an account. Disabling it signs the user out right away.
+    pub async fn execute(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: SetUserStatusRequest,
+        context: RequestContext,
+    ) -> Result<AdminActionResponse, AdminDomainError> {
+        if admin_id == user_id {
+            return Err(AdminDomainError::CannotChangeOwnStatus);
+        }
+
+        // An active account has nothing to explain, and a status already over is a mistake
+        let is_active = request.status == AccountStatus::Active;
+        let is_over = request.until.is_some_and(|until| until <= now());
+        if (is_active && (request.reason.is_some() || request.until.is_some())) || is_over {
+            return Err(AdminDomainError::InvalidAccountStatus);
+        }
+
+        let token_ids = self
+            .unit_of_work
+            .run(|| async {
+                self.user_repository
+                    .set_status(
+                        user_id,
+                        request.status,
+                        request.reason.as_deref(),
+                        request.until,
+                    )
+                    .await?;
+
+                if is_active {
+                    return Ok(Vec::new());
+                }
+                let token_ids = self
+                    .session_repository
+                    .delete_all_by_user_id(user_id)
+                    .await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(user_id, &token_ids))
+                    .await?;
+
+                Ok::<_, AdminDomainError>(token_ids)
+            })
+            .await?;
+
+        // Only once committed, or a concurrent request could cache the tokens again
+        for token_id in token_ids {
+            self.token_cache.remove_key(token_id).await;
+        }
+        self.audit_log
+            .record(
+                SecurityEvent::new(
+                    user_id,
+                    SecurityEventType::AccountStatusChanged,
+                    SecurityEventOutcome::Success,
+                    &context,
+                )
+                .with_actor(Some(admin_id)),
+            )
+            .await;
+
+        Ok(AdminActionResponse {
+            code: "ACCOUNT_STATUS_UPDATED".to_string(),
+        })
+    }
+}
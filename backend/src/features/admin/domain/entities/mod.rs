@@ -0,0 +1,5 @@
+pub mod session;
+pub mod user;
+
+pub use session::Session;
+pub use user::{User, UsersPage};
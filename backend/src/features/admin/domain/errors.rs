@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+use crate::core::structs::transaction::TransactionError;
+use crate::features::events::domain::errors::EventsDomainError;
+
+#[derive(Error, Debug)]
+pub enum AdminDomainError {
+    #[error("User not found")]
+    UserNotFound,
+
+    #[error("Role not found")]
+    RoleNotFound,
+
+    #[error("Cannot revoke your own role")]
+    CannotRevokeOwnRole,
+
+    #[error("Cannot change your own status")]
+    CannotChangeOwnStatus,
+
+    #[error("Invalid account status")]
+    InvalidAccountStatus,
+
+    #[error("Database error")]
+    DatabaseError,
+}
+
+impl From<TransactionError> for AdminDomainError {
+    fn from(e: TransactionError) -> Self {
+        tracing::error!("Transaction error: {}", e);
+        AdminDomainError::DatabaseError
+    }
+}
+
+// The outbox is written in the transaction of the change, so failing to publish undoes it
+impl From<EventsDomainError> for AdminDomainError {
+    fn from(e: EventsDomainError) -> Self {
+        tracing::error!("Outbox error: {}", e);
+        AdminDomainError::DatabaseError
+    }
+}
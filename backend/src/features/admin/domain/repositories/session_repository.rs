@@ -0,0 +1,11 @@
+use uuid::Uuid;
+
+use crate::features::admin::domain::entities::Session;
+use crate::features::admin::domain::errors::AdminDomainError;
+
+#[async_trait::async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Session>, AdminDomainError>;
+    /// Returns the ids of the deleted tokens.
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AdminDomainError>;
+}
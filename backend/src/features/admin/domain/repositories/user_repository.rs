@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::admin::domain::entities::{User, UsersPage};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::auth::domain::entities::AccountStatus;
+
+/// The update methods fail with `UserNotFound` if the user doesn't exist.
+#[async_trait::async_trait]
+pub trait UserRepository: Send + Sync {
+    /// Users whose username contains `search`, oldest first.
+    async fn find_page(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<UsersPage, AdminDomainError>;
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AdminDomainError>;
+    async fn expire_password(&self, user_id: Uuid) -> Result<(), AdminDomainError>;
+    /// Disables 2FA and deletes the OTP secret.
+    async fn reset_otp(&self, user_id: Uuid) -> Result<(), AdminDomainError>;
+    async fn set_status(
+        &self,
+        user_id: Uuid,
+        status: AccountStatus,
+        reason: Option<&str>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<(), AdminDomainError>;
+    async fn role_exists(&self, role: &str) -> Result<bool, AdminDomainError>;
+    /// Does nothing if the user already has the role.
+    async fn add_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError>;
+    /// Does nothing if the user doesn't have the role.
+    async fn remove_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError>;
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct SessionModel {
+    pub user_id: Uuid,
+    pub token_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub os: Option<String>,
+    pub is_mobile: Option<bool>,
+    pub browser: Option<String>,
+    pub app_version: Option<String>,
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
+}
+
+impl From<SessionModel> for crate::features::admin::domain::entities::Session {
+    fn from(model: SessionModel) -> Self {
+        Self {
+            token_id: model.token_id,
+            user_id: model.user_id,
+            os: model.os,
+            is_mobile: model.is_mobile,
+            browser: model.browser,
+            app_version: model.app_version,
+            model: model.model,
+            expires_at: model.expires_at,
+            last_activity: model.last_activity_at,
+            last_ip: model.last_ip,
+            created_at: model.created_at,
+        }
+    }
+}
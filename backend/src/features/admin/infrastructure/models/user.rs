@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::AccountStatus;
+
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct UserModel {
+    pub id: Uuid,
+    pub username: String,
+    pub roles: Vec<String>,
+    pub otp_verified: bool,
+    pub password_is_expired: bool,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<UserModel> for crate::features::admin::domain::entities::User {
+    fn from(model: UserModel) -> Self {
+        Self {
+            id: model.id,
+            username: model.username,
+            roles: model.roles,
+            otp_verified: model.otp_verified,
+            password_is_expired: model.password_is_expired,
+            status: AccountStatus::from_name(&model.status),
+            status_reason: model.status_reason,
+            status_until: model.status_until,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::sync::MutexGuard;
+
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::admin::domain::entities::Session;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::SessionRepository;
+use crate::features::auth::domain::entities::UserToken;
+
+#[derive(Clone)]
+pub struct InMemorySessionRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemorySessionRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AdminDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for InMemorySessionRepository {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Session>, AdminDomainError> {
+        let tables = self.database.tables();
+
+        let mut sessions: Vec<Session> = tables
+            .user_tokens
+            .iter()
+            .filter(|token| token.user_id == user_id && token.rotated_at.is_none())
+            .map(|token| Session {
+                token_id: token.token_id,
+                user_id: token.user_id,
+                os: token.os.clone(),
+                is_mobile: token.is_mobile,
+                browser: token.browser.clone(),
+                app_version: token.app_version.clone(),
+                model: token.model.clone(),
+                expires_at: token.expires_at,
+                last_activity: token.last_activity_at,
+                last_ip: token.last_ip.clone(),
+                created_at: token.created_at,
+            })
+            .collect();
+        sessions.sort_by_key(|session| session.created_at);
+
+        Ok(sessions)
+    }
+
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AdminDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        let (deleted, kept): (Vec<UserToken>, Vec<UserToken>) =
+            std::mem::take(&mut tables.user_tokens)
+                .into_iter()
+                .partition(|token| token.user_id == user_id);
+        tables.user_tokens = kept;
+
+        Ok(deleted.into_iter().map(|token| token.token_id).collect())
+    }
+}
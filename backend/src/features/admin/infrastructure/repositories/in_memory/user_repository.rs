@@ -0,0 +1,169 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::admin::domain::entities::{User, UsersPage};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::UserRepository;
+use crate::features::auth::domain::entities::{AccountStatus, User as AuthUser};
+
+#[derive(Clone)]
+pub struct InMemoryUserRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AdminDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })
+    }
+
+    fn update(
+        &self,
+        user_id: Uuid,
+        change: impl FnOnce(&mut AuthUser),
+    ) -> Result<(), AdminDomainError> {
+        let mut tables = self.write("users")?;
+
+        let user = tables
+            .users
+            .iter_mut()
+            .find(|user| user.id == user_id)
+            .ok_or(AdminDomainError::UserNotFound)?;
+        change(user);
+        user.updated_at = now();
+
+        Ok(())
+    }
+}
+
+fn to_admin_user(tables: &Tables, user: &AuthUser) -> User {
+    let mut roles: Vec<String> = tables
+        .user_roles
+        .iter()
+        .filter(|(user_id, _)| *user_id == user.id)
+        .map(|(_, role)| role.clone())
+        .collect();
+    roles.sort();
+
+    User {
+        id: user.id,
+        username: user.username.clone(),
+        roles,
+        otp_verified: user.otp_verified,
+        password_is_expired: user.password_is_expired,
+        status: user.status,
+        status_reason: user.status_reason.clone(),
+        status_until: user.status_until,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_page(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<UsersPage, AdminDomainError> {
+        let tables = self.database.tables();
+
+        // The search is matched literally, ignoring the case
+        let search = search.map(str::to_lowercase);
+        let mut users: Vec<&AuthUser> = tables
+            .users
+            .iter()
+            .filter(|user| {
+                search
+                    .as_deref()
+                    .is_none_or(|search| user.username.to_lowercase().contains(search))
+            })
+            .collect();
+        users.sort_by_key(|user| (user.created_at, user.id));
+
+        Ok(UsersPage {
+            total: users.len() as i64,
+            users: users
+                .into_iter()
+                .skip(offset.max(0) as usize)
+                .take(limit.max(0) as usize)
+                .map(|user| to_admin_user(&tables, user))
+                .collect(),
+        })
+    }
+
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AdminDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.id == user_id)
+            .map(|user| to_admin_user(&tables, user)))
+    }
+
+    async fn expire_password(&self, user_id: Uuid) -> Result<(), AdminDomainError> {
+        self.update(user_id, |user| user.password_is_expired = true)
+    }
+
+    async fn reset_otp(&self, user_id: Uuid) -> Result<(), AdminDomainError> {
+        self.update(user_id, |user| {
+            user.otp_verified = false;
+            user.otp_base32 = None;
+            user.otp_auth_url = None;
+            user.otp_last_used_step = None;
+        })
+    }
+
+    async fn set_status(
+        &self,
+        user_id: Uuid,
+        status: AccountStatus,
+        reason: Option<&str>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<(), AdminDomainError> {
+        self.update(user_id, |user| {
+            user.status = status;
+            user.status_reason = reason.map(str::to_string);
+            user.status_until = until;
+        })
+    }
+
+    async fn role_exists(&self, role: &str) -> Result<bool, AdminDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables.role_permissions.iter().any(|(name, _)| name == role))
+    }
+
+    async fn add_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError> {
+        let mut tables = self.write("user_roles")?;
+
+        let granted = (user_id, role.to_string());
+        if !tables.user_roles.contains(&granted) {
+            tables.user_roles.push(granted);
+        }
+
+        Ok(())
+    }
+
+    async fn remove_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError> {
+        let mut tables = self.write("user_roles")?;
+
+        tables
+            .user_roles
+            .retain(|(id, name)| !(*id == user_id && name == role));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod session_repository_impl;
+pub mod user_repository_impl;
+
+pub use session_repository_impl::SessionRepositoryImpl;
+pub use user_repository_impl::UserRepositoryImpl;
+
+#[cfg(feature = "test-util")]
+pub mod in_memory;
@@ -0,0 +1,72 @@
+use uuid::Uuid;
+
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::admin::domain::entities::Session;
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::SessionRepository;
+use crate::features::admin::infrastructure::models::SessionModel;
+
+#[derive(Clone)]
+pub struct SessionRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl SessionRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<Connection, AdminDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Session>, AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let sessions = sqlx::query_as!(
+            SessionModel,
+            r#"
+            SELECT user_id, token_id, expires_at, os, is_mobile, browser, app_version, model,
+                created_at, last_activity_at, last_ip
+            FROM user_tokens
+            WHERE user_id = $1 AND rotated_at IS NULL
+            ORDER BY created_at
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(sessions.into_iter().map(|s| s.into()).collect())
+    }
+
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let token_ids = sqlx::query_scalar!(
+            r#"
+            DELETE
+            FROM user_tokens
+            WHERE user_id = $1
+            RETURNING token_id
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(token_ids)
+    }
+}
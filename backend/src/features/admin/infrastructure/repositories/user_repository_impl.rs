@@ -0,0 +1,254 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::admin::domain::entities::{User, UsersPage};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::admin::domain::repositories::UserRepository;
+use crate::features::admin::infrastructure::models::UserModel;
+use crate::features::auth::domain::entities::AccountStatus;
+
+#[derive(Clone)]
+pub struct UserRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl UserRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<Connection, AdminDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })
+    }
+}
+
+fn ensure_user_found(result: sqlx::postgres::PgQueryResult) -> Result<(), AdminDomainError> {
+    if result.rows_affected() == 0 {
+        return Err(AdminDomainError::UserNotFound);
+    }
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn find_page(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<UsersPage, AdminDomainError> {
+        // The search is matched literally
+        let pattern = search.map(|search| {
+            let escaped = search
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{}%", escaped)
+        });
+
+        let mut conn = self.connection().await?;
+        let users = sqlx::query_as!(
+            UserModel,
+            r#"
+            SELECT id, username, otp_verified, password_is_expired, status, status_reason,
+                status_until, created_at, updated_at,
+                ARRAY(
+                    SELECT role FROM user_roles WHERE user_id = users.id ORDER BY role
+                ) AS "roles!"
+            FROM users
+            WHERE $1::TEXT IS NULL OR username ILIKE $1
+            ORDER BY created_at, id
+            LIMIT $2 OFFSET $3
+            "#,
+            pattern,
+            limit,
+            offset
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM users
+            WHERE $1::TEXT IS NULL OR username ILIKE $1
+            "#,
+            pattern
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(UsersPage {
+            users: users.into_iter().map(|u| u.into()).collect(),
+            total,
+        })
+    }
+
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let user = sqlx::query_as!(
+            UserModel,
+            r#"
+            SELECT id, username, otp_verified, password_is_expired, status, status_reason,
+                status_until, created_at, updated_at,
+                ARRAY(
+                    SELECT role FROM user_roles WHERE user_id = users.id ORDER BY role
+                ) AS "roles!"
+            FROM users
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(user.map(|u| u.into()))
+    }
+
+    async fn expire_password(&self, user_id: Uuid) -> Result<(), AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET password_is_expired = TRUE, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        ensure_user_found(result)
+    }
+
+    async fn reset_otp(&self, user_id: Uuid) -> Result<(), AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET otp_verified = FALSE, otp_base32 = NULL, otp_auth_url = NULL,
+                otp_last_used_step = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        ensure_user_found(result)
+    }
+
+    async fn set_status(
+        &self,
+        user_id: Uuid,
+        status: AccountStatus,
+        reason: Option<&str>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<(), AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET status = $1, status_reason = $2, status_until = $3, updated_at = NOW()
+            WHERE id = $4
+            "#,
+            status.as_str(),
+            reason,
+            until,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        ensure_user_found(result)
+    }
+
+    async fn role_exists(&self, role: &str) -> Result<bool, AdminDomainError> {
+        let mut conn = self.connection().await?;
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM roles WHERE name = $1) AS "exists!"
+            "#,
+            role
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(exists)
+    }
+
+    async fn add_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError> {
+        let mut conn = self.connection().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_id, role)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            role
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn remove_role(&self, user_id: Uuid, role: &str) -> Result<(), AdminDomainError> {
+        let mut conn = self.connection().await?;
+        sqlx::query!(
+            r#"
+            DELETE
+            FROM user_roles
+            WHERE user_id = $1 AND role = $2
+            "#,
+            user_id,
+            role
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AdminDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod session_controller;
+pub mod user_controller;
+
+pub use session_controller::{get_user_sessions, revoke_user_sessions};
+pub use user_controller::{
+    expire_user_password, grant_role, list_users, reset_user_otp, revoke_role, set_user_status,
+};
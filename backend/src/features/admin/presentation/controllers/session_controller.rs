@@ -0,0 +1,49 @@
+use actix_web::{delete, get, web, web::Path, web::ReqData, HttpRequest, HttpResponse, Responder};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::core::middlewares::permission_guard::PermissionGuard;
+use crate::features::admin::application::usecases::{
+    GetUserSessionsUseCase, RevokeUserSessionsUseCase,
+};
+use crate::features::admin::presentation::controllers::user_controller::admin_error_response;
+use crate::features::audit::helpers::request_context::get_request_context;
+use crate::features::auth::domain::entities::{Claims, Permission};
+
+#[get(
+    "/{user_id}/sessions",
+    wrap = "PermissionGuard::new(Permission::ReadSessions)"
+)]
+pub async fn get_user_sessions(
+    user_id: Path<Uuid>,
+    use_case: web::Data<GetUserSessionsUseCase>,
+) -> impl Responder {
+    match use_case.execute(*user_id).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Get user sessions error: {}", e);
+            admin_error_response(e, "SESSIONS_FETCH_ERROR", "Failed to fetch sessions")
+        }
+    }
+}
+
+#[delete(
+    "/{user_id}/sessions",
+    wrap = "PermissionGuard::new(Permission::WriteSessions)"
+)]
+pub async fn revoke_user_sessions(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    user_id: Path<Uuid>,
+    use_case: web::Data<RevokeUserSessionsUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(claims.user_id, *user_id, context).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Revoke user sessions error: {}", e);
+            admin_error_response(e, "SESSIONS_REVOKE_ERROR", "Failed to revoke sessions")
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use actix_web::{
+    delete, get, post, put, web, web::Path, web::ReqData, HttpRequest, HttpResponse, Responder,
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::core::middlewares::permission_guard::PermissionGuard;
+use crate::core::structs::responses::GenericResponse;
+use crate::features::admin::application::dto::{ListUsersQuery, SetUserStatusRequest};
+use crate::features::admin::application::usecases::{
+    ExpireUserPasswordUseCase, ListUsersUseCase, ResetUserOtpUseCase, SetUserRoleUseCase,
+    SetUserStatusUseCase,
+};
+use crate::features::admin::domain::errors::AdminDomainError;
+use crate::features::audit::helpers::request_context::get_request_context;
+use crate::features::auth::domain::entities::{Claims, Permission};
+
+pub(super) fn admin_error_response(e: AdminDomainError, code: &str, message: &str) -> HttpResponse {
+    match e {
+        AdminDomainError::UserNotFound => HttpResponse::NotFound().json(GenericResponse {
+            code: "USER_NOT_FOUND".to_string(),
+            message: "User not found".to_string(),
+        }),
+        AdminDomainError::RoleNotFound => HttpResponse::NotFound().json(GenericResponse {
+            code: "ROLE_NOT_FOUND".to_string(),
+            message: "Role not found".to_string(),
+        }),
+        AdminDomainError::CannotRevokeOwnRole => HttpResponse::BadRequest().json(GenericResponse {
+            code: "CANNOT_REVOKE_OWN_ROLE".to_string(),
+            message: "Admins cannot revoke their own roles".to_string(),
+        }),
+        AdminDomainError::CannotChangeOwnStatus => {
+            HttpResponse::BadRequest().json(GenericResponse {
+                code: "CANNOT_CHANGE_OWN_STATUS".to_string(),
+                message: "Admins cannot change the status of their own account".to_string(),
+            })
+        }
+        AdminDomainError::InvalidAccountStatus => {
+            HttpResponse::BadRequest().json(GenericResponse {
+                code: "INVALID_ACCOUNT_STATUS".to_string(),
+                message: "Only a suspension or a ban can have a reason and a future end date"
+                    .to_string(),
+            })
+        }
+        AdminDomainError::DatabaseError => {
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: code.to_string(),
+                message: message.to_string(),
+            })
+        }
+    }
+}
+
+#[get("", wrap = "PermissionGuard::new(Permission::ReadUsers)")]
+pub async fn list_users(
+    query: web::Query<ListUsersQuery>,
+    use_case: web::Data<ListUsersUseCase>,
+) -> impl Responder {
+    match use_case.execute(query.into_inner()).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("List users error: {}", e);
+            admin_error_response(e, "USERS_FETCH_ERROR", "Failed to fetch users")
+        }
+    }
+}
+
+#[post(
+    "/{user_id}/password/expire",
+    wrap = "PermissionGuard::new(Permission::WriteUsers)"
+)]
+pub async fn expire_user_password(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    user_id: Path<Uuid>,
+    use_case: web::Data<ExpireUserPasswordUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(claims.user_id, *user_id, context).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Expire user password error: {}", e);
+            admin_error_response(e, "PASSWORD_EXPIRE_ERROR", "Failed to expire password")
+        }
+    }
+}
+
+#[post(
+    "/{user_id}/otp/reset",
+    wrap = "PermissionGuard::new(Permission::WriteUsers)"
+)]
+pub async fn reset_user_otp(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    user_id: Path<Uuid>,
+    use_case: web::Data<ResetUserOtpUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(claims.user_id, *user_id, context).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Reset user OTP error: {}", e);
+            admin_error_response(e, "OTP_RESET_ERROR", "Failed to reset 2FA")
+        }
+    }
+}
+
+#[put(
+    "/{user_id}/status",
+    wrap = "PermissionGuard::new(Permission::WriteUsers)"
+)]
+pub async fn set_user_status(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    user_id: Path<Uuid>,
+    body: web::Json<SetUserStatusRequest>,
+    use_case: web::Data<SetUserStatusUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(claims.user_id, *user_id, body.into_inner(), context)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Set user status error: {}", e);
+            admin_error_response(e, "ACCOUNT_STATUS_UPDATE_ERROR", "Failed to update status")
+        }
+    }
+}
+
+#[post(
+    "/{user_id}/roles/{role}",
+    wrap = "PermissionGuard::new(Permission::WriteRoles)"
+)]
+pub async fn grant_role(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    path: Path<(Uuid, String)>,
+    use_case: web::Data<SetUserRoleUseCase>,
+) -> impl Responder {
+    let (user_id, role) = path.into_inner();
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(claims.user_id, user_id, &role, true, context)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Grant role error: {}", e);
+            admin_error_response(e, "ROLE_GRANT_ERROR", "Failed to grant role")
+        }
+    }
+}
+
+#[delete(
+    "/{user_id}/roles/{role}",
+    wrap = "PermissionGuard::new(Permission::WriteRoles)"
+)]
+pub async fn revoke_role(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    path: Path<(Uuid, String)>,
+    use_case: web::Data<SetUserRoleUseCase>,
+) -> impl Responder {
+    let (user_id, role) = path.into_inner();
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(claims.user_id, user_id, &role, false, context)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Revoke role error: {}", e);
+            admin_error_response(e, "ROLE_REVOKE_ERROR", "Failed to revoke role")
+        }
+    }
+}
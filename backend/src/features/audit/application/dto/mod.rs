@@ -0,0 +1,5 @@
+pub mod security_event_request;
+pub mod security_event_response;
+
+pub use security_event_request::{AdminSecurityEventsQuery, SecurityEventsQuery};
+pub use security_event_response::{SecurityEventData, SecurityEventsResponse};
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SecurityEventsQuery {
+    // The `next_cursor` of the previous page, the first page is returned without it
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminSecurityEventsQuery {
+    pub user_id: Option<Uuid>,
+    pub event_type: Option<SecurityEventType>,
+    pub outcome: Option<SecurityEventOutcome>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
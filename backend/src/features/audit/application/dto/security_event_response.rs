@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{
+    SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::profile::structs::models::ParsedDeviceInfo;
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct SecurityEventData {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub event_type: SecurityEventType,
+    pub outcome: SecurityEventOutcome,
+    pub ip: Option<String>,
+    pub parsed_device_info: ParsedDeviceInfo,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SecurityEvent> for SecurityEventData {
+    fn from(event: SecurityEvent) -> Self {
+        Self {
+            id: event.id,
+            user_id: event.user_id,
+            actor_id: event.actor_id,
+            event_type: event.event_type,
+            outcome: event.outcome,
+            ip: event.ip,
+            parsed_device_info: ParsedDeviceInfo {
+                os: event.os,
+                is_mobile: event.is_mobile,
+                browser: event.browser,
+                app_version: event.app_version,
+                model: event.model,
+            },
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct SecurityEventsResponse {
+    pub code: String,
+    pub events: Vec<SecurityEventData>,
+    // Not set on the last page
+    pub next_cursor: Option<String>,
+}
@@ -0,0 +1,54 @@
+use crate::features::audit::application::dto::SecurityEventsResponse;
+use crate::features::audit::domain::entities::{SecurityEventCursor, SecurityEventFilter};
+use crate::features::audit::domain::errors::AuditDomainError;
+use crate::features::audit::domain::repositories::SecurityEventRepository;
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+/// Lists the security events from the most recent, for a user or for an admin.
+pub struct ListSecurityEventsUseCase {
+    security_event_repository: Box<dyn SecurityEventRepository>,
+}
+
+impl ListSecurityEventsUseCase {
+    pub fn new(security_event_repository: Box<dyn SecurityEventRepository>) -> Self {
+        Self {
+            security_event_repository,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        filter: SecurityEventFilter,
+        cursor: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<SecurityEventsResponse, AuditDomainError> {
+        let after = cursor.map(SecurityEventCursor::decode).transpose()?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        // One more event than needed tells whether there is a next page
+        let mut events = self
+            .security_event_repository
+            .find_page(&filter, after, limit + 1)
+            .await?;
+        let next_cursor = if events.len() as i64 > limit {
+            events.truncate(limit as usize);
+            events.last().map(|event| {
+                SecurityEventCursor {
+                    created_at: event.created_at,
+                    id: event.id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        Ok(SecurityEventsResponse {
+            code: "SECURITY_EVENTS_FETCHED".to_string(),
+            events: events.into_iter().map(|event| event.into()).collect(),
+            next_cursor,
+        })
+    }
+}
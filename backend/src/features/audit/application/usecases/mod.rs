@@ -0,0 +1,3 @@
+pub mod list_security_events_use_case;
+
+pub use list_security_events_use_case::ListSecurityEventsUseCase;
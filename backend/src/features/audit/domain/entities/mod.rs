@@ -0,0 +1,9 @@
+pub mod request_context;
+pub mod security_event;
+pub mod security_event_cursor;
+
+pub use request_context::RequestContext;
+pub use security_event::{
+    SecurityEvent, SecurityEventFilter, SecurityEventOutcome, SecurityEventType,
+};
+pub use security_event_cursor::SecurityEventCursor;
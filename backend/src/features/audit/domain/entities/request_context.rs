@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::profile::structs::models::ParsedDeviceInfo;
+
+/// The client a request came from, as recorded with the security events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestContext {
+    pub ip: Option<String>,
+    pub device_info: ParsedDeviceInfo,
+}
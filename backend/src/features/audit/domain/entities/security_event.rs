@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::audit::domain::entities::RequestContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SecurityEventType {
+    LoginSucceeded,
+    LoginFailed,
+    OtpEnabled,
+    OtpDisabled,
+    RecoveryCodeUsed,
+    RecoveryCodesRegenerated,
+    PasswordChanged,
+    DeviceRevoked,
+    SessionsRevoked,
+    AccountDeletionScheduled,
+    RefreshTokenReuseDetected,
+    SignInReported,
+    AccountStatusChanged,
+    RoleGranted,
+    RoleRevoked,
+    PasswordExpired,
+}
+
+impl SecurityEventType {
+    const ALL: [SecurityEventType; 16] = [
+        SecurityEventType::LoginSucceeded,
+        SecurityEventType::LoginFailed,
+        SecurityEventType::OtpEnabled,
+        SecurityEventType::OtpDisabled,
+        SecurityEventType::RecoveryCodeUsed,
+        SecurityEventType::RecoveryCodesRegenerated,
+        SecurityEventType::PasswordChanged,
+        SecurityEventType::DeviceRevoked,
+        SecurityEventType::SessionsRevoked,
+        SecurityEventType::AccountDeletionScheduled,
+        SecurityEventType::RefreshTokenReuseDetected,
+        SecurityEventType::SignInReported,
+        SecurityEventType::AccountStatusChanged,
+        SecurityEventType::RoleGranted,
+        SecurityEventType::RoleRevoked,
+        SecurityEventType::PasswordExpired,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityEventType::LoginSucceeded => "LOGIN_SUCCEEDED",
+            SecurityEventType::LoginFailed => "LOGIN_FAILED",
+            SecurityEventType::OtpEnabled => "OTP_ENABLED",
+            SecurityEventType::OtpDisabled => "OTP_DISABLED",
+            SecurityEventType::RecoveryCodeUsed => "RECOVERY_CODE_USED",
+            SecurityEventType::RecoveryCodesRegenerated => "RECOVERY_CODES_REGENERATED",
+            SecurityEventType::PasswordChanged => "PASSWORD_CHANGED",
+            SecurityEventType::DeviceRevoked => "DEVICE_REVOKED",
+            SecurityEventType::SessionsRevoked => "SESSIONS_REVOKED",
+            SecurityEventType::AccountDeletionScheduled => "ACCOUNT_DELETION_SCHEDULED",
+            SecurityEventType::RefreshTokenReuseDetected => "REFRESH_TOKEN_REUSE_DETECTED",
+            SecurityEventType::SignInReported => "SIGN_IN_REPORTED",
+            SecurityEventType::AccountStatusChanged => "ACCOUNT_STATUS_CHANGED",
+            SecurityEventType::RoleGranted => "ROLE_GRANTED",
+            SecurityEventType::RoleRevoked => "ROLE_REVOKED",
+            SecurityEventType::PasswordExpired => "PASSWORD_EXPIRED",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityEventOutcome {
+    Success,
+    Failure,
+}
+
+impl SecurityEventOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityEventOutcome::Success => "success",
+            SecurityEventOutcome::Failure => "failure",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "success" => SecurityEventOutcome::Success,
+            _ => SecurityEventOutcome::Failure,
+        }
+    }
+}
+
+/// Something that happened to the account of `user_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    // Who caused the event: the user, an admin, or nobody authenticated yet
+    pub actor_id: Option<Uuid>,
+    pub event_type: SecurityEventType,
+    pub outcome: SecurityEventOutcome,
+    pub ip: Option<String>,
+    pub os: Option<String>,
+    pub is_mobile: Option<bool>,
+    pub browser: Option<String>,
+    pub app_version: Option<String>,
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SecurityEvent {
+    /// An event the user caused, from the client described by `context`.
+    pub fn new(
+        user_id: Uuid,
+        event_type: SecurityEventType,
+        outcome: SecurityEventOutcome,
+        context: &RequestContext,
+    ) -> Self {
+        let device_info = context.device_info.clone();
+
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            actor_id: Some(user_id),
+            event_type,
+            outcome,
+            ip: context.ip.clone(),
+            os: device_info.os,
+            is_mobile: device_info.is_mobile,
+            browser: device_info.browser,
+            app_version: device_info.app_version,
+            model: device_info.model,
+            created_at: now(),
+        }
+    }
+
+    pub fn with_actor(mut self, actor_id: Option<Uuid>) -> Self {
+        self.actor_id = actor_id;
+        self
+    }
+}
+
+/// Restricts the events listed to the ones matching every field set.
+#[derive(Debug, Clone, Default)]
+pub struct SecurityEventFilter {
+    pub user_id: Option<Uuid>,
+    pub event_type: Option<SecurityEventType>,
+    pub outcome: Option<SecurityEventOutcome>,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::audit::domain::errors::AuditDomainError;
+
+/// Position after the last event of a page. The events are ordered from the most recent, the
+/// id telling apart the events recorded at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityEventCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl SecurityEventCursor {
+    pub fn encode(&self) -> String {
+        format!("{}_{}", self.created_at.timestamp_micros(), self.id)
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, AuditDomainError> {
+        let (micros, id) = cursor
+            .split_once('_')
+            .ok_or(AuditDomainError::InvalidCursor)?;
+        let created_at = micros
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(AuditDomainError::InvalidCursor)?;
+        let id = id.parse().map_err(|_| AuditDomainError::InvalidCursor)?;
+
+        Ok(Self { created_at, id })
+    }
+}
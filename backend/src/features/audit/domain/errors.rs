@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AuditDomainError {
+    #[error("Invalid cursor")]
+    InvalidCursor,
+
+    #[error("Database error")]
+    DatabaseError,
+}
@@ -0,0 +1,3 @@
+pub mod security_event_repository;
+
+pub use security_event_repository::SecurityEventRepository;
@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{
+    SecurityEvent, SecurityEventCursor, SecurityEventFilter,
+};
+use crate::features::audit::domain::errors::AuditDomainError;
+
+/// The events can only be added. They are deleted along with their user.
+#[async_trait::async_trait]
+pub trait SecurityEventRepository: Send + Sync {
+    async fn save(&self, event: &SecurityEvent) -> Result<(), AuditDomainError>;
+    /// Most recent first, starting after `after` when set.
+    async fn find_page(
+        &self,
+        filter: &SecurityEventFilter,
+        after: Option<SecurityEventCursor>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError>;
+    /// Most recent first.
+    async fn find_all_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError>;
+}
@@ -0,0 +1,28 @@
+use crate::features::audit::domain::entities::SecurityEvent;
+use crate::features::audit::domain::repositories::SecurityEventRepository;
+
+/// Records the security events of the auth, profile and admin use cases.
+pub struct AuditLog {
+    security_event_repository: Box<dyn SecurityEventRepository>,
+}
+
+impl AuditLog {
+    pub fn new(security_event_repository: Box<dyn SecurityEventRepository>) -> Self {
+        Self {
+            security_event_repository,
+        }
+    }
+
+    /// The action recorded has already happened by then, so failing to record it is only
+    /// logged instead of being reported to the client as if the action had failed.
+    pub async fn record(&self, event: SecurityEvent) {
+        if let Err(e) = self.security_event_repository.save(&event).await {
+            tracing::error!(
+                "Failed to record the security event {} of user {}: {}",
+                event.event_type.as_str(),
+                event.user_id,
+                e
+            );
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod audit_log;
+
+pub use audit_log::AuditLog;
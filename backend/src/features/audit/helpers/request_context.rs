@@ -0,0 +1,12 @@
+use actix_web::HttpRequest;
+
+use crate::core::helpers::client_ip::get_client_ip;
+use crate::features::audit::domain::entities::RequestContext;
+use crate::features::profile::helpers::device_info::get_user_agent;
+
+pub async fn get_request_context(req: HttpRequest) -> RequestContext {
+    let ip = get_client_ip(&req);
+    let device_info = get_user_agent(req).await;
+
+    RequestContext { ip, device_info }
+}
@@ -0,0 +1,3 @@
+pub mod security_event;
+
+pub use security_event::SecurityEventModel;
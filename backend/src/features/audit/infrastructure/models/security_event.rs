@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct SecurityEventModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub event_type: String,
+    pub outcome: String,
+    pub ip: Option<String>,
+    pub os: Option<String>,
+    pub is_mobile: Option<bool>,
+    pub browser: Option<String>,
+    pub app_version: Option<String>,
+    pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<SecurityEventModel> for crate::features::audit::domain::entities::SecurityEvent {
+    type Error = String;
+
+    fn try_from(model: SecurityEventModel) -> Result<Self, Self::Error> {
+        let event_type = SecurityEventType::from_name(&model.event_type)
+            .ok_or_else(|| format!("Unknown security event type: {}", model.event_type))?;
+
+        Ok(Self {
+            id: model.id,
+            user_id: model.user_id,
+            actor_id: model.actor_id,
+            event_type,
+            outcome: SecurityEventOutcome::from_name(&model.outcome),
+            ip: model.ip,
+            os: model.os,
+            is_mobile: model.is_mobile,
+            browser: model.browser,
+            app_version: model.app_version,
+            model: model.model,
+            created_at: model.created_at,
+        })
+    }
+}
+
+impl From<crate::features::audit::domain::entities::SecurityEvent> for SecurityEventModel {
+    fn from(entity: crate::features::audit::domain::entities::SecurityEvent) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            actor_id: entity.actor_id,
+            event_type: entity.event_type.as_str().to_string(),
+            outcome: entity.outcome.as_str().to_string(),
+            ip: entity.ip,
+            os: entity.os,
+            is_mobile: entity.is_mobile,
+            browser: entity.browser,
+            app_version: entity.app_version,
+            model: entity.model,
+            created_at: entity.created_at,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::InMemoryDatabase;
+use crate::features::audit::domain::entities::{
+    SecurityEvent, SecurityEventCursor, SecurityEventFilter,
+};
+use crate::features::audit::domain::errors::AuditDomainError;
+use crate::features::audit::domain::repositories::SecurityEventRepository;
+
+#[derive(Clone)]
+pub struct InMemorySecurityEventRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemorySecurityEventRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    // Most recent first, the id telling apart the events recorded at the same time
+    fn find_where(&self, predicate: impl Fn(&SecurityEvent) -> bool) -> Vec<SecurityEvent> {
+        let tables = self.database.tables();
+
+        let mut events: Vec<SecurityEvent> = tables
+            .security_events
+            .iter()
+            .filter(|event| predicate(event))
+            .cloned()
+            .collect();
+        events.sort_by_key(|event| std::cmp::Reverse((event.created_at, event.id)));
+
+        events
+    }
+}
+
+#[async_trait::async_trait]
+impl SecurityEventRepository for InMemorySecurityEventRepository {
+    async fn save(&self, event: &SecurityEvent) -> Result<(), AuditDomainError> {
+        let mut tables = self.database.write("security_events").map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuditDomainError::DatabaseError
+        })?;
+
+        tables.security_events.push(event.clone());
+
+        Ok(())
+    }
+
+    async fn find_page(
+        &self,
+        filter: &SecurityEventFilter,
+        after: Option<SecurityEventCursor>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError> {
+        let mut events = self.find_where(|event| {
+            filter
+                .user_id
+                .is_none_or(|user_id| event.user_id == user_id)
+                && filter
+                    .event_type
+                    .is_none_or(|event_type| event.event_type == event_type)
+                && filter
+                    .outcome
+                    .is_none_or(|outcome| event.outcome == outcome)
+                && after.is_none_or(|cursor| {
+                    (event.created_at, event.id) < (cursor.created_at, cursor.id)
+                })
+        });
+        events.truncate(limit.max(0) as usize);
+
+        Ok(events)
+    }
+
+    async fn find_all_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError> {
+        Ok(self.find_where(|event| event.user_id == user_id))
+    }
+}
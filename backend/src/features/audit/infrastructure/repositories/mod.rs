@@ -0,0 +1,6 @@
+pub mod security_event_repository_impl;
+
+pub use security_event_repository_impl::SecurityEventRepositoryImpl;
+
+#[cfg(feature = "test-util")]
+pub mod in_memory;
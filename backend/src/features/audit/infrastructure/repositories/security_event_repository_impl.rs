@@ -0,0 +1,127 @@
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{
+    SecurityEvent, SecurityEventCursor, SecurityEventFilter,
+};
+use crate::features::audit::domain::errors::AuditDomainError;
+use crate::features::audit::domain::repositories::SecurityEventRepository;
+use crate::features::audit::infrastructure::models::SecurityEventModel;
+
+#[derive(Clone)]
+pub struct SecurityEventRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl SecurityEventRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// Events of a type this version doesn't know, written by a newer one, are skipped
+fn into_events(models: Vec<SecurityEventModel>) -> Vec<SecurityEvent> {
+    models
+        .into_iter()
+        .filter_map(|model| {
+            SecurityEvent::try_from(model)
+                .map_err(|e| tracing::warn!("{}", e))
+                .ok()
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl SecurityEventRepository for SecurityEventRepositoryImpl {
+    async fn save(&self, event: &SecurityEvent) -> Result<(), AuditDomainError> {
+        let model: SecurityEventModel = event.clone().into();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO security_events (id, user_id, actor_id, event_type, outcome, ip, os,
+                is_mobile, browser, app_version, model, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            "#,
+            model.id,
+            model.user_id,
+            model.actor_id,
+            model.event_type,
+            model.outcome,
+            model.ip,
+            model.os,
+            model.is_mobile,
+            model.browser,
+            model.app_version,
+            model.model,
+            model.created_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuditDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_page(
+        &self,
+        filter: &SecurityEventFilter,
+        after: Option<SecurityEventCursor>,
+        limit: i64,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError> {
+        let events = sqlx::query_as!(
+            SecurityEventModel,
+            r#"
+            SELECT id, user_id, actor_id, event_type, outcome, ip, os, is_mobile, browser,
+                app_version, model, created_at
+            FROM security_events
+            WHERE ($1::UUID IS NULL OR user_id = $1)
+                AND ($2::TEXT IS NULL OR event_type = $2)
+                AND ($3::TEXT IS NULL OR outcome = $3)
+                AND ($4::TIMESTAMPTZ IS NULL OR (created_at, id) < ($4, $5))
+            ORDER BY created_at DESC, id DESC
+            LIMIT $6
+            "#,
+            filter.user_id,
+            filter.event_type.map(|event_type| event_type.as_str()),
+            filter.outcome.map(|outcome| outcome.as_str()),
+            after.map(|cursor| cursor.created_at),
+            after.map(|cursor| cursor.id),
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuditDomainError::DatabaseError
+        })?;
+
+        Ok(into_events(events))
+    }
+
+    async fn find_all_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<SecurityEvent>, AuditDomainError> {
+        let events = sqlx::query_as!(
+            SecurityEventModel,
+            r#"
+            SELECT id, user_id, actor_id, event_type, outcome, ip, os, is_mobile, browser,
+                app_version, model, created_at
+            FROM security_events
+            WHERE user_id = $1
+            ORDER BY created_at DESC, id DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuditDomainError::DatabaseError
+        })?;
+
+        Ok(into_events(events))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod security_event_controller;
+
+pub use security_event_controller::{get_own_security_events, list_security_events};
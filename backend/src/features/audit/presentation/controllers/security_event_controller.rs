@@ -0,0 +1,76 @@
+use actix_web::{get, web, web::ReqData, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::middlewares::permission_guard::PermissionGuard;
+use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::application::dto::{AdminSecurityEventsQuery, SecurityEventsQuery};
+use crate::features::audit::application::usecases::ListSecurityEventsUseCase;
+use crate::features::audit::domain::entities::SecurityEventFilter;
+use crate::features::audit::domain::errors::AuditDomainError;
+use crate::features::auth::domain::entities::{Claims, Permission};
+
+fn audit_error_response(e: AuditDomainError) -> HttpResponse {
+    match e {
+        AuditDomainError::InvalidCursor => HttpResponse::BadRequest().json(GenericResponse {
+            code: "INVALID_CURSOR".to_string(),
+            message: "Invalid cursor".to_string(),
+        }),
+        AuditDomainError::DatabaseError => {
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: "SECURITY_EVENTS_FETCH_ERROR".to_string(),
+                message: "Failed to fetch security events".to_string(),
+            })
+        }
+    }
+}
+
+#[get("/me/security-events")]
+pub async fn get_own_security_events(
+    claims: ReqData<Claims>,
+    query: web::Query<SecurityEventsQuery>,
+    use_case: web::Data<ListSecurityEventsUseCase>,
+) -> impl Responder {
+    let query = query.into_inner();
+    let filter = SecurityEventFilter {
+        user_id: Some(claims.user_id),
+        ..Default::default()
+    };
+
+    match use_case
+        .execute(filter, query.cursor.as_deref(), query.limit)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Get security events error: {}", e);
+            audit_error_response(e)
+        }
+    }
+}
+
+#[get(
+    "/security-events",
+    wrap = "PermissionGuard::new(Permission::ReadSecurityEvents)"
+)]
+pub async fn list_security_events(
+    query: web::Query<AdminSecurityEventsQuery>,
+    use_case: web::Data<ListSecurityEventsUseCase>,
+) -> impl Responder {
+    let query = query.into_inner();
+    let filter = SecurityEventFilter {
+        user_id: query.user_id,
+        event_type: query.event_type,
+        outcome: query.outcome,
+    };
+
+    match use_case
+        .execute(filter, query.cursor.as_deref(), query.limit)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("List security events error: {}", e);
+            audit_error_response(e)
+        }
+    }
+}
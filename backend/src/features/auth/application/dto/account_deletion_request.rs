@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeleteAccountRequest {
+    pub password: String,
+    // Required if 2FA is enabled
+    pub code: Option<String>,
+}
@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccountDeletionResponse {
+    pub code: String,
+    pub deletion_scheduled_at: DateTime<Utc>,
+}
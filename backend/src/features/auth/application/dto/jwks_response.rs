@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+use crate::features::auth::domain::entities::JsonWebKey;
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct JwksResponse {
+    pub keys: Vec<JsonWebKey>,
+}
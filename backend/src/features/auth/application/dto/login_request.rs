@@ -5,4 +5,3 @@ pub struct LoginRequest {
     pub username: String,
     pub password: String,
 }
-
@@ -10,6 +10,6 @@ pub struct LoginResponse {
 #[derive(Serialize, Debug, Deserialize)]
 pub struct LoginWhenOtpEnabledResponse {
     pub code: String,
-    pub user_id: String,
+    // Single-use and short-lived, to be sent with the OTP to complete the login
+    pub mfa_token: String,
 }
-
@@ -1,22 +1,35 @@
+pub mod account_deletion_request;
+pub mod account_deletion_response;
+pub mod jwks_response;
 pub mod login_request;
 pub mod login_response;
 pub mod otp_request;
 pub mod otp_response;
+pub mod recovery_codes_request;
+pub mod recovery_codes_response;
 pub mod recovery_request;
 pub mod refresh_token_request;
 pub mod refresh_token_response;
+pub mod sign_in_report_request;
 pub mod signup_request;
 pub mod signup_response;
 
+pub use account_deletion_request::DeleteAccountRequest;
+pub use account_deletion_response::AccountDeletionResponse;
+pub use jwks_response::JwksResponse;
 pub use login_request::LoginRequest;
 pub use login_response::{LoginResponse, LoginWhenOtpEnabledResponse};
 pub use otp_request::{ValidateOtpRequest, VerifyOtpRequest};
 pub use otp_response::{DisableOtpResponse, GenerateOtpResponse, VerifyOtpResponse};
+pub use recovery_codes_request::RegenerateRecoveryCodesRequest;
+pub use recovery_codes_response::{
+    RegenerateRecoveryCodesResponse, RemainingRecoveryCodesResponse,
+};
 pub use recovery_request::{
     RecoverAccountUsing2FARequest, RecoverAccountUsingPasswordRequest,
     RecoverAccountWithout2FAEnabledRequest,
 };
 pub use refresh_token_request::RefreshTokenRequest;
+pub use sign_in_report_request::ReportSignInRequest;
 pub use signup_request::SignupRequest;
 pub use signup_response::SignupResponse;
-
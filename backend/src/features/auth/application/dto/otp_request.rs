@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct VerifyOtpRequest {
@@ -9,6 +8,5 @@ pub struct VerifyOtpRequest {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ValidateOtpRequest {
     pub code: String,
-    pub user_id: Uuid,
+    pub mfa_token: String,
 }
-
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct RegenerateRecoveryCodesResponse {
+    pub code: String,
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct RemainingRecoveryCodesResponse {
+    pub code: String,
+    pub remaining_recovery_codes: i64,
+}
@@ -4,4 +4,3 @@ use serde::{Deserialize, Serialize};
 pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
-
@@ -5,4 +5,4 @@ pub struct RefreshTokenResponse {
     pub code: String,
     pub access_token: String,
     pub refresh_token: String,
-}
\ No newline at end of file
+}
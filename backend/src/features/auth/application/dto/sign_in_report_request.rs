@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReportSignInRequest {
+    // The `report_token` sent with the new sign-in notification
+    pub token: String,
+}
@@ -7,4 +7,3 @@ pub struct SignupRequest {
     pub locale: String,
     pub theme: String,
 }
-
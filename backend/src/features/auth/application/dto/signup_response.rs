@@ -7,4 +7,3 @@ pub struct SignupResponse {
     pub access_token: String,
     pub refresh_token: String,
 }
-
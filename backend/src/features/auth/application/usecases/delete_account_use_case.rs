@@ -0,0 +1,156 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::application::dto::{AccountDeletionResponse, DeleteAccountRequest};
+use crate::features::auth::domain::entities::{AttemptSubject, User};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::{TokenRepository, UserRepository};
+use crate::features::auth::domain::services::{LoginThrottleService, OtpService};
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct DeleteAccountUseCase {
+    user_repository: Box<dyn UserRepository>,
+    token_repository: Box<dyn TokenRepository>,
+    login_throttle_service: LoginThrottleService,
+    otp_service: OtpService,
+    token_cache: TokenCache,
+    grace_period: Duration,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
+}
+
+impl DeleteAccountUseCase {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        token_repository: Box<dyn TokenRepository>,
+        login_throttle_service: LoginThrottleService,
+        otp_service: OtpService,
+        token_cache: TokenCache,
+        grace_period: Duration,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            token_repository,
+            login_throttle_service,
+            otp_service,
+            token_cache,
+            grace_period,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
+    }
+
+    /// Signs the user out of every device, and deletes the account once the grace period is
+    /// over. Logging in again in the meantime cancels the deletion.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        request: DeleteAccountRequest,
+        context: RequestContext,
+    ) -> Result<AccountDeletionResponse, AuthDomainError> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AuthDomainError::UserNotFound)?;
+
+        // Failed passwords and codes count like failed logins, so a stolen access token can't
+        // be used to guess them
+        let subjects = AttemptSubject::for_login(&user.username, context.ip.as_deref());
+        self.login_throttle_service
+            .ensure_allowed(&subjects)
+            .await?;
+
+        let result = self.schedule_deletion(&user, request, &context).await;
+        self.login_throttle_service
+            .record_outcome(&subjects, &result)
+            .await?;
+
+        result
+    }
+
+    async fn schedule_deletion(
+        &self,
+        user: &User,
+        request: DeleteAccountRequest,
+        context: &RequestContext,
+    ) -> Result<AccountDeletionResponse, AuthDomainError> {
+        let parsed_hash = PasswordHash::new(&user.password_hash)
+            .map_err(|_| AuthDomainError::InvalidCredentials)?;
+        let argon2 = Argon2::default();
+        let is_password_valid = argon2
+            .verify_password(request.password.as_bytes(), &parsed_hash)
+            .is_ok();
+
+        if !is_password_valid {
+            return Err(AuthDomainError::InvalidCredentials);
+        }
+
+        if user.otp_verified {
+            let otp_base32 = user
+                .otp_base32
+                .as_ref()
+                .ok_or(AuthDomainError::OtpNotEnabled)?;
+            let code = request.code.ok_or(AuthDomainError::InvalidOtp)?;
+
+            let is_otp_valid = self
+                .otp_service
+                .verify_code(user.id, otp_base32, &code)
+                .await?;
+
+            if !is_otp_valid {
+                return Err(AuthDomainError::InvalidOtp);
+            }
+        }
+
+        let deletion_scheduled_at = now() + self.grace_period;
+        let token_ids = self
+            .unit_of_work
+            .run(|| async {
+                self.user_repository
+                    .update_deletion_scheduled_at(user.id, Some(deletion_scheduled_at))
+                    .await?;
+
+                let token_ids = self.token_repository.delete_all_by_user_id(user.id).await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(user.id, &token_ids))
+                    .await?;
+
+                Ok::<_, AuthDomainError>(token_ids)
+            })
+            .await?;
+
+        for token_id in token_ids {
+            self.token_cache.remove_key(token_id).await;
+        }
+
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::AccountDeletionScheduled,
+                SecurityEventOutcome::Success,
+                context,
+            ))
+            .await;
+
+        Ok(AccountDeletionResponse {
+            code: "ACCOUNT_DELETION_SCHEDULED".to_string(),
+            deletion_scheduled_at,
+        })
+    }
+}
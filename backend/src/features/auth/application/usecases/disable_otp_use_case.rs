@@ -1,19 +1,43 @@
 use uuid::Uuid;
 
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::application::dto::DisableOtpResponse;
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct DisableOtpUseCase {
     user_repository: Box<dyn UserRepository>,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl DisableOtpUseCase {
-    pub fn new(user_repository: Box<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<DisableOtpResponse, AuthDomainError> {
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        context: RequestContext,
+    ) -> Result<DisableOtpResponse, AuthDomainError> {
         let mut user = self
             .user_repository
             .find_by_id(user_id)
@@ -21,10 +45,28 @@ impl DisableOtpUseCase {
             .ok_or(AuthDomainError::UserNotFound)?;
 
         user.otp_verified = false;
-        user.otp_base32 = None;
-        user.otp_auth_url = None;
 
-        self.user_repository.update(&user).await?;
+        self.unit_of_work
+            .run(|| async {
+                self.user_repository.update(&user).await?;
+                self.user_repository
+                    .update_otp_secret(user.id, None, None)
+                    .await?;
+                self.outbox
+                    .publish(vec![DomainEvent::OtpDisabled { user_id: user.id }])
+                    .await?;
+
+                Ok::<_, AuthDomainError>(())
+            })
+            .await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::OtpDisabled,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
 
         Ok(DisableOtpResponse {
             code: "OTP_DISABLED".to_string(),
@@ -1,19 +1,21 @@
-use base32;
-use rand::Rng;
-use totp_rs::{Algorithm, Secret, TOTP};
 use uuid::Uuid;
 
 use crate::features::auth::application::dto::GenerateOtpResponse;
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::OtpService;
 
 pub struct GenerateOtpUseCase {
     user_repository: Box<dyn UserRepository>,
+    otp_service: OtpService,
 }
 
 impl GenerateOtpUseCase {
-    pub fn new(user_repository: Box<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(user_repository: Box<dyn UserRepository>, otp_service: OtpService) -> Self {
+        Self {
+            user_repository,
+            otp_service,
+        }
     }
 
     pub async fn execute(&self, user_id: Uuid) -> Result<GenerateOtpResponse, AuthDomainError> {
@@ -23,31 +25,14 @@ impl GenerateOtpUseCase {
             .await?
             .ok_or(AuthDomainError::UserNotFound)?;
 
-        let mut rng = rand::thread_rng();
-        let data_byte: [u8; 21] = rng.gen();
-        let base32_string = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &data_byte);
+        let (otp_base32, otp_auth_url) = self.otp_service.generate_secret(&user.username)?;
 
-        let totp = TOTP::new(
-            Algorithm::SHA1,
-            6,
-            1,
-            30,
-            Secret::Encoded(base32_string).to_bytes().unwrap(),
-        )
-        .map_err(|_| AuthDomainError::InvalidOtp)?;
-
-        let otp_base32 = totp.get_secret_base32();
-        let username = user.username.clone();
-        let issuer = "Flutter Actix App";
-
-        let otp_auth_url =
-            format!("otpauth://totp/{issuer}:{username}?secret={otp_base32}&issuer={issuer}");
-
-        user.otp_base32 = Some(otp_base32.clone());
-        user.otp_auth_url = Some(otp_auth_url.clone());
         user.otp_verified = false;
 
         self.user_repository.update(&user).await?;
+        self.user_repository
+            .update_otp_secret(user.id, Some(&otp_base32), Some(&otp_auth_url))
+            .await?;
 
         Ok(GenerateOtpResponse {
             code: "OTP_GENERATED".to_string(),
@@ -56,4 +41,3 @@ impl GenerateOtpUseCase {
         })
     }
 }
-
@@ -0,0 +1,18 @@
+use crate::features::auth::application::dto::JwksResponse;
+use crate::features::auth::domain::repositories::TokenService;
+
+pub struct GetJwksUseCase {
+    token_service: Box<dyn TokenService>,
+}
+
+impl GetJwksUseCase {
+    pub fn new(token_service: Box<dyn TokenService>) -> Self {
+        Self { token_service }
+    }
+
+    pub fn execute(&self) -> JwksResponse {
+        JwksResponse {
+            keys: self.token_service.public_keys(),
+        }
+    }
+}
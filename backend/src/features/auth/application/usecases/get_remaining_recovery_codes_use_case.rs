@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::features::auth::application::dto::RemainingRecoveryCodesResponse;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::services::RecoveryCodeService;
+
+pub struct GetRemainingRecoveryCodesUseCase {
+    recovery_code_service: RecoveryCodeService,
+}
+
+impl GetRemainingRecoveryCodesUseCase {
+    pub fn new(recovery_code_service: RecoveryCodeService) -> Self {
+        Self {
+            recovery_code_service,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+    ) -> Result<RemainingRecoveryCodesResponse, AuthDomainError> {
+        let remaining_recovery_codes = self.recovery_code_service.count_remaining(user_id).await?;
+
+        Ok(RemainingRecoveryCodesResponse {
+            code: "REMAINING_RECOVERY_CODES".to_string(),
+            remaining_recovery_codes,
+        })
+    }
+}
@@ -1,29 +1,49 @@
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 
 use crate::core::helpers::mock_now::now;
-use crate::features::auth::application::dto::{LoginRequest, LoginResponse, LoginWhenOtpEnabledResponse};
-use crate::features::auth::domain::entities::{Claims, DeviceInfo, UserToken};
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::application::dto::{
+    LoginRequest, LoginResponse, LoginWhenOtpEnabledResponse,
+};
+use crate::features::auth::domain::entities::{AttemptSubject, DeviceInfo};
 use crate::features::auth::domain::errors::AuthDomainError;
-use crate::features::auth::domain::repositories::{
-    TokenRepository, TokenService, UserRepository,
+use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::{
+    LoginThrottleService, MfaChallengeService, NewDeviceService, SessionService,
 };
 
 pub struct LoginUseCase {
     user_repository: Box<dyn UserRepository>,
-    token_repository: Box<dyn TokenRepository>,
-    token_service: Box<dyn TokenService>,
+    session_service: SessionService,
+    login_throttle_service: LoginThrottleService,
+    mfa_challenge_service: MfaChallengeService,
+    audit_log: AuditLog,
+    new_device_service: NewDeviceService,
+    unit_of_work: Box<dyn UnitOfWork>,
 }
 
 impl LoginUseCase {
     pub fn new(
         user_repository: Box<dyn UserRepository>,
-        token_repository: Box<dyn TokenRepository>,
-        token_service: Box<dyn TokenService>,
+        session_service: SessionService,
+        login_throttle_service: LoginThrottleService,
+        mfa_challenge_service: MfaChallengeService,
+        audit_log: AuditLog,
+        new_device_service: NewDeviceService,
+        unit_of_work: Box<dyn UnitOfWork>,
     ) -> Self {
         Self {
             user_repository,
-            token_repository,
-            token_service,
+            session_service,
+            login_throttle_service,
+            mfa_challenge_service,
+            audit_log,
+            new_device_service,
+            unit_of_work,
         }
     }
 
@@ -31,6 +51,33 @@ impl LoginUseCase {
         &self,
         request: LoginRequest,
         device_info: DeviceInfo,
+        context: RequestContext,
+    ) -> Result<Result<LoginResponse, LoginWhenOtpEnabledResponse>, AuthDomainError> {
+        let subjects = AttemptSubject::for_login(&request.username, context.ip.as_deref());
+        self.login_throttle_service
+            .ensure_allowed(&subjects)
+            .await?;
+
+        let result = self.login(request, device_info, &context).await;
+
+        match result {
+            // The second factor is still pending, so the account counter must not be reset yet
+            Ok(Err(_)) => {}
+            _ => {
+                self.login_throttle_service
+                    .record_outcome(&subjects, &result)
+                    .await?
+            }
+        }
+
+        result
+    }
+
+    async fn login(
+        &self,
+        request: LoginRequest,
+        device_info: DeviceInfo,
+        context: &RequestContext,
     ) -> Result<Result<LoginResponse, LoginWhenOtpEnabledResponse>, AuthDomainError> {
         let username_lower = request.username.to_lowercase();
 
@@ -49,67 +96,75 @@ impl LoginUseCase {
             .is_ok();
 
         if !is_valid {
+            self.audit_log
+                .record(
+                    SecurityEvent::new(
+                        user.id,
+                        SecurityEventType::LoginFailed,
+                        SecurityEventOutcome::Failure,
+                        context,
+                    )
+                    .with_actor(None),
+                )
+                .await;
             return Err(AuthDomainError::InvalidCredentials);
         }
 
+        if user.is_disabled_at(now()) {
+            return Err(AuthDomainError::AccountDisabled);
+        }
+
         if user.password_is_expired {
             return Err(AuthDomainError::PasswordExpired);
         }
 
         if user.otp_verified {
+            let mfa_token = self.mfa_challenge_service.issue(user.id).await?;
+
             return Ok(Err(LoginWhenOtpEnabledResponse {
                 code: "USER_LOGS_IN_WITH_OTP_ENABLED".to_string(),
-                user_id: user.id.to_string(),
+                mfa_token,
             }));
         }
 
-        // Generate tokens
-        let jti = uuid::Uuid::new_v4();
-        let now_time = now();
-        let access_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::minutes(15))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-
-        let access_token = self.token_service.generate_access_token(&access_claims)?;
-
-        let refresh_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
-
-        // Save token
-        let user_token = UserToken {
-            id: uuid::Uuid::new_v4(),
-            user_id: user.id,
-            token_id: jti,
-            expires_at: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap(),
-            os: device_info.os,
-            is_mobile: device_info.is_mobile,
-            browser: device_info.browser,
-            app_version: device_info.app_version,
-            model: device_info.model,
-        };
-        self.token_repository.save(&user_token).await?;
+        let is_new_device = self
+            .new_device_service
+            .is_new_device(user.id, &device_info)
+            .await?;
+        let tokens = self
+            .unit_of_work
+            .run(|| async {
+                // Logging in again means the user wants to keep the account
+                if user.deletion_scheduled_at.is_some() {
+                    self.user_repository
+                        .update_deletion_scheduled_at(user.id, None)
+                        .await?;
+                }
+
+                // Generate tokens
+                self.session_service
+                    .issue_tokens(user.id, device_info.clone())
+                    .await
+            })
+            .await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::LoginSucceeded,
+                SecurityEventOutcome::Success,
+                context,
+            ))
+            .await;
+        if is_new_device {
+            self.new_device_service
+                .notify(&user, &tokens, device_info, context)
+                .await;
+        }
 
         Ok(Ok(LoginResponse {
             code: "USER_LOGGED_IN_WITHOUT_OTP".to_string(),
-            access_token,
-            refresh_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
         }))
     }
 }
-
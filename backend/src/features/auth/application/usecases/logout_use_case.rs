@@ -1,28 +1,48 @@
 use uuid::Uuid;
 
+use crate::core::structs::unit_of_work::UnitOfWork;
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::TokenRepository;
 use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct LogoutUseCase {
     token_repository: Box<dyn TokenRepository>,
     token_cache: TokenCache,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl LogoutUseCase {
-    pub fn new(token_repository: Box<dyn TokenRepository>, token_cache: TokenCache) -> Self {
+    pub fn new(
+        token_repository: Box<dyn TokenRepository>,
+        token_cache: TokenCache,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
         Self {
             token_repository,
             token_cache,
+            unit_of_work,
+            outbox,
         }
     }
 
-    pub async fn execute(&self, token_id: Uuid) -> Result<(), AuthDomainError> {
+    pub async fn execute(&self, user_id: Uuid, token_id: Uuid) -> Result<(), AuthDomainError> {
         // Delete from database
-        self.token_repository.delete_by_token_id(token_id).await?;
+        self.unit_of_work
+            .run(|| async {
+                self.token_repository.delete_by_token_id(token_id).await?;
+                self.outbox
+                    .publish(vec![DomainEvent::SessionRevoked { user_id, token_id }])
+                    .await?;
+
+                Ok::<_, AuthDomainError>(())
+            })
+            .await?;
         // Remove from cache
         self.token_cache.remove_key(token_id).await;
         Ok(())
     }
 }
-
@@ -1,24 +1,33 @@
+pub mod delete_account_use_case;
 pub mod disable_otp_use_case;
 pub mod generate_otp_use_case;
+pub mod get_jwks_use_case;
+pub mod get_remaining_recovery_codes_use_case;
 pub mod login_use_case;
 pub mod logout_use_case;
 pub mod recover_account_using_2fa_use_case;
 pub mod recover_account_using_password_use_case;
 pub mod recover_account_without_2fa_enabled_use_case;
 pub mod refresh_token_use_case;
+pub mod regenerate_recovery_codes_use_case;
+pub mod report_sign_in_use_case;
 pub mod signup_use_case;
 pub mod validate_otp_use_case;
 pub mod verify_otp_use_case;
 
+pub use delete_account_use_case::DeleteAccountUseCase;
 pub use disable_otp_use_case::DisableOtpUseCase;
 pub use generate_otp_use_case::GenerateOtpUseCase;
+pub use get_jwks_use_case::GetJwksUseCase;
+pub use get_remaining_recovery_codes_use_case::GetRemainingRecoveryCodesUseCase;
 pub use login_use_case::LoginUseCase;
 pub use logout_use_case::LogoutUseCase;
 pub use recover_account_using_2fa_use_case::RecoverAccountUsing2FAUseCase;
 pub use recover_account_using_password_use_case::RecoverAccountUsingPasswordUseCase;
 pub use recover_account_without_2fa_enabled_use_case::RecoverAccountWithout2FAEnabledUseCase;
 pub use refresh_token_use_case::RefreshTokenUseCase;
+pub use regenerate_recovery_codes_use_case::RegenerateRecoveryCodesUseCase;
+pub use report_sign_in_use_case::ReportSignInUseCase;
 pub use signup_use_case::SignupUseCase;
 pub use validate_otp_use_case::ValidateOtpUseCase;
 pub use verify_otp_use_case::VerifyOtpUseCase;
-
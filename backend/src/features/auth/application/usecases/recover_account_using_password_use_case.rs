@@ -1,27 +1,53 @@
 use argon2::{Argon2, PasswordHash, PasswordVerifier};
 
 use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::application::dto::{LoginResponse, RecoverAccountUsingPasswordRequest};
-use crate::features::auth::domain::entities::{Claims, DeviceInfo, UserToken};
+use crate::features::auth::domain::entities::{AttemptSubject, DeviceInfo};
 use crate::features::auth::domain::errors::AuthDomainError;
-use crate::features::auth::domain::repositories::{TokenRepository, TokenService, UserRepository};
+use crate::features::auth::domain::repositories::{TokenRepository, UserRepository};
+use crate::features::auth::domain::services::{
+    LoginThrottleService, RecoveryCodeService, SessionService,
+};
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct RecoverAccountUsingPasswordUseCase {
     user_repository: Box<dyn UserRepository>,
     token_repository: Box<dyn TokenRepository>,
-    token_service: Box<dyn TokenService>,
+    session_service: SessionService,
+    login_throttle_service: LoginThrottleService,
+    recovery_code_service: RecoveryCodeService,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl RecoverAccountUsingPasswordUseCase {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: Box<dyn UserRepository>,
         token_repository: Box<dyn TokenRepository>,
-        token_service: Box<dyn TokenService>,
+        session_service: SessionService,
+        login_throttle_service: LoginThrottleService,
+        recovery_code_service: RecoveryCodeService,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
     ) -> Self {
         Self {
             user_repository,
             token_repository,
-            token_service,
+            session_service,
+            login_throttle_service,
+            recovery_code_service,
+            audit_log,
+            unit_of_work,
+            outbox,
         }
     }
 
@@ -29,6 +55,26 @@ impl RecoverAccountUsingPasswordUseCase {
         &self,
         request: RecoverAccountUsingPasswordRequest,
         device_info: DeviceInfo,
+        context: RequestContext,
+    ) -> Result<LoginResponse, AuthDomainError> {
+        let subjects = AttemptSubject::for_login(&request.username, context.ip.as_deref());
+        self.login_throttle_service
+            .ensure_allowed(&subjects)
+            .await?;
+
+        let result = self.recover(request, device_info, &context).await;
+        self.login_throttle_service
+            .record_outcome(&subjects, &result)
+            .await?;
+
+        result
+    }
+
+    async fn recover(
+        &self,
+        request: RecoverAccountUsingPasswordRequest,
+        device_info: DeviceInfo,
+        context: &RequestContext,
     ) -> Result<LoginResponse, AuthDomainError> {
         let username_lower = request.username.to_lowercase();
 
@@ -55,96 +101,83 @@ impl RecoverAccountUsingPasswordUseCase {
             return Err(AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode);
         }
 
-        // Verify recovery code
-        let mut recovery_code_valid = false;
-        let mut updated_recovery_codes = Vec::<String>::new();
-
-        for recovery_code_hash in user.recovery_codes.split(";") {
-            if recovery_code_hash.is_empty() {
-                continue;
-            }
-
-            let parsed_hash = PasswordHash::new(recovery_code_hash)
-                .map_err(|_| AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode)?;
-            let argon2 = Argon2::default();
-            let is_valid = argon2
-                .verify_password(request.recovery_code.as_bytes(), &parsed_hash)
-                .is_ok();
-
-            if is_valid {
-                recovery_code_valid = true;
-                // Don't add this recovery code to the updated list
-                continue;
-            } else {
-                updated_recovery_codes.push(recovery_code_hash.to_string());
-            }
-        }
-
-        if !recovery_code_valid {
-            return Err(AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode);
-        }
-
-        // Update recovery codes (remove the used one) and disable OTP
-        user.recovery_codes = updated_recovery_codes.join(";");
-        user.otp_verified = false;
-        user.otp_base32 = None;
-        user.otp_auth_url = None;
-
-        // Delete all existing tokens for this user
-        self.token_repository
-            .delete_all_by_user_id(user.id)
+        // The recovery code is only consumed if the account is recovered
+        let user_id = user.id;
+        let tokens = self
+            .unit_of_work
+            .run(|| async move {
+                let recovery_code_valid = self
+                    .recovery_code_service
+                    .consume(user.id, &request.recovery_code)
+                    .await?;
+
+                if !recovery_code_valid {
+                    self.audit_log
+                        .record(
+                            SecurityEvent::new(
+                                user.id,
+                                SecurityEventType::RecoveryCodeUsed,
+                                SecurityEventOutcome::Failure,
+                                context,
+                            )
+                            .with_actor(None),
+                        )
+                        .await;
+                    return Err(AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode);
+                }
+
+                if user.is_disabled_at(now()) {
+                    return Err(AuthDomainError::AccountDisabled);
+                }
+
+                // Disable OTP
+                user.otp_verified = false;
+
+                if user.deletion_scheduled_at.is_some() {
+                    self.user_repository
+                        .update_deletion_scheduled_at(user.id, None)
+                        .await?;
+                }
+
+                // Delete all existing tokens for this user
+                let revoked_token_ids =
+                    self.token_repository.delete_all_by_user_id(user.id).await?;
+
+                // Generate new tokens
+                let tokens = self
+                    .session_service
+                    .issue_tokens(user.id, device_info)
+                    .await?;
+
+                // Update user with disabled OTP
+                self.user_repository.update(&user).await?;
+                self.user_repository
+                    .update_otp_secret(user.id, None, None)
+                    .await?;
+
+                let mut events = vec![
+                    DomainEvent::AccountRecovered { user_id: user.id },
+                    DomainEvent::OtpDisabled { user_id: user.id },
+                ];
+                events.extend(DomainEvent::sessions_revoked(user.id, &revoked_token_ids));
+                self.outbox.publish(events).await?;
+
+                Ok::<_, AuthDomainError>(tokens)
+            })
             .await?;
-
-        // Generate new tokens
-        let jti = uuid::Uuid::new_v4();
-        let now_time = now();
-        let access_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::minutes(15))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-
-        let access_token = self.token_service.generate_access_token(&access_claims)?;
-
-        let refresh_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
-
-        // Save new token
-        let user_token = UserToken {
-            id: uuid::Uuid::new_v4(),
-            user_id: user.id,
-            token_id: jti,
-            expires_at: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap(),
-            os: device_info.os,
-            is_mobile: device_info.is_mobile,
-            browser: device_info.browser,
-            app_version: device_info.app_version,
-            model: device_info.model,
-        };
-        self.token_repository.save(&user_token).await?;
-
-        // Update user with new recovery codes and disabled OTP
-        self.user_repository.update(&user).await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user_id,
+                SecurityEventType::RecoveryCodeUsed,
+                SecurityEventOutcome::Success,
+                context,
+            ))
+            .await;
 
         Ok(LoginResponse {
             code: "USER_LOGGED_IN_AFTER_ACCOUNT_RECOVERY".to_string(),
-            access_token,
-            refresh_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
         })
     }
 }
-
@@ -1,23 +1,51 @@
 use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::application::dto::refresh_token_response::RefreshTokenResponse;
 use crate::features::auth::application::dto::RefreshTokenRequest;
-use crate::features::auth::domain::entities::{Claims, DeviceInfo, UserToken};
+use crate::features::auth::domain::entities::{DeviceInfo, TokenType, UserToken};
 use crate::features::auth::domain::errors::AuthDomainError;
-use crate::features::auth::domain::repositories::{TokenRepository, TokenService};
+use crate::features::auth::domain::repositories::{TokenRepository, TokenService, UserRepository};
+use crate::features::auth::domain::services::SessionService;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct RefreshTokenUseCase {
+    user_repository: Box<dyn UserRepository>,
     token_repository: Box<dyn TokenRepository>,
     token_service: Box<dyn TokenService>,
+    audit_log: AuditLog,
+    session_service: SessionService,
+    token_cache: TokenCache,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl RefreshTokenUseCase {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        user_repository: Box<dyn UserRepository>,
         token_repository: Box<dyn TokenRepository>,
         token_service: Box<dyn TokenService>,
+        audit_log: AuditLog,
+        session_service: SessionService,
+        token_cache: TokenCache,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
     ) -> Self {
         Self {
+            user_repository,
             token_repository,
             token_service,
+            audit_log,
+            session_service,
+            token_cache,
+            unit_of_work,
+            outbox,
         }
     }
 
@@ -25,9 +53,12 @@ impl RefreshTokenUseCase {
         &self,
         request: RefreshTokenRequest,
         device_info: DeviceInfo,
+        context: RequestContext,
     ) -> Result<RefreshTokenResponse, AuthDomainError> {
         // Decode refresh token
-        let claims = self.token_service.decode_token(&request.refresh_token)?;
+        let claims = self
+            .token_service
+            .decode_token(&request.refresh_token, TokenType::Refresh)?;
 
         // Check if token exists in database
         let stored_token = self
@@ -37,6 +68,11 @@ impl RefreshTokenUseCase {
 
         let token = stored_token.ok_or(AuthDomainError::InvalidToken)?;
 
+        // A token that was already rotated is being replayed: it has probably been stolen
+        if token.rotated_at.is_some() {
+            return Err(self.revoke_token_family(&token, &context).await);
+        }
+
         // Check if token expired
         if now() > token.expires_at {
             // Remove expired token
@@ -44,55 +80,89 @@ impl RefreshTokenUseCase {
             return Err(AuthDomainError::TokenExpired);
         }
 
-        // Delete old token
-        self.token_repository.delete_by_token_id(claims.jti).await?;
-
-        // Generate new tokens
-        let new_jti = uuid::Uuid::new_v4();
-        let now_time = now();
-        let access_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::minutes(15))
-                .unwrap()
-                .timestamp(),
-            jti: new_jti,
-            user_id: claims.user_id,
-            is_admin: claims.is_admin,
-        };
+        let user = self
+            .user_repository
+            .find_by_id(token.user_id)
+            .await?
+            .ok_or(AuthDomainError::InvalidToken)?;
 
-        let access_token = self.token_service.generate_access_token(&access_claims)?;
+        if user.is_disabled_at(now()) {
+            return Err(AuthDomainError::AccountDisabled);
+        }
 
-        let refresh_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap()
-                .timestamp(),
-            jti: new_jti,
-            user_id: claims.user_id,
-            is_admin: claims.is_admin,
+        // Rotate the token, keeping the old one to detect a later reuse
+        let tokens = match self
+            .unit_of_work
+            .run(|| {
+                self.session_service
+                    .rotate_tokens(&token, device_info.clone())
+            })
+            .await
+        {
+            Ok(tokens) => tokens,
+            Err(AuthDomainError::RefreshTokenReused) => {
+                return Err(self.revoke_token_family(&token, &context).await);
+            }
+            Err(e) => return Err(e),
         };
-        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
-
-        // Save new token
-        let user_token = UserToken {
-            id: uuid::Uuid::new_v4(),
-            user_id: claims.user_id,
-            token_id: new_jti,
-            expires_at: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap(),
-            os: device_info.os,
-            is_mobile: device_info.is_mobile,
-            browser: device_info.browser,
-            app_version: device_info.app_version,
-            model: device_info.model,
-        };
-        self.token_repository.save(&user_token).await?;
 
         Ok(RefreshTokenResponse {
             code: "TOKEN_REFRESHED".to_string(),
-            access_token,
-            refresh_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
         })
     }
+
+    async fn revoke_token_family(
+        &self,
+        token: &UserToken,
+        context: &RequestContext,
+    ) -> AuthDomainError {
+        tracing::warn!(
+            "Refresh token reuse detected for user {}, revoking token family {}",
+            token.user_id,
+            token.family_id
+        );
+
+        match self
+            .unit_of_work
+            .run(|| async {
+                let revoked_token_ids = self
+                    .token_repository
+                    .delete_all_by_family_id(token.family_id)
+                    .await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(
+                        token.user_id,
+                        &revoked_token_ids,
+                    ))
+                    .await?;
+
+                Ok::<_, AuthDomainError>(revoked_token_ids)
+            })
+            .await
+        {
+            Ok(revoked_token_ids) => {
+                for token_id in revoked_token_ids {
+                    self.token_cache.remove_key(token_id).await;
+                }
+            }
+            Err(e) => return e,
+        }
+
+        // Whoever replayed the token can't be told apart from the user
+        self.audit_log
+            .record(
+                SecurityEvent::new(
+                    token.user_id,
+                    SecurityEventType::RefreshTokenReuseDetected,
+                    SecurityEventOutcome::Failure,
+                    context,
+                )
+                .with_actor(None),
+            )
+            .await;
+
+        AuthDomainError::RefreshTokenReused
+    }
 }
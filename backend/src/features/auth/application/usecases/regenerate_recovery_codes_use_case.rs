@@ -0,0 +1,98 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use uuid::Uuid;
+
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::application::dto::{
+    RegenerateRecoveryCodesRequest, RegenerateRecoveryCodesResponse,
+};
+use crate::features::auth::domain::entities::{AttemptSubject, User};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::{LoginThrottleService, RecoveryCodeService};
+
+pub struct RegenerateRecoveryCodesUseCase {
+    user_repository: Box<dyn UserRepository>,
+    login_throttle_service: LoginThrottleService,
+    recovery_code_service: RecoveryCodeService,
+    audit_log: AuditLog,
+}
+
+impl RegenerateRecoveryCodesUseCase {
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        login_throttle_service: LoginThrottleService,
+        recovery_code_service: RecoveryCodeService,
+        audit_log: AuditLog,
+    ) -> Self {
+        Self {
+            user_repository,
+            login_throttle_service,
+            recovery_code_service,
+            audit_log,
+        }
+    }
+
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        request: RegenerateRecoveryCodesRequest,
+        context: RequestContext,
+    ) -> Result<RegenerateRecoveryCodesResponse, AuthDomainError> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AuthDomainError::UserNotFound)?;
+
+        // Failed passwords count like failed logins, so a stolen access token can't be used
+        // to guess the password
+        let subjects = AttemptSubject::for_login(&user.username, context.ip.as_deref());
+        self.login_throttle_service
+            .ensure_allowed(&subjects)
+            .await?;
+
+        let result = self.regenerate(&user, request, &context).await;
+        self.login_throttle_service
+            .record_outcome(&subjects, &result)
+            .await?;
+
+        result
+    }
+
+    async fn regenerate(
+        &self,
+        user: &User,
+        request: RegenerateRecoveryCodesRequest,
+        context: &RequestContext,
+    ) -> Result<RegenerateRecoveryCodesResponse, AuthDomainError> {
+        // The password is asked again, an access token alone is not enough
+        let parsed_hash = PasswordHash::new(&user.password_hash)
+            .map_err(|_| AuthDomainError::InvalidCredentials)?;
+        let argon2 = Argon2::default();
+        let is_password_valid = argon2
+            .verify_password(request.password.as_bytes(), &parsed_hash)
+            .is_ok();
+
+        if !is_password_valid {
+            return Err(AuthDomainError::InvalidCredentials);
+        }
+
+        let recovery_codes = self.recovery_code_service.regenerate(user.id).await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::RecoveryCodesRegenerated,
+                SecurityEventOutcome::Success,
+                context,
+            ))
+            .await;
+
+        Ok(RegenerateRecoveryCodesResponse {
+            code: "RECOVERY_CODES_REGENERATED".to_string(),
+            recovery_codes,
+        })
+    }
+}
@@ -0,0 +1,83 @@
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::application::dto::ReportSignInRequest;
+use crate::features::auth::domain::entities::TokenType;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::{TokenRepository, TokenService};
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+
+pub struct ReportSignInUseCase {
+    token_repository: Box<dyn TokenRepository>,
+    token_service: Box<dyn TokenService>,
+    token_cache: TokenCache,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
+}
+
+impl ReportSignInUseCase {
+    pub fn new(
+        token_repository: Box<dyn TokenRepository>,
+        token_service: Box<dyn TokenService>,
+        token_cache: TokenCache,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            token_repository,
+            token_service,
+            token_cache,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
+    }
+
+    /// Revokes the session the new sign-in notification was sent for. Fails with
+    /// `InvalidSignInReportLink` if the link was tampered with or expired.
+    pub async fn execute(
+        &self,
+        request: ReportSignInRequest,
+        context: RequestContext,
+    ) -> Result<(), AuthDomainError> {
+        let claims = self
+            .token_service
+            .decode_token(&request.token, TokenType::SignInReport)
+            .map_err(|_| AuthDomainError::InvalidSignInReportLink)?;
+
+        // Reporting the same sign-in again does nothing, the session is already gone
+        let token_ids = self
+            .unit_of_work
+            .run(|| async {
+                let token_ids = self
+                    .token_repository
+                    .delete_all_by_family_id(claims.jti)
+                    .await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(claims.user_id, &token_ids))
+                    .await?;
+
+                Ok::<_, AuthDomainError>(token_ids)
+            })
+            .await?;
+        for token_id in token_ids {
+            self.token_cache.remove_key(token_id).await;
+        }
+        self.audit_log
+            .record(SecurityEvent::new(
+                claims.user_id,
+                SecurityEventType::SignInReported,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
+
+        Ok(())
+    }
+}
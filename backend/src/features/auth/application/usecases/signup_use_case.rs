@@ -1,35 +1,39 @@
 use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
-use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use rand::rngs::OsRng;
 use uuid::Uuid;
 
 use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
 use crate::features::auth::application::dto::{SignupRequest, SignupResponse};
-use crate::features::auth::domain::entities::{DeviceInfo, User};
+use crate::features::auth::domain::entities::{AccountStatus, DeviceInfo, User};
 use crate::features::auth::domain::errors::AuthDomainError;
-use crate::features::auth::domain::repositories::{
-    TokenRepository, TokenService, UserRepository,
-};
+use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::{RecoveryCodeService, SessionService};
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct SignupUseCase {
     user_repository: Box<dyn UserRepository>,
-    token_repository: Box<dyn TokenRepository>,
-    token_service: Box<dyn TokenService>,
-    #[allow(dead_code)] // Stored for potential future use
-    secret_key: Vec<u8>,
+    session_service: SessionService,
+    recovery_code_service: RecoveryCodeService,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl SignupUseCase {
     pub fn new(
         user_repository: Box<dyn UserRepository>,
-        token_repository: Box<dyn TokenRepository>,
-        token_service: Box<dyn TokenService>,
-        secret_key: Vec<u8>,
+        session_service: SessionService,
+        recovery_code_service: RecoveryCodeService,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
     ) -> Self {
         Self {
             user_repository,
-            token_repository,
-            token_service,
-            secret_key,
+            session_service,
+            recovery_code_service,
+            unit_of_work,
+            outbox,
         }
     }
 
@@ -40,9 +44,14 @@ impl SignupUseCase {
     ) -> Result<SignupResponse, AuthDomainError> {
         // Validate username and password
         let username_lower = request.username.to_lowercase();
-        
+
         // Check if user already exists
-        if (self.user_repository.find_by_username(&username_lower).await?).is_some() {
+        if (self
+            .user_repository
+            .find_by_username(&username_lower)
+            .await?)
+            .is_some()
+        {
             return Err(AuthDomainError::UserAlreadyExists);
         }
 
@@ -54,26 +63,6 @@ impl SignupUseCase {
             .map_err(|_| AuthDomainError::InvalidPassword)?
             .to_string();
 
-        // Generate recovery codes
-        let mut clear_recovery_codes = Vec::new();
-        let mut hashed_recovery_codes = Vec::new();
-        for _ in 0..5 {
-            let code: String = rand::thread_rng()
-                .sample_iter(&Alphanumeric)
-                .take(16)
-                .map(char::from)
-                .collect();
-
-            clear_recovery_codes.push(code.clone());
-
-            let hashed_code = argon2
-                .hash_password(code.as_bytes(), &salt)
-                .map_err(|_| AuthDomainError::InvalidPassword)?
-                .to_string();
-
-            hashed_recovery_codes.push(hashed_code);
-        }
-
         // Create user entity
         let user_id = Uuid::new_v4();
         let now_time = now();
@@ -83,59 +72,47 @@ impl SignupUseCase {
             password_hash,
             locale: request.locale,
             theme: request.theme,
-            is_admin: false,
             otp_verified: false,
             otp_base32: None,
             otp_auth_url: None,
-            recovery_codes: hashed_recovery_codes.join(";"),
+            otp_last_used_step: None,
             password_is_expired: false,
+            status: AccountStatus::Active,
+            status_reason: None,
+            status_until: None,
+            deletion_scheduled_at: None,
             created_at: now_time,
             updated_at: now_time,
         };
 
-        // Save user
-        self.user_repository.create(&user).await?;
+        let (recovery_codes, tokens) = self
+            .unit_of_work
+            .run(|| async move {
+                // Save user
+                self.user_repository.create(&user).await?;
 
-        // Generate tokens
-        let jti = Uuid::new_v4();
-        use crate::features::auth::domain::entities::Claims;
-        let claims = Claims {
-            exp: now_time.checked_add_signed(chrono::Duration::minutes(15)).unwrap().timestamp(),
-            jti,
-            user_id,
-            is_admin: false,
-        };
+                // Generate recovery codes
+                let recovery_codes = self.recovery_code_service.regenerate(user_id).await?;
 
-        let access_token = self.token_service.generate_access_token(&claims)?;
-        let refresh_claims = Claims {
-            exp: now_time.checked_add_signed(chrono::Duration::days(7)).unwrap().timestamp(),
-            jti,
-            user_id,
-            is_admin: false,
-        };
-        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
+                // Generate tokens
+                let tokens = self
+                    .session_service
+                    .issue_tokens(user_id, device_info)
+                    .await?;
 
-        // Save token
-        use crate::features::auth::domain::entities::UserToken;
-        let user_token = UserToken {
-            id: Uuid::new_v4(),
-            user_id,
-            token_id: jti,
-            expires_at: now_time.checked_add_signed(chrono::Duration::days(7)).unwrap(),
-            os: device_info.os,
-            is_mobile: device_info.is_mobile,
-            browser: device_info.browser,
-            app_version: device_info.app_version,
-            model: device_info.model,
-        };
-        self.token_repository.save(&user_token).await?;
+                self.outbox
+                    .publish(vec![DomainEvent::UserSignedUp { user_id }])
+                    .await?;
+
+                Ok::<_, AuthDomainError>((recovery_codes, tokens))
+            })
+            .await?;
 
         Ok(SignupResponse {
             code: "USER_SIGNED_UP".to_string(),
-            recovery_codes: clear_recovery_codes,
-            access_token,
-            refresh_token,
+            recovery_codes,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
         })
     }
 }
-
@@ -1,28 +1,49 @@
 use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::application::dto::{LoginResponse, ValidateOtpRequest};
-use crate::features::auth::domain::entities::{Claims, DeviceInfo, UserToken};
+use crate::features::auth::domain::entities::{AttemptSubject, DeviceInfo, MfaChallenge, User};
 use crate::features::auth::domain::errors::AuthDomainError;
-use crate::features::auth::domain::repositories::{
-    TokenRepository, TokenService, UserRepository,
+use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::{
+    LoginThrottleService, MfaChallengeService, NewDeviceService, OtpService, SessionService,
 };
-use totp_rs::{Algorithm, Secret, TOTP};
 
 pub struct ValidateOtpUseCase {
     user_repository: Box<dyn UserRepository>,
-    token_repository: Box<dyn TokenRepository>,
-    token_service: Box<dyn TokenService>,
+    session_service: SessionService,
+    login_throttle_service: LoginThrottleService,
+    otp_service: OtpService,
+    mfa_challenge_service: MfaChallengeService,
+    audit_log: AuditLog,
+    new_device_service: NewDeviceService,
+    unit_of_work: Box<dyn UnitOfWork>,
 }
 
 impl ValidateOtpUseCase {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repository: Box<dyn UserRepository>,
-        token_repository: Box<dyn TokenRepository>,
-        token_service: Box<dyn TokenService>,
+        session_service: SessionService,
+        login_throttle_service: LoginThrottleService,
+        otp_service: OtpService,
+        mfa_challenge_service: MfaChallengeService,
+        audit_log: AuditLog,
+        new_device_service: NewDeviceService,
+        unit_of_work: Box<dyn UnitOfWork>,
     ) -> Self {
         Self {
             user_repository,
-            token_repository,
-            token_service,
+            session_service,
+            login_throttle_service,
+            otp_service,
+            mfa_challenge_service,
+            audit_log,
+            new_device_service,
+            unit_of_work,
         }
     }
 
@@ -30,13 +51,49 @@ impl ValidateOtpUseCase {
         &self,
         request: ValidateOtpRequest,
         device_info: DeviceInfo,
+        context: RequestContext,
     ) -> Result<LoginResponse, AuthDomainError> {
+        // Only a client that went through the password step holds a challenge
+        let challenge = self
+            .mfa_challenge_service
+            .verify(&request.mfa_token)
+            .await?;
+
         let user = self
             .user_repository
-            .find_by_id(request.user_id)
+            .find_by_id(challenge.user_id)
             .await?
             .ok_or(AuthDomainError::UserNotFound)?;
 
+        // Shares the account counter with the password step, so a code can't be brute-forced
+        // by alternating between the two
+        let subjects = AttemptSubject::for_login(&user.username, context.ip.as_deref());
+        self.login_throttle_service
+            .ensure_allowed(&subjects)
+            .await?;
+
+        let result = self
+            .validate(user, challenge, request, device_info, &context)
+            .await;
+        self.login_throttle_service
+            .record_outcome(&subjects, &result)
+            .await?;
+
+        result
+    }
+
+    async fn validate(
+        &self,
+        user: User,
+        challenge: MfaChallenge,
+        request: ValidateOtpRequest,
+        device_info: DeviceInfo,
+        context: &RequestContext,
+    ) -> Result<LoginResponse, AuthDomainError> {
+        if user.is_disabled_at(now()) {
+            return Err(AuthDomainError::AccountDisabled);
+        }
+
         if !user.otp_verified {
             return Err(AuthDomainError::OtpNotEnabled);
         }
@@ -46,68 +103,66 @@ impl ValidateOtpUseCase {
             .as_ref()
             .ok_or(AuthDomainError::OtpNotEnabled)?;
 
-        let totp = TOTP::new(
-            Algorithm::SHA1,
-            6,
-            1,
-            30,
-            Secret::Encoded(otp_base32.clone()).to_bytes().unwrap(),
-        )
-        .map_err(|_| AuthDomainError::InvalidOtp)?;
-
-        let is_valid = totp.check_current(&request.code).unwrap_or(false);
+        let is_valid = self
+            .otp_service
+            .verify_code(user.id, otp_base32, &request.code)
+            .await?;
 
         if !is_valid {
+            // The password step succeeded, but that doesn't make the client the user
+            self.audit_log
+                .record(
+                    SecurityEvent::new(
+                        user.id,
+                        SecurityEventType::LoginFailed,
+                        SecurityEventOutcome::Failure,
+                        context,
+                    )
+                    .with_actor(None),
+                )
+                .await;
             return Err(AuthDomainError::InvalidOtp);
         }
 
-        // Generate tokens
-        let jti = uuid::Uuid::new_v4();
-        let now_time = now();
-        let access_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::minutes(15))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-
-        let access_token = self.token_service.generate_access_token(&access_claims)?;
-
-        let refresh_claims = Claims {
-            exp: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap()
-                .timestamp(),
-            jti,
-            user_id: user.id,
-            is_admin: user.is_admin,
-        };
-        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
-
-        // Save token
-        let user_token = UserToken {
-            id: uuid::Uuid::new_v4(),
-            user_id: user.id,
-            token_id: jti,
-            expires_at: now_time
-                .checked_add_signed(chrono::Duration::days(7))
-                .unwrap(),
-            os: device_info.os,
-            is_mobile: device_info.is_mobile,
-            browser: device_info.browser,
-            app_version: device_info.app_version,
-            model: device_info.model,
-        };
-        self.token_repository.save(&user_token).await?;
+        let is_new_device = self
+            .new_device_service
+            .is_new_device(user.id, &device_info)
+            .await?;
+        let tokens = self
+            .unit_of_work
+            .run(|| async {
+                self.mfa_challenge_service.consume(&challenge).await?;
+
+                if user.deletion_scheduled_at.is_some() {
+                    self.user_repository
+                        .update_deletion_scheduled_at(user.id, None)
+                        .await?;
+                }
+
+                // Generate tokens
+                self.session_service
+                    .issue_tokens(user.id, device_info.clone())
+                    .await
+            })
+            .await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::LoginSucceeded,
+                SecurityEventOutcome::Success,
+                context,
+            ))
+            .await;
+        if is_new_device {
+            self.new_device_service
+                .notify(&user, &tokens, device_info, context)
+                .await;
+        }
 
         Ok(LoginResponse {
             code: "USER_LOGGED_IN_AFTER_OTP_VALIDATION".to_string(),
-            access_token,
-            refresh_token,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
         })
     }
 }
-
@@ -1,24 +1,48 @@
-use totp_rs::{Algorithm, Secret, TOTP};
 use uuid::Uuid;
 
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::application::dto::VerifyOtpRequest;
 use crate::features::auth::application::dto::VerifyOtpResponse;
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::UserRepository;
+use crate::features::auth::domain::services::OtpService;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 
 pub struct VerifyOtpUseCase {
     user_repository: Box<dyn UserRepository>,
+    otp_service: OtpService,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl VerifyOtpUseCase {
-    pub fn new(user_repository: Box<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        otp_service: OtpService,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            otp_service,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
     }
 
     pub async fn execute(
         &self,
         user_id: Uuid,
         request: VerifyOtpRequest,
+        context: RequestContext,
     ) -> Result<VerifyOtpResponse, AuthDomainError> {
         let mut user = self
             .user_repository
@@ -31,23 +55,34 @@ impl VerifyOtpUseCase {
             .as_ref()
             .ok_or(AuthDomainError::OtpNotEnabled)?;
 
-        let totp = TOTP::new(
-            Algorithm::SHA1,
-            6,
-            1,
-            30,
-            Secret::Encoded(otp_base32.clone()).to_bytes().unwrap(),
-        )
-        .map_err(|_| AuthDomainError::InvalidOtp)?;
-
-        let is_valid = totp.check_current(&request.code).unwrap_or(false);
+        let is_valid = self
+            .otp_service
+            .verify_code(user.id, otp_base32, &request.code)
+            .await?;
 
         if !is_valid {
             return Err(AuthDomainError::InvalidOtp);
         }
 
         user.otp_verified = true;
-        self.user_repository.update(&user).await?;
+        self.unit_of_work
+            .run(|| async {
+                self.user_repository.update(&user).await?;
+                self.outbox
+                    .publish(vec![DomainEvent::OtpEnabled { user_id: user.id }])
+                    .await?;
+
+                Ok::<_, AuthDomainError>(())
+            })
+            .await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user.id,
+                SecurityEventType::OtpEnabled,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
 
         Ok(VerifyOtpResponse {
             code: "OTP_VERIFIED".to_string(),
@@ -55,4 +90,3 @@ impl VerifyOtpUseCase {
         })
     }
 }
-
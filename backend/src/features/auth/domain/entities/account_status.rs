@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    Active,
+    Suspended,
+    Banned,
+}
+
+impl AccountStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountStatus::Active => "active",
+            AccountStatus::Suspended => "suspended",
+            AccountStatus::Banned => "banned",
+        }
+    }
+
+    // Unknown statuses disable the account rather than let it in
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "active" => AccountStatus::Active,
+            "suspended" => AccountStatus::Suspended,
+            _ => AccountStatus::Banned,
+        }
+    }
+
+    /// Whether an account with this status can't be used at `now`. A suspension or a ban
+    /// without an end date lasts until the account is reactivated.
+    pub fn is_disabled_at(&self, until: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+        match self {
+            AccountStatus::Active => false,
+            AccountStatus::Suspended | AccountStatus::Banned => {
+                until.is_none_or(|until| now < until)
+            }
+        }
+    }
+}
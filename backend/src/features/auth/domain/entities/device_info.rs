@@ -8,4 +8,3 @@ pub struct DeviceInfo {
     pub app_version: Option<String>,
     pub model: Option<String>,
 }
-
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptScope {
+    Account,
+    Ip,
+}
+
+impl AttemptScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptScope::Account => "account",
+            AttemptScope::Ip => "ip",
+        }
+    }
+}
+
+/// What failed authentication attempts are counted against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttemptSubject {
+    pub scope: AttemptScope,
+    pub key: String,
+}
+
+impl AttemptSubject {
+    /// The subjects of an attempt to authenticate as `username` from `client_ip`.
+    pub fn for_login(username: &str, client_ip: Option<&str>) -> Vec<Self> {
+        let mut subjects = vec![Self {
+            scope: AttemptScope::Account,
+            key: username.to_lowercase(),
+        }];
+
+        if let Some(client_ip) = client_ip {
+            subjects.push(Self {
+                scope: AttemptScope::Ip,
+                key: client_ip.to_string(),
+            });
+        }
+
+        subjects
+    }
+
+    pub fn cache_key(&self) -> String {
+        format!("{}:{}", self.scope.as_str(), self.key)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginAttempts {
+    pub subject: AttemptSubject,
+    pub failed_attempts: i32,
+    pub window_started_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
@@ -1,8 +1,21 @@
+pub mod account_status;
 pub mod device_info;
+pub mod json_web_key;
+pub mod login_attempt;
+pub mod mfa_challenge;
+pub mod new_sign_in;
+pub mod permission;
+pub mod recovery_code;
 pub mod user;
 pub mod user_token;
 
+pub use account_status::AccountStatus;
 pub use device_info::DeviceInfo;
+pub use json_web_key::JsonWebKey;
+pub use login_attempt::{AttemptScope, AttemptSubject, LoginAttempts};
+pub use mfa_challenge::MfaChallenge;
+pub use new_sign_in::NewSignIn;
+pub use permission::Permission;
+pub use recovery_code::RecoveryCode;
 pub use user::User;
-pub use user_token::{Claims, UserToken};
-
+pub use user_token::{Claims, TokenType, UserToken};
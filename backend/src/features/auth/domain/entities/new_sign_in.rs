@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::DeviceInfo;
+
+/// A session started from a device the user never signed in from before.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewSignIn {
+    pub user_id: Uuid,
+    pub username: String,
+    pub device_info: DeviceInfo,
+    pub ip: Option<String>,
+    // Revokes the session when sent to `/api/auth/sign-ins/report`, without signing in
+    pub report_token: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// What a role allows. Stored by name in the `permissions` table, and carried by the access
+/// tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Permission {
+    #[serde(rename = "users:read")]
+    ReadUsers,
+    #[serde(rename = "users:write")]
+    WriteUsers,
+    #[serde(rename = "sessions:read")]
+    ReadSessions,
+    #[serde(rename = "sessions:write")]
+    WriteSessions,
+    #[serde(rename = "roles:write")]
+    WriteRoles,
+    #[serde(rename = "maintenance:run")]
+    RunMaintenance,
+    #[serde(rename = "security_events:read")]
+    ReadSecurityEvents,
+}
+
+impl Permission {
+    const ALL: [Permission; 7] = [
+        Permission::ReadUsers,
+        Permission::WriteUsers,
+        Permission::ReadSessions,
+        Permission::WriteSessions,
+        Permission::WriteRoles,
+        Permission::RunMaintenance,
+        Permission::ReadSecurityEvents,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ReadUsers => "users:read",
+            Permission::WriteUsers => "users:write",
+            Permission::ReadSessions => "sessions:read",
+            Permission::WriteSessions => "sessions:write",
+            Permission::WriteRoles => "roles:write",
+            Permission::RunMaintenance => "maintenance:run",
+            Permission::ReadSecurityEvents => "security_events:read",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == name)
+    }
+}
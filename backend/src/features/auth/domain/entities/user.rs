@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::features::auth::domain::entities::AccountStatus;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: Uuid,
@@ -9,12 +11,23 @@ pub struct User {
     pub password_hash: String,
     pub locale: String,
     pub theme: String,
-    pub is_admin: bool,
     pub otp_verified: bool,
     pub otp_base32: Option<String>,
     pub otp_auth_url: Option<String>,
-    pub recovery_codes: String,
+    // The TOTP time step of the last accepted code, a code is never accepted twice
+    pub otp_last_used_step: Option<i64>,
     pub password_is_expired: bool,
+    pub status: AccountStatus,
+    pub status_reason: Option<String>,
+    pub status_until: Option<DateTime<Utc>>,
+    // The account is deleted once this date is passed, unless the user logs in again
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+impl User {
+    pub fn is_disabled_at(&self, now: DateTime<Utc>) -> bool {
+        self.status.is_disabled_at(self.status_until, now)
+    }
+}
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::features::auth::domain::entities::Permission;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserToken {
     pub id: Uuid,
@@ -13,13 +15,35 @@ pub struct UserToken {
     pub browser: Option<String>,
     pub app_version: Option<String>,
     pub model: Option<String>,
+    pub family_id: Uuid,
+    pub parent_token_id: Option<Uuid>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    // When the session was started, kept when its tokens are rotated
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    Access,
+    Refresh,
+    MfaChallenge,
+    DataExport,
+    SignInReport,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub exp: i64,
+    pub iat: i64,
+    pub iss: String,
+    pub aud: String,
     pub jti: Uuid,
     pub user_id: Uuid,
-    pub is_admin: bool,
+    // Resolved from the roles of the user when the token is issued
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+    pub token_type: TokenType,
 }
-
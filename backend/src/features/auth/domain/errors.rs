@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::core::structs::transaction::TransactionError;
+use crate::features::events::domain::errors::EventsDomainError;
+
 #[derive(Error, Debug)]
 pub enum AuthDomainError {
     #[error("Invalid username or password")]
@@ -32,6 +35,12 @@ pub enum AuthDomainError {
     #[error("Invalid token")]
     InvalidToken,
 
+    #[error("Refresh token reuse detected")]
+    RefreshTokenReused,
+
+    #[error("Invalid or expired MFA challenge")]
+    InvalidMfaChallenge,
+
     #[error("Two-factor authentication not enabled")]
     OtpNotEnabled,
 
@@ -41,10 +50,36 @@ pub enum AuthDomainError {
     #[error("Password expired")]
     PasswordExpired,
 
+    #[error("Account disabled")]
+    AccountDisabled,
+
     #[error("Invalid password")]
     InvalidPassword,
 
+    #[error("Too many attempts, retry in {retry_after_seconds} seconds")]
+    TooManyAttempts { retry_after_seconds: i64 },
+
+    #[error("Invalid sign-in report link")]
+    InvalidSignInReportLink,
+
+    #[error("Failed to send notification")]
+    NotificationFailed,
+
     #[error("Database error")]
     DatabaseError,
 }
 
+impl From<TransactionError> for AuthDomainError {
+    fn from(e: TransactionError) -> Self {
+        tracing::error!("Transaction error: {}", e);
+        AuthDomainError::DatabaseError
+    }
+}
+
+// The outbox is written in the transaction of the change, so failing to publish undoes it
+impl From<EventsDomainError> for AuthDomainError {
+    fn from(e: EventsDomainError) -> Self {
+        tracing::error!("Outbox error: {}", e);
+        AuthDomainError::DatabaseError
+    }
+}
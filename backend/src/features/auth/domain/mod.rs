@@ -1,8 +1,10 @@
 pub mod entities;
 pub mod errors;
 pub mod repositories;
+pub mod services;
 
 pub use entities::*;
 pub use errors::AuthDomainError;
 pub use repositories::*;
+pub use services::*;
 
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::auth::domain::entities::{AttemptSubject, LoginAttempts};
+use crate::features::auth::domain::errors::AuthDomainError;
+
+#[async_trait::async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    async fn find_by_subject(
+        &self,
+        subject: &AttemptSubject,
+    ) -> Result<Option<LoginAttempts>, AuthDomainError>;
+    /// Counts a failed attempt, starting a new window at `now` if the current one began
+    /// before `window_started_after`.
+    async fn record_failure(
+        &self,
+        subject: &AttemptSubject,
+        now: DateTime<Utc>,
+        window_started_after: DateTime<Utc>,
+    ) -> Result<LoginAttempts, AuthDomainError>;
+    async fn lock(
+        &self,
+        subject: &AttemptSubject,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), AuthDomainError>;
+    async fn delete(&self, subject: &AttemptSubject) -> Result<(), AuthDomainError>;
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::MfaChallenge;
+use crate::features::auth::domain::errors::AuthDomainError;
+
+#[async_trait::async_trait]
+pub trait MfaChallengeRepository: Send + Sync {
+    async fn save(&self, challenge: &MfaChallenge) -> Result<(), AuthDomainError>;
+    /// Returns the challenge if it is neither consumed nor expired at `now`.
+    async fn find_active(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MfaChallenge>, AuthDomainError>;
+    /// Returns false if the challenge was already consumed or is expired at `now`.
+    async fn consume(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError>;
+}
@@ -1,6 +1,15 @@
+pub mod login_attempt_repository;
+pub mod mfa_challenge_repository;
+pub mod notifier;
+pub mod permission_repository;
+pub mod recovery_code_repository;
 pub mod token_repository;
 pub mod user_repository;
 
+pub use login_attempt_repository::LoginAttemptRepository;
+pub use mfa_challenge_repository::MfaChallengeRepository;
+pub use notifier::Notifier;
+pub use permission_repository::PermissionRepository;
+pub use recovery_code_repository::RecoveryCodeRepository;
 pub use token_repository::{TokenRepository, TokenService};
 pub use user_repository::UserRepository;
-
@@ -0,0 +1,8 @@
+use crate::features::auth::domain::entities::NewSignIn;
+use crate::features::auth::domain::errors::AuthDomainError;
+
+/// Tells the user about activity on their account, out of band.
+#[async_trait::async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify_new_sign_in(&self, sign_in: &NewSignIn) -> Result<(), AuthDomainError>;
+}
@@ -0,0 +1,10 @@
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::Permission;
+use crate::features::auth::domain::errors::AuthDomainError;
+
+#[async_trait::async_trait]
+pub trait PermissionRepository: Send + Sync {
+    /// The permissions granted by every role of the user.
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Permission>, AuthDomainError>;
+}
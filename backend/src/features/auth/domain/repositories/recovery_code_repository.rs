@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::RecoveryCode;
+use crate::features::auth::domain::errors::AuthDomainError;
+
+#[async_trait::async_trait]
+pub trait RecoveryCodeRepository: Send + Sync {
+    async fn find_unused_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RecoveryCode>, AuthDomainError>;
+    async fn count_unused_by_user_id(&self, user_id: Uuid) -> Result<i64, AuthDomainError>;
+    /// Returns false if the code was already used.
+    async fn mark_as_used(
+        &self,
+        code_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError>;
+    /// Atomically deletes every code of the user, used or not, and saves `codes` instead.
+    async fn replace_all_by_user_id(
+        &self,
+        user_id: Uuid,
+        codes: &[RecoveryCode],
+    ) -> Result<(), AuthDomainError>;
+}
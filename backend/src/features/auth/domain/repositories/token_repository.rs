@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::features::auth::domain::entities::{Claims, UserToken};
+use crate::features::auth::domain::entities::{
+    Claims, DeviceInfo, JsonWebKey, TokenType, UserToken,
+};
 use crate::features::auth::domain::errors::AuthDomainError;
 
 #[async_trait::async_trait]
@@ -13,14 +16,33 @@ pub trait TokenRepository: Send + Sync {
     ) -> Result<Option<UserToken>, AuthDomainError>;
     async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserToken>, AuthDomainError>;
     async fn delete_by_token_id(&self, token_id: Uuid) -> Result<(), AuthDomainError>;
-    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<(), AuthDomainError>;
+    /// Returns the ids of the revoked tokens.
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError>;
+    /// Returns false if the token was already rotated (or no longer exists).
+    async fn mark_as_rotated(
+        &self,
+        token_id: Uuid,
+        rotated_at: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError>;
+    /// Returns the ids of the revoked tokens.
+    async fn delete_all_by_family_id(&self, family_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError>;
+    /// Whether the user has a session on this device, or signed in from it before.
+    async fn is_known_device(
+        &self,
+        user_id: Uuid,
+        device_info: &DeviceInfo,
+    ) -> Result<bool, AuthDomainError>;
 }
 
 #[async_trait::async_trait]
 pub trait TokenService: Send + Sync {
     fn generate_access_token(&self, claims: &Claims) -> Result<String, AuthDomainError>;
     fn generate_refresh_token(&self, claims: &Claims) -> Result<String, AuthDomainError>;
-    fn decode_token(&self, token: &str) -> Result<Claims, AuthDomainError>;
+    fn generate_mfa_challenge_token(&self, claims: &Claims) -> Result<String, AuthDomainError>;
+    fn generate_data_export_token(&self, claims: &Claims) -> Result<String, AuthDomainError>;
+    fn generate_sign_in_report_token(&self, claims: &Claims) -> Result<String, AuthDomainError>;
+    fn decode_token(&self, token: &str, token_type: TokenType) -> Result<Claims, AuthDomainError>;
     fn hash_token(&self, token: &str) -> String;
+    /// Public keys accepted when verifying tokens, including the current signing key.
+    fn public_keys(&self) -> Vec<JsonWebKey>;
 }
-
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::features::auth::domain::entities::User;
@@ -8,6 +9,22 @@ pub trait UserRepository: Send + Sync {
     async fn create(&self, user: &User) -> Result<(), AuthDomainError>;
     async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AuthDomainError>;
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AuthDomainError>;
+    /// Persists every field but the OTP secret, the account status and the scheduled deletion,
+    /// which are only set by their own queries.
     async fn update(&self, user: &User) -> Result<(), AuthDomainError>;
+    async fn update_otp_secret(
+        &self,
+        user_id: Uuid,
+        otp_base32: Option<&str>,
+        otp_auth_url: Option<&str>,
+    ) -> Result<(), AuthDomainError>;
+    /// Atomically records `step` as the last accepted TOTP step. Returns false if that step
+    /// or a later one was already used.
+    async fn claim_otp_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthDomainError>;
+    /// Schedules the deletion of the account at `deletion_scheduled_at`, or cancels it.
+    async fn update_deletion_scheduled_at(
+        &self,
+        user_id: Uuid,
+        deletion_scheduled_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AuthDomainError>;
 }
-
@@ -0,0 +1,142 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::entities::{AttemptScope, AttemptSubject};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::LoginAttemptRepository;
+use crate::features::auth::structs::models::LockoutCache;
+
+#[derive(Debug, Clone)]
+pub struct LoginThrottleSettings {
+    pub account_max_attempts: i32,
+    pub ip_max_attempts: i32,
+    pub window: Duration,
+    pub lockout: Duration,
+}
+
+/// Counts failed authentication attempts per account and per client IP, and locks a subject
+/// out once it reaches its limit within the window.
+pub struct LoginThrottleService {
+    login_attempt_repository: Box<dyn LoginAttemptRepository>,
+    lockout_cache: LockoutCache,
+    settings: LoginThrottleSettings,
+}
+
+impl LoginThrottleService {
+    pub fn new(
+        login_attempt_repository: Box<dyn LoginAttemptRepository>,
+        lockout_cache: LockoutCache,
+        settings: LoginThrottleSettings,
+    ) -> Self {
+        Self {
+            login_attempt_repository,
+            lockout_cache,
+            settings,
+        }
+    }
+
+    /// Fails with `TooManyAttempts` if any of the subjects is currently locked out.
+    pub async fn ensure_allowed(&self, subjects: &[AttemptSubject]) -> Result<(), AuthDomainError> {
+        let now = now();
+
+        for subject in subjects {
+            let cache_key = subject.cache_key();
+
+            if let Some(locked_until) = self.lockout_cache.get_locked_until(&cache_key, now).await {
+                return Err(too_many_attempts(locked_until, now));
+            }
+
+            let locked_until = self
+                .login_attempt_repository
+                .find_by_subject(subject)
+                .await?
+                .and_then(|attempts| attempts.locked_until);
+
+            if let Some(locked_until) = locked_until.filter(|locked_until| *locked_until > now) {
+                self.lockout_cache.insert(cache_key, locked_until).await;
+                return Err(too_many_attempts(locked_until, now));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the outcome of an authentication attempt. Only the errors returned for wrong
+    /// credentials count as failures, a success clears the account counter.
+    pub async fn record_outcome<T>(
+        &self,
+        subjects: &[AttemptSubject],
+        result: &Result<T, AuthDomainError>,
+    ) -> Result<(), AuthDomainError> {
+        match result {
+            Ok(_) => self.record_success(subjects).await,
+            Err(e) if is_failed_attempt(e) => self.record_failure(subjects).await,
+            Err(_) => Ok(()),
+        }
+    }
+
+    async fn record_failure(&self, subjects: &[AttemptSubject]) -> Result<(), AuthDomainError> {
+        let now = now();
+
+        for subject in subjects {
+            let attempts = self
+                .login_attempt_repository
+                .record_failure(subject, now, now - self.settings.window)
+                .await?;
+
+            let max_attempts = match subject.scope {
+                AttemptScope::Account => self.settings.account_max_attempts,
+                AttemptScope::Ip => self.settings.ip_max_attempts,
+            };
+
+            if attempts.failed_attempts >= max_attempts {
+                let locked_until = now + self.settings.lockout;
+                self.login_attempt_repository
+                    .lock(subject, locked_until)
+                    .await?;
+                self.lockout_cache
+                    .insert(subject.cache_key(), locked_until)
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn record_success(&self, subjects: &[AttemptSubject]) -> Result<(), AuthDomainError> {
+        // The IP counter is kept, otherwise signing into one account would reset an attack
+        // spread over many others from the same address
+        for subject in subjects
+            .iter()
+            .filter(|subject| subject.scope == AttemptScope::Account)
+        {
+            self.login_attempt_repository.delete(subject).await?;
+            self.lockout_cache.remove(&subject.cache_key()).await;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_failed_attempt(error: &AuthDomainError) -> bool {
+    matches!(
+        error,
+        AuthDomainError::InvalidCredentials
+            | AuthDomainError::InvalidOtp
+            | AuthDomainError::InvalidRecoveryCode
+            | AuthDomainError::InvalidUsernameOrRecoveryCode
+            | AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode
+            | AuthDomainError::InvalidUsernameOrCodeOrRecoveryCode
+    )
+}
+
+fn too_many_attempts(locked_until: DateTime<Utc>, now: DateTime<Utc>) -> AuthDomainError {
+    // Round up so clients never retry a second too early
+    let remaining = locked_until - now;
+    let retry_after_seconds =
+        remaining.num_seconds() + i64::from(remaining > Duration::seconds(remaining.num_seconds()));
+
+    AuthDomainError::TooManyAttempts {
+        retry_after_seconds: retry_after_seconds.max(1),
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::entities::{Claims, MfaChallenge, TokenType};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::{MfaChallengeRepository, TokenService};
+
+#[derive(Debug, Clone)]
+pub struct MfaChallengeSettings {
+    pub lifetime: Duration,
+    pub issuer: String,
+    pub audience: String,
+}
+
+/// Issues the token proving that the password step of a login succeeded. The token is signed
+/// like the session tokens, and its jti refers to a server-side challenge so that it can only
+/// complete a single login.
+pub struct MfaChallengeService {
+    mfa_challenge_repository: Box<dyn MfaChallengeRepository>,
+    token_service: Box<dyn TokenService>,
+    settings: MfaChallengeSettings,
+}
+
+impl MfaChallengeService {
+    pub fn new(
+        mfa_challenge_repository: Box<dyn MfaChallengeRepository>,
+        token_service: Box<dyn TokenService>,
+        settings: MfaChallengeSettings,
+    ) -> Self {
+        Self {
+            mfa_challenge_repository,
+            token_service,
+            settings,
+        }
+    }
+
+    pub async fn issue(&self, user_id: Uuid) -> Result<String, AuthDomainError> {
+        let now_time = now();
+        let expires_at = now_time
+            .checked_add_signed(self.settings.lifetime)
+            .ok_or(AuthDomainError::InvalidToken)?;
+
+        let challenge = MfaChallenge {
+            id: Uuid::new_v4(),
+            user_id,
+            expires_at,
+            consumed_at: None,
+            created_at: now_time,
+        };
+        self.mfa_challenge_repository.save(&challenge).await?;
+
+        self.token_service.generate_mfa_challenge_token(&Claims {
+            exp: expires_at.timestamp(),
+            iat: now_time.timestamp(),
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
+            jti: challenge.id,
+            user_id,
+            // Only the session tokens grant permissions
+            permissions: Vec::new(),
+            token_type: TokenType::MfaChallenge,
+        })
+    }
+
+    /// Returns the challenge of a token if it can still be used, without consuming it.
+    pub async fn verify(&self, token: &str) -> Result<MfaChallenge, AuthDomainError> {
+        let claims = self
+            .token_service
+            .decode_token(token, TokenType::MfaChallenge)
+            .map_err(|_| AuthDomainError::InvalidMfaChallenge)?;
+
+        self.mfa_challenge_repository
+            .find_active(claims.jti, now())
+            .await?
+            .filter(|challenge| challenge.user_id == claims.user_id)
+            .ok_or(AuthDomainError::InvalidMfaChallenge)
+    }
+
+    /// Fails with `InvalidMfaChallenge` if the challenge was consumed in the meantime.
+    pub async fn consume(&self, challenge: &MfaChallenge) -> Result<(), AuthDomainError> {
+        if !self
+            .mfa_challenge_repository
+            .consume(challenge.id, now())
+            .await?
+        {
+            return Err(AuthDomainError::InvalidMfaChallenge);
+        }
+
+        Ok(())
+    }
+}
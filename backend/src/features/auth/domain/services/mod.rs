@@ -0,0 +1,13 @@
+pub mod login_throttle_service;
+pub mod mfa_challenge_service;
+pub mod new_device_service;
+pub mod otp_service;
+pub mod recovery_code_service;
+pub mod session_service;
+
+pub use login_throttle_service::{LoginThrottleService, LoginThrottleSettings};
+pub use mfa_challenge_service::{MfaChallengeService, MfaChallengeSettings};
+pub use new_device_service::{NewDeviceService, NewDeviceSettings};
+pub use otp_service::{OtpService, TotpSettings};
+pub use recovery_code_service::RecoveryCodeService;
+pub use session_service::{SessionService, SessionSettings, TokenPair};
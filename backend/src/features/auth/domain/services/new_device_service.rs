@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::audit::domain::entities::RequestContext;
+use crate::features::auth::domain::entities::{Claims, DeviceInfo, NewSignIn, TokenType, User};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::{Notifier, TokenRepository, TokenService};
+use crate::features::auth::domain::services::TokenPair;
+
+#[derive(Debug, Clone)]
+pub struct NewDeviceSettings {
+    // How long the link sent with the notification can revoke the session
+    pub report_link_lifetime: Duration,
+    pub issuer: String,
+    pub audience: String,
+}
+
+/// Notifies the user when a session is started from a device they never used before.
+pub struct NewDeviceService {
+    token_repository: Box<dyn TokenRepository>,
+    token_service: Box<dyn TokenService>,
+    notifier: Arc<dyn Notifier>,
+    settings: NewDeviceSettings,
+}
+
+impl NewDeviceService {
+    pub fn new(
+        token_repository: Box<dyn TokenRepository>,
+        token_service: Box<dyn TokenService>,
+        notifier: Arc<dyn Notifier>,
+        settings: NewDeviceSettings,
+    ) -> Self {
+        Self {
+            token_repository,
+            token_service,
+            notifier,
+            settings,
+        }
+    }
+
+    /// Must be called before the session is stored, which makes the device known.
+    pub async fn is_new_device(
+        &self,
+        user_id: Uuid,
+        device_info: &DeviceInfo,
+    ) -> Result<bool, AuthDomainError> {
+        Ok(!self
+            .token_repository
+            .is_known_device(user_id, device_info)
+            .await?)
+    }
+
+    /// The session is already started, so a failure is only logged.
+    pub async fn notify(
+        &self,
+        user: &User,
+        tokens: &TokenPair,
+        device_info: DeviceInfo,
+        context: &RequestContext,
+    ) {
+        if let Err(e) = self.try_notify(user, tokens, device_info, context).await {
+            tracing::error!("Failed to notify user {} of a new sign-in: {}", user.id, e);
+        }
+    }
+
+    async fn try_notify(
+        &self,
+        user: &User,
+        tokens: &TokenPair,
+        device_info: DeviceInfo,
+        context: &RequestContext,
+    ) -> Result<(), AuthDomainError> {
+        let now_time = now();
+        let expires_at = now_time
+            .checked_add_signed(self.settings.report_link_lifetime)
+            .ok_or(AuthDomainError::InvalidToken)?;
+
+        // The jti is the token family, so the link still revokes the session once its tokens
+        // have been rotated
+        let report_token = self.token_service.generate_sign_in_report_token(&Claims {
+            exp: expires_at.timestamp(),
+            iat: now_time.timestamp(),
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
+            jti: tokens.family_id,
+            user_id: user.id,
+            // Only the session tokens grant permissions
+            permissions: Vec::new(),
+            token_type: TokenType::SignInReport,
+        })?;
+
+        self.notifier
+            .notify_new_sign_in(&NewSignIn {
+                user_id: user.id,
+                username: user.username.clone(),
+                device_info,
+                ip: context.ip.clone(),
+                report_token,
+                created_at: now_time,
+            })
+            .await
+    }
+}
@@ -0,0 +1,93 @@
+use rand::Rng;
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::UserRepository;
+
+const OTP_ISSUER: &str = "Flutter Actix App";
+
+#[derive(Debug, Clone)]
+pub struct TotpSettings {
+    pub algorithm: Algorithm,
+    pub digits: usize,
+    pub skew: u8,
+    pub step_seconds: u64,
+}
+
+/// Generates TOTP secrets and checks codes against them. An accepted code is bound to its time
+/// step, which is persisted so the code can't be replayed within the skew window.
+pub struct OtpService {
+    user_repository: Box<dyn UserRepository>,
+    settings: TotpSettings,
+}
+
+impl OtpService {
+    pub fn new(user_repository: Box<dyn UserRepository>, settings: TotpSettings) -> Self {
+        Self {
+            user_repository,
+            settings,
+        }
+    }
+
+    /// Returns a new random secret in base32 and the `otpauth://` URL to share it with
+    /// authenticator apps.
+    pub fn generate_secret(&self, username: &str) -> Result<(String, String), AuthDomainError> {
+        let mut rng = rand::thread_rng();
+        let data_byte: [u8; 21] = rng.gen();
+        let otp_base32 = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, &data_byte);
+
+        // Fails early if the configured parameters can't be used with this secret
+        self.totp(&otp_base32)?;
+
+        let issuer = OTP_ISSUER;
+        let otp_auth_url = format!(
+            "otpauth://totp/{issuer}:{username}?secret={otp_base32}&issuer={issuer}&algorithm={}&digits={}&period={}",
+            self.settings.algorithm, self.settings.digits, self.settings.step_seconds
+        );
+
+        Ok((otp_base32, otp_auth_url))
+    }
+
+    /// Checks `code` against the steps within the skew of the current one. A matching code is
+    /// only accepted if its step is later than the last one accepted for this user.
+    pub async fn verify_code(
+        &self,
+        user_id: Uuid,
+        otp_base32: &str,
+        code: &str,
+    ) -> Result<bool, AuthDomainError> {
+        let totp = self.totp(otp_base32)?;
+
+        let step_seconds = self.settings.step_seconds as i64;
+        let current_step = now().timestamp() / step_seconds;
+        let skew = i64::from(self.settings.skew);
+
+        let matching_step = (current_step - skew..=current_step + skew)
+            .rev()
+            .filter(|step| *step >= 0)
+            .find(|step| totp.check(code, (step * step_seconds) as u64));
+
+        match matching_step {
+            Some(step) => self.user_repository.claim_otp_step(user_id, step).await,
+            None => Ok(false),
+        }
+    }
+
+    fn totp(&self, otp_base32: &str) -> Result<TOTP, AuthDomainError> {
+        let secret = Secret::Encoded(otp_base32.to_string())
+            .to_bytes()
+            .map_err(|_| AuthDomainError::InvalidOtp)?;
+
+        // The skew is applied by `verify_code`, which needs to know the step that matched
+        TOTP::new(
+            self.settings.algorithm,
+            self.settings.digits,
+            0,
+            self.settings.step_seconds,
+            secret,
+        )
+        .map_err(|_| AuthDomainError::InvalidOtp)
+    }
+}
@@ -0,0 +1,98 @@
+use argon2::password_hash::SaltString;
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::entities::RecoveryCode;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::RecoveryCodeRepository;
+
+const RECOVERY_CODE_COUNT: usize = 5;
+const RECOVERY_CODE_LENGTH: usize = 16;
+
+/// Generates the recovery codes of a user and checks codes against them. Codes are hashed like
+/// passwords, and each one can only be used once.
+pub struct RecoveryCodeService {
+    recovery_code_repository: Box<dyn RecoveryCodeRepository>,
+}
+
+impl RecoveryCodeService {
+    pub fn new(recovery_code_repository: Box<dyn RecoveryCodeRepository>) -> Self {
+        Self {
+            recovery_code_repository,
+        }
+    }
+
+    /// Replaces every code of the user with a new set, returned in clear. The previous codes,
+    /// used or not, can't be used anymore.
+    pub async fn regenerate(&self, user_id: Uuid) -> Result<Vec<String>, AuthDomainError> {
+        let argon2 = Argon2::default();
+        let now_time = now();
+
+        let mut clear_codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+        let mut codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+
+        for _ in 0..RECOVERY_CODE_COUNT {
+            let code: String = rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(RECOVERY_CODE_LENGTH)
+                .map(char::from)
+                .collect();
+
+            let salt = SaltString::generate(&mut OsRng);
+            let code_hash = argon2
+                .hash_password(code.as_bytes(), &salt)
+                .map_err(|_| AuthDomainError::InvalidPassword)?
+                .to_string();
+
+            clear_codes.push(code);
+            codes.push(RecoveryCode {
+                id: Uuid::new_v4(),
+                user_id,
+                code_hash,
+                used_at: None,
+                created_at: now_time,
+            });
+        }
+
+        self.recovery_code_repository
+            .replace_all_by_user_id(user_id, &codes)
+            .await?;
+
+        Ok(clear_codes)
+    }
+
+    /// Marks the unused code matching `code` as used. Returns false if there is none.
+    pub async fn consume(&self, user_id: Uuid, code: &str) -> Result<bool, AuthDomainError> {
+        let argon2 = Argon2::default();
+
+        let matching_code = self
+            .recovery_code_repository
+            .find_unused_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .find(|recovery_code| {
+                PasswordHash::new(&recovery_code.code_hash)
+                    .map(|hash| argon2.verify_password(code.as_bytes(), &hash).is_ok())
+                    .unwrap_or(false)
+            });
+
+        match matching_code {
+            Some(recovery_code) => {
+                self.recovery_code_repository
+                    .mark_as_used(recovery_code.id, now())
+                    .await
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub async fn count_remaining(&self, user_id: Uuid) -> Result<i64, AuthDomainError> {
+        self.recovery_code_repository
+            .count_unused_by_user_id(user_id)
+            .await
+    }
+}
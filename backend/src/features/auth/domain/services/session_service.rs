@@ -0,0 +1,152 @@
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::entities::{Claims, DeviceInfo, TokenType, UserToken};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::{
+    PermissionRepository, TokenRepository, TokenService,
+};
+
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    pub token_id: Uuid,
+    // Shared by every token the session will be rotated to
+    pub family_id: Uuid,
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionSettings {
+    pub access_token_lifetime: Duration,
+    pub refresh_token_lifetime: Duration,
+    pub issuer: String,
+    pub audience: String,
+}
+
+pub struct SessionService {
+    token_repository: Box<dyn TokenRepository>,
+    token_service: Box<dyn TokenService>,
+    permission_repository: Box<dyn PermissionRepository>,
+    settings: SessionSettings,
+}
+
+impl SessionService {
+    pub fn new(
+        token_repository: Box<dyn TokenRepository>,
+        token_service: Box<dyn TokenService>,
+        permission_repository: Box<dyn PermissionRepository>,
+        settings: SessionSettings,
+    ) -> Self {
+        Self {
+            token_repository,
+            token_service,
+            permission_repository,
+            settings,
+        }
+    }
+
+    /// Mints an access/refresh token pair sharing a new jti and stores the
+    /// matching `user_tokens` row for the given device, starting a new token family.
+    pub async fn issue_tokens(
+        &self,
+        user_id: Uuid,
+        device_info: DeviceInfo,
+    ) -> Result<TokenPair, AuthDomainError> {
+        self.issue_tokens_in_family(user_id, device_info, None)
+            .await
+    }
+
+    /// Marks `parent` as rotated and issues its successor in the same token family.
+    /// Fails with `RefreshTokenReused` if `parent` has already been rotated.
+    pub async fn rotate_tokens(
+        &self,
+        parent: &UserToken,
+        device_info: DeviceInfo,
+    ) -> Result<TokenPair, AuthDomainError> {
+        if !self
+            .token_repository
+            .mark_as_rotated(parent.token_id, now())
+            .await?
+        {
+            return Err(AuthDomainError::RefreshTokenReused);
+        }
+
+        self.issue_tokens_in_family(parent.user_id, device_info, Some(parent))
+            .await
+    }
+
+    async fn issue_tokens_in_family(
+        &self,
+        user_id: Uuid,
+        device_info: DeviceInfo,
+        parent: Option<&UserToken>,
+    ) -> Result<TokenPair, AuthDomainError> {
+        // Resolved on every issuance, so a refresh picks up the roles granted since the login
+        let permissions = self
+            .permission_repository
+            .find_all_by_user_id(user_id)
+            .await?;
+        let jti = Uuid::new_v4();
+        let family_id = parent.map_or_else(Uuid::new_v4, |parent| parent.family_id);
+        let now_time = now();
+
+        let access_token_expires_at = now_time
+            .checked_add_signed(self.settings.access_token_lifetime)
+            .ok_or(AuthDomainError::InvalidToken)?;
+        let refresh_token_expires_at = now_time
+            .checked_add_signed(self.settings.refresh_token_lifetime)
+            .ok_or(AuthDomainError::InvalidToken)?;
+
+        let access_claims = Claims {
+            exp: access_token_expires_at.timestamp(),
+            iat: now_time.timestamp(),
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
+            jti,
+            user_id,
+            permissions: permissions.clone(),
+            token_type: TokenType::Access,
+        };
+        let access_token = self.token_service.generate_access_token(&access_claims)?;
+
+        let refresh_claims = Claims {
+            exp: refresh_token_expires_at.timestamp(),
+            iat: now_time.timestamp(),
+            iss: self.settings.issuer.clone(),
+            aud: self.settings.audience.clone(),
+            jti,
+            user_id,
+            permissions,
+            token_type: TokenType::Refresh,
+        };
+        let refresh_token = self.token_service.generate_refresh_token(&refresh_claims)?;
+
+        let user_token = UserToken {
+            id: Uuid::new_v4(),
+            user_id,
+            token_id: jti,
+            expires_at: refresh_token_expires_at,
+            os: device_info.os,
+            is_mobile: device_info.is_mobile,
+            browser: device_info.browser,
+            app_version: device_info.app_version,
+            model: device_info.model,
+            family_id,
+            parent_token_id: parent.map(|parent| parent.token_id),
+            rotated_at: None,
+            created_at: parent.map_or(now_time, |parent| parent.created_at),
+            last_activity_at: Some(now_time),
+            last_ip: parent.and_then(|parent| parent.last_ip.clone()),
+        };
+        self.token_repository.save(&user_token).await?;
+
+        Ok(TokenPair {
+            token_id: jti,
+            family_id,
+            access_token,
+            refresh_token,
+        })
+    }
+}
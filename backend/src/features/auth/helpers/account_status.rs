@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Error, Executor, Postgres};
+use uuid::Uuid;
+
+use crate::features::auth::domain::entities::AccountStatus;
+
+/// Whether the user can't use the account at `now`. A user that no longer exists counts as
+/// disabled.
+pub async fn is_account_disabled<'a, E>(
+    executor: E,
+    user_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<bool, Error>
+where
+    E: Executor<'a, Database = Postgres>,
+{
+    let row = sqlx::query!(
+        r#"
+        SELECT status, status_until
+        FROM users
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.is_none_or(|row| {
+        AccountStatus::from_name(&row.status).is_disabled_at(row.status_until, now)
+    }))
+}
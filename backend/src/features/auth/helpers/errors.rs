@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::features::auth::domain::errors::AuthDomainError;
+
 #[derive(Error, Debug)]
 pub enum AuthError {
     #[error("Authorization header is missing")]
@@ -9,5 +11,5 @@ pub enum AuthError {
     InvalidAuthHeader,
 
     #[error("Token decoding error: {0}")]
-    TokenDecodingError(#[from] jsonwebtoken::errors::Error),
+    TokenDecodingError(#[from] AuthDomainError),
 }
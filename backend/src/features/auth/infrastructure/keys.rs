@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::fs;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use simple_asn1::ASN1Block;
+
+use crate::configuration::{JwtKeySettings, JwtSettings};
+use crate::features::auth::domain::entities::JsonWebKey;
+
+#[derive(thiserror::Error, Debug)]
+pub enum JwtKeyError {
+    #[error("Failed to read key file {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("Invalid key {0}: {1}")]
+    InvalidKey(String, String),
+    #[error("Unsupported algorithm {1:?} for key {0}")]
+    UnsupportedAlgorithm(String, Algorithm),
+    #[error("Signing key {0} is not configured or has no private key")]
+    MissingSigningKey(String),
+    #[error("Key id {0} is configured more than once")]
+    DuplicateKeyId(String),
+}
+
+#[derive(Clone)]
+pub struct VerificationKey {
+    pub algorithm: Algorithm,
+    pub decoding_key: DecodingKey,
+    pub jwk: JsonWebKey,
+}
+
+#[derive(Clone)]
+pub struct SigningKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    pub encoding_key: EncodingKey,
+}
+
+/// The key used to sign new tokens and every key still accepted for verification, by `kid`.
+#[derive(Clone)]
+pub struct JwtKeySet {
+    signing_key: SigningKey,
+    verification_keys: HashMap<String, VerificationKey>,
+}
+
+impl JwtKeySet {
+    pub fn load(settings: &JwtSettings) -> Result<Self, JwtKeyError> {
+        let mut verification_keys = HashMap::new();
+        let mut signing_key = None;
+
+        for key in &settings.keys {
+            if verification_keys.contains_key(&key.id) {
+                return Err(JwtKeyError::DuplicateKeyId(key.id.clone()));
+            }
+
+            let public_pem = read_key_file(&key.public_key_path)?;
+            verification_keys.insert(key.id.clone(), load_verification_key(key, &public_pem)?);
+
+            if key.id == settings.signing_key_id {
+                if let Some(private_key_path) = &key.private_key_path {
+                    let private_pem = read_key_file(private_key_path)?;
+                    signing_key = Some(load_signing_key(key, &private_pem)?);
+                }
+            }
+        }
+
+        let signing_key = signing_key
+            .ok_or_else(|| JwtKeyError::MissingSigningKey(settings.signing_key_id.clone()))?;
+
+        Ok(Self {
+            signing_key,
+            verification_keys,
+        })
+    }
+
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+
+    pub fn verification_key(&self, kid: &str) -> Option<&VerificationKey> {
+        self.verification_keys.get(kid)
+    }
+
+    pub fn public_keys(&self) -> Vec<JsonWebKey> {
+        let mut keys: Vec<JsonWebKey> = self
+            .verification_keys
+            .values()
+            .map(|key| key.jwk.clone())
+            .collect();
+        keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+        keys
+    }
+}
+
+fn read_key_file(path: &str) -> Result<Vec<u8>, JwtKeyError> {
+    fs::read(path).map_err(|e| JwtKeyError::Io(path.to_string(), e))
+}
+
+fn invalid_key(key: &JwtKeySettings, e: impl ToString) -> JwtKeyError {
+    JwtKeyError::InvalidKey(key.id.clone(), e.to_string())
+}
+
+fn load_signing_key(key: &JwtKeySettings, pem: &[u8]) -> Result<SigningKey, JwtKeyError> {
+    let encoding_key = match key.algorithm {
+        Algorithm::EdDSA => EncodingKey::from_ed_pem(pem),
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => EncodingKey::from_rsa_pem(pem),
+        other => return Err(JwtKeyError::UnsupportedAlgorithm(key.id.clone(), other)),
+    }
+    .map_err(|e| invalid_key(key, e))?;
+
+    Ok(SigningKey {
+        kid: key.id.clone(),
+        algorithm: key.algorithm,
+        encoding_key,
+    })
+}
+
+fn load_verification_key(key: &JwtKeySettings, pem: &[u8]) -> Result<VerificationKey, JwtKeyError> {
+    let decoding_key = match key.algorithm {
+        Algorithm::EdDSA => DecodingKey::from_ed_pem(pem),
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => DecodingKey::from_rsa_pem(pem),
+        other => return Err(JwtKeyError::UnsupportedAlgorithm(key.id.clone(), other)),
+    }
+    .map_err(|e| invalid_key(key, e))?;
+
+    Ok(VerificationKey {
+        algorithm: key.algorithm,
+        decoding_key,
+        jwk: to_json_web_key(key, pem)?,
+    })
+}
+
+fn to_json_web_key(key: &JwtKeySettings, pem: &[u8]) -> Result<JsonWebKey, JwtKeyError> {
+    let public_key = subject_public_key(pem).map_err(|e| invalid_key(key, e))?;
+
+    let mut jwk = JsonWebKey {
+        kty: String::new(),
+        kid: key.id.clone(),
+        alg: format!("{:?}", key.algorithm),
+        key_use: "sig".to_string(),
+        crv: None,
+        x: None,
+        n: None,
+        e: None,
+    };
+
+    if key.algorithm == Algorithm::EdDSA {
+        // The subject public key of an Ed25519 SPKI is the raw 32-byte key
+        jwk.kty = "OKP".to_string();
+        jwk.crv = Some("Ed25519".to_string());
+        jwk.x = Some(URL_SAFE_NO_PAD.encode(public_key));
+    } else {
+        // The subject public key of an RSA SPKI is a DER sequence of the modulus and exponent
+        let (n, e) = rsa_components(&public_key).map_err(|e| invalid_key(key, e))?;
+        jwk.kty = "RSA".to_string();
+        jwk.n = Some(URL_SAFE_NO_PAD.encode(n));
+        jwk.e = Some(URL_SAFE_NO_PAD.encode(e));
+    }
+
+    Ok(jwk)
+}
+
+fn subject_public_key(pem: &[u8]) -> Result<Vec<u8>, String> {
+    let pem = pem::parse(pem).map_err(|e| e.to_string())?;
+    let blocks = simple_asn1::from_der(pem.contents()).map_err(|e| e.to_string())?;
+
+    match blocks.first() {
+        Some(ASN1Block::Sequence(_, fields)) => match fields.get(1) {
+            Some(ASN1Block::BitString(_, _, bytes)) => Ok(bytes.clone()),
+            _ => Err("missing subject public key".to_string()),
+        },
+        _ => Err("expected a SubjectPublicKeyInfo structure".to_string()),
+    }
+}
+
+fn rsa_components(public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let blocks = simple_asn1::from_der(public_key).map_err(|e| e.to_string())?;
+
+    match blocks.first() {
+        Some(ASN1Block::Sequence(_, fields)) => match (fields.first(), fields.get(1)) {
+            (Some(ASN1Block::Integer(_, n)), Some(ASN1Block::Integer(_, e))) => {
+                Ok((n.to_bytes_be().1, e.to_bytes_be().1))
+            }
+            _ => Err("expected an RSA modulus and exponent".to_string()),
+        },
+        _ => Err("expected an RSA public key".to_string()),
+    }
+}
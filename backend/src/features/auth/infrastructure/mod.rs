@@ -1,4 +1,6 @@
+pub mod keys;
 pub mod models;
+pub mod otp_secrets;
 pub mod repositories;
 
 pub use models::*;
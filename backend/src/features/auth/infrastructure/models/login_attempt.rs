@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct LoginAttemptModel {
+    pub scope: String,
+    pub key: String,
+    pub failed_attempts: i32,
+    pub window_started_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl From<LoginAttemptModel> for crate::features::auth::domain::entities::LoginAttempts {
+    fn from(model: LoginAttemptModel) -> Self {
+        let scope = match model.scope.as_str() {
+            "ip" => crate::features::auth::domain::entities::AttemptScope::Ip,
+            _ => crate::features::auth::domain::entities::AttemptScope::Account,
+        };
+
+        Self {
+            subject: crate::features::auth::domain::entities::AttemptSubject {
+                scope,
+                key: model.key,
+            },
+            failed_attempts: model.failed_attempts,
+            window_started_at: model.window_started_at,
+            locked_until: model.locked_until,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct MfaChallengeModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<MfaChallengeModel> for crate::features::auth::domain::entities::MfaChallenge {
+    fn from(model: MfaChallengeModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            expires_at: model.expires_at,
+            consumed_at: model.consumed_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<crate::features::auth::domain::entities::MfaChallenge> for MfaChallengeModel {
+    fn from(entity: crate::features::auth::domain::entities::MfaChallenge) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            expires_at: entity.expires_at,
+            consumed_at: entity.consumed_at,
+            created_at: entity.created_at,
+        }
+    }
+}
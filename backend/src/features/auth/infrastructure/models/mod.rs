@@ -1,6 +1,11 @@
+pub mod login_attempt;
+pub mod mfa_challenge;
+pub mod recovery_code;
 pub mod user;
 pub mod user_token;
 
+pub use login_attempt::LoginAttemptModel;
+pub use mfa_challenge::MfaChallengeModel;
+pub use recovery_code::RecoveryCodeModel;
 pub use user::UserModel;
 pub use user_token::UserTokenModel;
-
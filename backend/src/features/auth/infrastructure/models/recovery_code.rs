@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct RecoveryCodeModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RecoveryCodeModel> for crate::features::auth::domain::entities::RecoveryCode {
+    fn from(model: RecoveryCodeModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            code_hash: model.code_hash,
+            used_at: model.used_at,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<crate::features::auth::domain::entities::RecoveryCode> for RecoveryCodeModel {
+    fn from(entity: crate::features::auth::domain::entities::RecoveryCode) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            code_hash: entity.code_hash,
+            used_at: entity.used_at,
+            created_at: entity.created_at,
+        }
+    }
+}
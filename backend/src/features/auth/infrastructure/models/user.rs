@@ -11,12 +11,15 @@ pub struct UserModel {
     pub password: String,
     pub locale: String,
     pub theme: String,
-    pub is_admin: bool,
     pub otp_verified: bool,
     pub otp_base32: Option<String>,
     pub otp_auth_url: Option<String>,
-    pub recovery_codes: String,
+    pub otp_last_used_step: Option<i64>,
     pub password_is_expired: bool,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_until: Option<DateTime<Utc>>,
+    pub deletion_scheduled_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -29,12 +32,17 @@ impl From<UserModel> for crate::features::auth::domain::entities::User {
             password_hash: model.password,
             locale: model.locale,
             theme: model.theme,
-            is_admin: model.is_admin,
             otp_verified: model.otp_verified,
             otp_base32: model.otp_base32,
             otp_auth_url: model.otp_auth_url,
-            recovery_codes: model.recovery_codes,
+            otp_last_used_step: model.otp_last_used_step,
             password_is_expired: model.password_is_expired,
+            status: crate::features::auth::domain::entities::AccountStatus::from_name(
+                &model.status,
+            ),
+            status_reason: model.status_reason,
+            status_until: model.status_until,
+            deletion_scheduled_at: model.deletion_scheduled_at,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
@@ -49,15 +57,17 @@ impl From<crate::features::auth::domain::entities::User> for UserModel {
             password: entity.password_hash,
             locale: entity.locale,
             theme: entity.theme,
-            is_admin: entity.is_admin,
             otp_verified: entity.otp_verified,
             otp_base32: entity.otp_base32,
             otp_auth_url: entity.otp_auth_url,
-            recovery_codes: entity.recovery_codes,
+            otp_last_used_step: entity.otp_last_used_step,
             password_is_expired: entity.password_is_expired,
+            status: entity.status.as_str().to_string(),
+            status_reason: entity.status_reason,
+            status_until: entity.status_until,
+            deletion_scheduled_at: entity.deletion_scheduled_at,
             created_at: entity.created_at,
             updated_at: entity.updated_at,
         }
     }
 }
-
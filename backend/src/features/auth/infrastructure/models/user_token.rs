@@ -15,6 +15,12 @@ pub struct UserTokenModel {
     pub browser: Option<String>,
     pub app_version: Option<String>,
     pub model: Option<String>,
+    pub family_id: Uuid,
+    pub parent_token_id: Option<Uuid>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
 }
 
 impl From<UserTokenModel> for crate::features::auth::domain::entities::UserToken {
@@ -29,6 +35,12 @@ impl From<UserTokenModel> for crate::features::auth::domain::entities::UserToken
             browser: model.browser,
             app_version: model.app_version,
             model: model.model,
+            family_id: model.family_id,
+            parent_token_id: model.parent_token_id,
+            rotated_at: model.rotated_at,
+            created_at: model.created_at,
+            last_activity_at: model.last_activity_at,
+            last_ip: model.last_ip,
         }
     }
 }
@@ -45,7 +57,12 @@ impl From<crate::features::auth::domain::entities::UserToken> for UserTokenModel
             browser: entity.browser,
             app_version: entity.app_version,
             model: entity.model,
+            family_id: entity.family_id,
+            parent_token_id: entity.parent_token_id,
+            rotated_at: entity.rotated_at,
+            created_at: entity.created_at,
+            last_activity_at: entity.last_activity_at,
+            last_ip: entity.last_ip,
         }
     }
 }
-
@@ -0,0 +1,99 @@
+use std::path::PathBuf;
+
+use tokio::io::AsyncWriteExt;
+
+use crate::features::auth::domain::entities::NewSignIn;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::Notifier;
+
+/// Only writes the notifications to the logs, for local development.
+#[derive(Clone, Default)]
+pub struct LogNotifier;
+
+#[async_trait::async_trait]
+impl Notifier for LogNotifier {
+    async fn notify_new_sign_in(&self, sign_in: &NewSignIn) -> Result<(), AuthDomainError> {
+        tracing::info!(
+            "New sign-in for user {} from {:?} ({:?}, {:?}, {:?}), report token: {}",
+            sign_in.user_id,
+            sign_in.ip,
+            sign_in.device_info.os,
+            sign_in.device_info.model,
+            sign_in.device_info.browser,
+            sign_in.report_token,
+        );
+
+        Ok(())
+    }
+}
+
+/// Appends the notifications to a file, one JSON object per line, for a mailer or a test to
+/// pick them up.
+#[derive(Clone)]
+pub struct FileNotifier {
+    path: PathBuf,
+}
+
+impl FileNotifier {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for FileNotifier {
+    async fn notify_new_sign_in(&self, sign_in: &NewSignIn) -> Result<(), AuthDomainError> {
+        let mut line = serde_json::to_string(sign_in).map_err(|e| {
+            tracing::error!("Serialization error: {}", e);
+            AuthDomainError::NotificationFailed
+        })?;
+        line.push('\n');
+
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| {
+                tracing::error!("Outbox error: {}", e);
+                AuthDomainError::NotificationFailed
+            })?;
+        }
+        // Appending a single write keeps the lines whole when several workers notify at once
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| {
+                tracing::error!("Outbox error: {}", e);
+                AuthDomainError::NotificationFailed
+            })?;
+        file.write_all(line.as_bytes()).await.map_err(|e| {
+            tracing::error!("Outbox error: {}", e);
+            AuthDomainError::NotificationFailed
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Keeps the notifications, for the tests to read them.
+#[cfg(feature = "test-util")]
+#[derive(Clone, Default)]
+pub struct InMemoryNotifier {
+    sent: std::sync::Arc<std::sync::Mutex<Vec<NewSignIn>>>,
+}
+
+#[cfg(feature = "test-util")]
+impl InMemoryNotifier {
+    pub fn sent(&self) -> Vec<NewSignIn> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[async_trait::async_trait]
+impl Notifier for InMemoryNotifier {
+    async fn notify_new_sign_in(&self, sign_in: &NewSignIn) -> Result<(), AuthDomainError> {
+        self.sent.lock().unwrap().push(sign_in.clone());
+
+        Ok(())
+    }
+}
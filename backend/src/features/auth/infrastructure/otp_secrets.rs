@@ -0,0 +1,111 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::core::structs::envelope_cipher::{EnvelopeCipher, EnvelopeCipherError};
+
+pub const OTP_BASE32_COLUMN: &str = "otp_base32";
+pub const OTP_AUTH_URL_COLUMN: &str = "otp_auth_url";
+
+#[derive(thiserror::Error, Debug)]
+pub enum OtpSecretMigrationError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Failed to encrypt the OTP secret of user {0}: {1}")]
+    Encryption(Uuid, EnvelopeCipherError),
+}
+
+// Binds an encrypted value to its user and column, so it can't be moved to another row
+fn associated_data(user_id: Uuid, column: &str) -> Vec<u8> {
+    format!("users.{column}:{user_id}").into_bytes()
+}
+
+pub fn encrypt_otp_column(
+    cipher: &EnvelopeCipher,
+    user_id: Uuid,
+    column: &str,
+    value: Option<&str>,
+) -> Result<Option<String>, EnvelopeCipherError> {
+    value
+        .map(|value| cipher.encrypt(value, &associated_data(user_id, column)))
+        .transpose()
+}
+
+pub fn decrypt_otp_column(
+    cipher: &EnvelopeCipher,
+    user_id: Uuid,
+    column: &str,
+    value: Option<String>,
+) -> Result<Option<String>, EnvelopeCipherError> {
+    value
+        .map(|value| cipher.decrypt(&value, &associated_data(user_id, column)))
+        .transpose()
+}
+
+/// Encrypts the OTP secrets still stored in plaintext, and moves the ones encrypted with a
+/// previous key to the current key. Run with the migrations, returns the number of users updated.
+pub async fn encrypt_otp_secrets(
+    pool: &PgPool,
+    cipher: &EnvelopeCipher,
+) -> Result<u64, OtpSecretMigrationError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, otp_base32, otp_auth_url
+        FROM users
+        WHERE otp_base32 IS NOT NULL OR otp_auth_url IS NOT NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut updated = 0;
+
+    for row in rows {
+        let otp_base32 = migrate_value(cipher, row.id, OTP_BASE32_COLUMN, &row.otp_base32)
+            .map_err(|e| OtpSecretMigrationError::Encryption(row.id, e))?;
+        let otp_auth_url = migrate_value(cipher, row.id, OTP_AUTH_URL_COLUMN, &row.otp_auth_url)
+            .map_err(|e| OtpSecretMigrationError::Encryption(row.id, e))?;
+
+        if otp_base32 == row.otp_base32 && otp_auth_url == row.otp_auth_url {
+            continue;
+        }
+
+        // Skipped if the secret was changed in the meantime
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET otp_base32 = $2, otp_auth_url = $3
+            WHERE id = $1
+                AND otp_base32 IS NOT DISTINCT FROM $4
+                AND otp_auth_url IS NOT DISTINCT FROM $5
+            "#,
+            row.id,
+            otp_base32,
+            otp_auth_url,
+            row.otp_base32,
+            row.otp_auth_url,
+        )
+        .execute(pool)
+        .await?;
+
+        updated += result.rows_affected();
+    }
+
+    Ok(updated)
+}
+
+fn migrate_value(
+    cipher: &EnvelopeCipher,
+    user_id: Uuid,
+    column: &str,
+    value: &Option<String>,
+) -> Result<Option<String>, EnvelopeCipherError> {
+    match value {
+        Some(value) if !EnvelopeCipher::is_encrypted(value) => {
+            encrypt_otp_column(cipher, user_id, column, Some(value))
+        }
+        Some(value) if !cipher.is_encrypted_with_current_key(value) => {
+            cipher.rewrap(value).map(Some)
+        }
+        _ => Ok(value.clone()),
+    }
+}
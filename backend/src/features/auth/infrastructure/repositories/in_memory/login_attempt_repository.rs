@@ -0,0 +1,107 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::{AttemptSubject, LoginAttempts};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::LoginAttemptRepository;
+
+#[derive(Clone)]
+pub struct InMemoryLoginAttemptRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryLoginAttemptRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AuthDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginAttemptRepository for InMemoryLoginAttemptRepository {
+    async fn find_by_subject(
+        &self,
+        subject: &AttemptSubject,
+    ) -> Result<Option<LoginAttempts>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .login_attempts
+            .iter()
+            .find(|attempts| &attempts.subject == subject)
+            .cloned())
+    }
+
+    async fn record_failure(
+        &self,
+        subject: &AttemptSubject,
+        now: DateTime<Utc>,
+        window_started_after: DateTime<Utc>,
+    ) -> Result<LoginAttempts, AuthDomainError> {
+        let mut tables = self.write("login_attempts")?;
+
+        match tables
+            .login_attempts
+            .iter_mut()
+            .find(|attempts| &attempts.subject == subject)
+        {
+            Some(attempts) => {
+                if attempts.window_started_at < window_started_after {
+                    attempts.failed_attempts = 1;
+                    attempts.window_started_at = now;
+                } else {
+                    attempts.failed_attempts += 1;
+                }
+
+                Ok(attempts.clone())
+            }
+            None => {
+                let attempts = LoginAttempts {
+                    subject: subject.clone(),
+                    failed_attempts: 1,
+                    window_started_at: now,
+                    locked_until: None,
+                };
+                tables.login_attempts.push(attempts.clone());
+
+                Ok(attempts)
+            }
+        }
+    }
+
+    async fn lock(
+        &self,
+        subject: &AttemptSubject,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("login_attempts")?;
+
+        if let Some(attempts) = tables
+            .login_attempts
+            .iter_mut()
+            .find(|attempts| &attempts.subject == subject)
+        {
+            attempts.locked_until = Some(locked_until);
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, subject: &AttemptSubject) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("login_attempts")?;
+
+        tables
+            .login_attempts
+            .retain(|attempts| &attempts.subject != subject);
+
+        Ok(())
+    }
+}
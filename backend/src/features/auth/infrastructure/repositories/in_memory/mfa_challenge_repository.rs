@@ -0,0 +1,76 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::MfaChallenge;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::MfaChallengeRepository;
+
+#[derive(Clone)]
+pub struct InMemoryMfaChallengeRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryMfaChallengeRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AuthDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+fn is_active(challenge: &MfaChallenge, challenge_id: Uuid, now: DateTime<Utc>) -> bool {
+    challenge.id == challenge_id && challenge.consumed_at.is_none() && challenge.expires_at > now
+}
+
+#[async_trait::async_trait]
+impl MfaChallengeRepository for InMemoryMfaChallengeRepository {
+    async fn save(&self, challenge: &MfaChallenge) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("mfa_challenges")?;
+
+        tables.mfa_challenges.push(challenge.clone());
+
+        Ok(())
+    }
+
+    async fn find_active(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MfaChallenge>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .mfa_challenges
+            .iter()
+            .find(|challenge| is_active(challenge, challenge_id, now))
+            .cloned())
+    }
+
+    async fn consume(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        let mut tables = self.write("mfa_challenges")?;
+
+        match tables
+            .mfa_challenges
+            .iter_mut()
+            .find(|challenge| is_active(challenge, challenge_id, now))
+        {
+            Some(challenge) => {
+                challenge.consumed_at = Some(now);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
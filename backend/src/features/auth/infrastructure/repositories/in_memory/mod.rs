@@ -0,0 +1,13 @@
+pub mod login_attempt_repository;
+pub mod mfa_challenge_repository;
+pub mod permission_repository;
+pub mod recovery_code_repository;
+pub mod token_repository;
+pub mod user_repository;
+
+pub use login_attempt_repository::InMemoryLoginAttemptRepository;
+pub use mfa_challenge_repository::InMemoryMfaChallengeRepository;
+pub use permission_repository::InMemoryPermissionRepository;
+pub use recovery_code_repository::InMemoryRecoveryCodeRepository;
+pub use token_repository::InMemoryTokenRepository;
+pub use user_repository::InMemoryUserRepository;
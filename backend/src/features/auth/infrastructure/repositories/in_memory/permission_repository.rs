@@ -0,0 +1,41 @@
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::InMemoryDatabase;
+use crate::features::auth::domain::entities::Permission;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::PermissionRepository;
+
+#[derive(Clone)]
+pub struct InMemoryPermissionRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryPermissionRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait::async_trait]
+impl PermissionRepository for InMemoryPermissionRepository {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Permission>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        let mut permissions: Vec<Permission> = tables
+            .user_roles
+            .iter()
+            .filter(|(id, _)| *id == user_id)
+            .flat_map(|(_, role)| {
+                tables
+                    .role_permissions
+                    .iter()
+                    .filter(move |(granted_by, _)| granted_by == role)
+                    .map(|(_, permission)| *permission)
+            })
+            .collect();
+        permissions.sort_by_key(|permission| permission.as_str());
+        permissions.dedup();
+
+        Ok(permissions)
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::RecoveryCode;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::RecoveryCodeRepository;
+
+#[derive(Clone)]
+pub struct InMemoryRecoveryCodeRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryRecoveryCodeRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AuthDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RecoveryCodeRepository for InMemoryRecoveryCodeRepository {
+    async fn find_unused_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RecoveryCode>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .recovery_codes
+            .iter()
+            .filter(|code| code.user_id == user_id && code.used_at.is_none())
+            .cloned()
+            .collect())
+    }
+
+    async fn count_unused_by_user_id(&self, user_id: Uuid) -> Result<i64, AuthDomainError> {
+        Ok(self.find_unused_by_user_id(user_id).await?.len() as i64)
+    }
+
+    async fn mark_as_used(
+        &self,
+        code_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        let mut tables = self.write("recovery_codes")?;
+
+        match tables
+            .recovery_codes
+            .iter_mut()
+            .find(|code| code.id == code_id && code.used_at.is_none())
+        {
+            Some(code) => {
+                code.used_at = Some(now);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn replace_all_by_user_id(
+        &self,
+        user_id: Uuid,
+        codes: &[RecoveryCode],
+    ) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("recovery_codes")?;
+
+        tables.recovery_codes.retain(|code| code.user_id != user_id);
+        tables.recovery_codes.extend_from_slice(codes);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::audit::domain::entities::SecurityEventOutcome;
+use crate::features::auth::domain::entities::{DeviceInfo, UserToken};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::TokenRepository;
+
+#[derive(Clone)]
+pub struct InMemoryTokenRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryTokenRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AuthDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+
+    fn delete_where(
+        &self,
+        predicate: impl Fn(&UserToken) -> bool,
+    ) -> Result<Vec<Uuid>, AuthDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        let (deleted, kept) = std::mem::take(&mut tables.user_tokens)
+            .into_iter()
+            .partition(|token| predicate(token));
+        tables.user_tokens = kept;
+
+        Ok(deleted
+            .into_iter()
+            .map(|token: UserToken| token.token_id)
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenRepository for InMemoryTokenRepository {
+    async fn save(&self, token: &UserToken) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        if tables
+            .user_tokens
+            .iter()
+            .any(|existing| existing.token_id == token.token_id)
+        {
+            return Err(AuthDomainError::InvalidToken);
+        }
+
+        tables.user_tokens.push(token.clone());
+
+        Ok(())
+    }
+
+    async fn find_by_user_id_and_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<Option<UserToken>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .user_tokens
+            .iter()
+            .find(|token| token.user_id == user_id && token.token_id == token_id)
+            .cloned())
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserToken>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .user_tokens
+            .iter()
+            .filter(|token| token.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_by_token_id(&self, token_id: Uuid) -> Result<(), AuthDomainError> {
+        self.delete_where(|token| token.token_id == token_id)?;
+
+        Ok(())
+    }
+
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError> {
+        self.delete_where(|token| token.user_id == user_id)
+    }
+
+    async fn mark_as_rotated(
+        &self,
+        token_id: Uuid,
+        rotated_at: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        match tables
+            .user_tokens
+            .iter_mut()
+            .find(|token| token.token_id == token_id && token.rotated_at.is_none())
+        {
+            Some(token) => {
+                token.rotated_at = Some(rotated_at);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn delete_all_by_family_id(&self, family_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError> {
+        self.delete_where(|token| token.family_id == family_id)
+    }
+
+    async fn is_known_device(
+        &self,
+        user_id: Uuid,
+        device_info: &DeviceInfo,
+    ) -> Result<bool, AuthDomainError> {
+        let tables = self.database.tables();
+
+        // Same device as the Postgres query: the app version isn't part of it
+        let has_session = tables.user_tokens.iter().any(|token| {
+            token.user_id == user_id
+                && token.os == device_info.os
+                && token.model == device_info.model
+                && token.browser == device_info.browser
+        });
+        let signed_in_before = tables.security_events.iter().any(|event| {
+            event.user_id == user_id
+                && event.actor_id == Some(user_id)
+                && event.outcome == SecurityEventOutcome::Success
+                && event.os == device_info.os
+                && event.model == device_info.model
+                && event.browser == device_info.browser
+        });
+
+        Ok(has_session || signed_in_before)
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::User;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::UserRepository;
+
+#[derive(Clone)]
+pub struct InMemoryUserRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, AuthDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn create(&self, user: &User) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("users")?;
+
+        // The constraints of the table
+        if tables
+            .users
+            .iter()
+            .any(|existing| existing.id == user.id || existing.username == user.username)
+        {
+            return Err(AuthDomainError::UserNotFound);
+        }
+
+        tables.users.push(user.clone());
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables.users.iter().find(|user| user.id == user_id).cloned())
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, AuthDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.username == username)
+            .cloned())
+    }
+
+    async fn update(&self, user: &User) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("users")?;
+
+        if let Some(existing) = tables
+            .users
+            .iter_mut()
+            .find(|existing| existing.id == user.id)
+        {
+            existing.username = user.username.clone();
+            existing.password_hash = user.password_hash.clone();
+            existing.locale = user.locale.clone();
+            existing.theme = user.theme.clone();
+            existing.otp_verified = user.otp_verified;
+            existing.updated_at = user.updated_at;
+            existing.password_is_expired = user.password_is_expired;
+        }
+
+        Ok(())
+    }
+
+    async fn update_otp_secret(
+        &self,
+        user_id: Uuid,
+        otp_base32: Option<&str>,
+        otp_auth_url: Option<&str>,
+    ) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("users")?;
+
+        if let Some(user) = tables.users.iter_mut().find(|user| user.id == user_id) {
+            user.otp_base32 = otp_base32.map(str::to_string);
+            user.otp_auth_url = otp_auth_url.map(str::to_string);
+            user.otp_last_used_step = None;
+        }
+
+        Ok(())
+    }
+
+    async fn claim_otp_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthDomainError> {
+        let mut tables = self.write("users")?;
+
+        match tables.users.iter_mut().find(|user| user.id == user_id) {
+            Some(user) if user.otp_last_used_step.is_none_or(|last| last < step) => {
+                user.otp_last_used_step = Some(step);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_deletion_scheduled_at(
+        &self,
+        user_id: Uuid,
+        deletion_scheduled_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AuthDomainError> {
+        let mut tables = self.write("users")?;
+
+        if let Some(user) = tables.users.iter_mut().find(|user| user.id == user_id) {
+            user.deletion_scheduled_at = deletion_scheduled_at;
+        }
+
+        Ok(())
+    }
+}
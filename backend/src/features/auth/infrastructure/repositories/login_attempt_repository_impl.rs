@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::auth::domain::entities::{AttemptSubject, LoginAttempts};
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::LoginAttemptRepository;
+use crate::features::auth::infrastructure::models::LoginAttemptModel;
+
+#[derive(Clone)]
+pub struct LoginAttemptRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl LoginAttemptRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl LoginAttemptRepository for LoginAttemptRepositoryImpl {
+    async fn find_by_subject(
+        &self,
+        subject: &AttemptSubject,
+    ) -> Result<Option<LoginAttempts>, AuthDomainError> {
+        let result = sqlx::query_as!(
+            LoginAttemptModel,
+            r#"
+            SELECT scope, key, failed_attempts, window_started_at, locked_until
+            FROM login_attempts
+            WHERE scope = $1 AND key = $2
+            "#,
+            subject.scope.as_str(),
+            subject.key,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.map(|model| model.into()))
+    }
+
+    async fn record_failure(
+        &self,
+        subject: &AttemptSubject,
+        now: DateTime<Utc>,
+        window_started_after: DateTime<Utc>,
+    ) -> Result<LoginAttempts, AuthDomainError> {
+        // Done in a single statement so concurrent failures are all counted
+        let result = sqlx::query_as!(
+            LoginAttemptModel,
+            r#"
+            INSERT INTO login_attempts (scope, key, failed_attempts, window_started_at)
+            VALUES ($1, $2, 1, $3)
+            ON CONFLICT (scope, key) DO UPDATE SET
+                failed_attempts = CASE
+                    WHEN login_attempts.window_started_at < $4 THEN 1
+                    ELSE login_attempts.failed_attempts + 1
+                END,
+                window_started_at = CASE
+                    WHEN login_attempts.window_started_at < $4 THEN $3
+                    ELSE login_attempts.window_started_at
+                END
+            RETURNING scope, key, failed_attempts, window_started_at, locked_until
+            "#,
+            subject.scope.as_str(),
+            subject.key,
+            now,
+            window_started_after,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.into())
+    }
+
+    async fn lock(
+        &self,
+        subject: &AttemptSubject,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), AuthDomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE login_attempts
+            SET locked_until = $3
+            WHERE scope = $1 AND key = $2
+            "#,
+            subject.scope.as_str(),
+            subject.key,
+            locked_until,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, subject: &AttemptSubject) -> Result<(), AuthDomainError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM login_attempts
+            WHERE scope = $1 AND key = $2
+            "#,
+            subject.scope.as_str(),
+            subject.key,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
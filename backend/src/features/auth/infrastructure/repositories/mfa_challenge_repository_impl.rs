@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::auth::domain::entities::MfaChallenge;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::MfaChallengeRepository;
+use crate::features::auth::infrastructure::models::MfaChallengeModel;
+
+#[derive(Clone)]
+pub struct MfaChallengeRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl MfaChallengeRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<Connection, AuthDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl MfaChallengeRepository for MfaChallengeRepositoryImpl {
+    async fn save(&self, challenge: &MfaChallenge) -> Result<(), AuthDomainError> {
+        let model: MfaChallengeModel = challenge.clone().into();
+
+        let mut conn = self.connection().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO mfa_challenges (id, user_id, expires_at, consumed_at, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            model.id,
+            model.user_id,
+            model.expires_at,
+            model.consumed_at,
+            model.created_at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_active(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<MfaChallenge>, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query_as!(
+            MfaChallengeModel,
+            r#"
+            SELECT id, user_id, expires_at, consumed_at, created_at
+            FROM mfa_challenges
+            WHERE id = $1 AND consumed_at IS NULL AND expires_at > $2
+            "#,
+            challenge_id,
+            now,
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.map(|model| model.into()))
+    }
+
+    async fn consume(
+        &self,
+        challenge_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        // Conditional update so that two concurrent validations can't both consume it
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE mfa_challenges
+            SET consumed_at = $2
+            WHERE id = $1 AND consumed_at IS NULL AND expires_at > $2
+            "#,
+            challenge_id,
+            now,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -1,6 +1,16 @@
+pub mod login_attempt_repository_impl;
+pub mod mfa_challenge_repository_impl;
+pub mod permission_repository_impl;
+pub mod recovery_code_repository_impl;
 pub mod token_repository_impl;
 pub mod user_repository_impl;
 
+pub use login_attempt_repository_impl::LoginAttemptRepositoryImpl;
+pub use mfa_challenge_repository_impl::MfaChallengeRepositoryImpl;
+pub use permission_repository_impl::PermissionRepositoryImpl;
+pub use recovery_code_repository_impl::RecoveryCodeRepositoryImpl;
 pub use token_repository_impl::{TokenRepositoryImpl, TokenServiceImpl};
 pub use user_repository_impl::UserRepositoryImpl;
 
+#[cfg(feature = "test-util")]
+pub mod in_memory;
@@ -0,0 +1,53 @@
+use uuid::Uuid;
+
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::auth::domain::entities::Permission;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::PermissionRepository;
+
+#[derive(Clone)]
+pub struct PermissionRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl PermissionRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<Connection, AuthDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PermissionRepository for PermissionRepositoryImpl {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Permission>, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let names = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT role_permissions.permission
+            FROM user_roles
+            JOIN role_permissions ON role_permissions.role = user_roles.role
+            WHERE user_roles.user_id = $1
+            ORDER BY role_permissions.permission
+            "#,
+            user_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        // A permission added to the table before the code knows it grants nothing
+        Ok(names
+            .iter()
+            .filter_map(|name| Permission::from_name(name))
+            .collect())
+    }
+}
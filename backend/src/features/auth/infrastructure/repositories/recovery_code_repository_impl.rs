@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::auth::domain::entities::RecoveryCode;
+use crate::features::auth::domain::errors::AuthDomainError;
+use crate::features::auth::domain::repositories::RecoveryCodeRepository;
+use crate::features::auth::infrastructure::models::RecoveryCodeModel;
+
+#[derive(Clone)]
+pub struct RecoveryCodeRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl RecoveryCodeRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn connection(&self) -> Result<Connection, AuthDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl RecoveryCodeRepository for RecoveryCodeRepositoryImpl {
+    async fn find_unused_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RecoveryCode>, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query_as!(
+            RecoveryCodeModel,
+            r#"
+            SELECT id, user_id, code_hash, used_at, created_at
+            FROM recovery_codes
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+            user_id,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.into_iter().map(|model| model.into()).collect())
+    }
+
+    async fn count_unused_by_user_id(&self, user_id: Uuid) -> Result<i64, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM recovery_codes
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+            user_id,
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(count)
+    }
+
+    async fn mark_as_used(
+        &self,
+        code_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        // Conditional update so that a code can't be used by two concurrent recoveries
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE recovery_codes
+            SET used_at = $2
+            WHERE id = $1 AND used_at IS NULL
+            "#,
+            code_id,
+            now,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn replace_all_by_user_id(
+        &self,
+        user_id: Uuid,
+        codes: &[RecoveryCode],
+    ) -> Result<(), AuthDomainError> {
+        // A savepoint when the caller already runs in a transaction
+        let mut conn = self.connection().await?;
+        let mut transaction = sqlx::Connection::begin(&mut *conn).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM recovery_codes
+            WHERE user_id = $1
+            "#,
+            user_id,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        for code in codes {
+            let model: RecoveryCodeModel = code.clone().into();
+
+            sqlx::query!(
+                r#"
+                INSERT INTO recovery_codes (id, user_id, code_hash, used_at, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                model.id,
+                model.user_id,
+                model.code_hash,
+                model.used_at,
+                model.created_at,
+            )
+            .execute(&mut *transaction)
+            .await
+            .map_err(|e| {
+                tracing::error!("Database error: {}", e);
+                AuthDomainError::DatabaseError
+            })?;
+        }
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
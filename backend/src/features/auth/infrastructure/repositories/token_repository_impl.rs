@@ -1,11 +1,16 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::features::auth::domain::entities::{Claims, UserToken};
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::auth::domain::entities::{
+    Claims, DeviceInfo, JsonWebKey, TokenType, UserToken,
+};
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::{TokenRepository, TokenService};
+use crate::features::auth::helpers::token::hash_token;
+use crate::features::auth::infrastructure::keys::JwtKeySet;
 use crate::features::auth::infrastructure::models::UserTokenModel;
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
-use sha2::{Digest, Sha256};
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
 
 #[derive(Clone)]
 pub struct TokenRepositoryImpl {
@@ -16,17 +21,28 @@ impl TokenRepositoryImpl {
     pub fn new(pool: sqlx::PgPool) -> Self {
         Self { pool }
     }
+
+    async fn connection(&self) -> Result<Connection, AuthDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
 }
 
 #[async_trait::async_trait]
 impl TokenRepository for TokenRepositoryImpl {
     async fn save(&self, token: &UserToken) -> Result<(), AuthDomainError> {
         let token_model: UserTokenModel = token.clone().into();
-        
+
+        let mut conn = self.connection().await?;
         sqlx::query!(
             r#"
-            INSERT INTO user_tokens (id, user_id, token_id, expires_at, os, is_mobile, browser, app_version, model)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO user_tokens (
+                id, user_id, token_id, expires_at, os, is_mobile, browser, app_version, model,
+                family_id, parent_token_id, rotated_at, created_at, last_activity_at, last_ip
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             "#,
             token_model.id,
             token_model.user_id,
@@ -36,9 +52,15 @@ impl TokenRepository for TokenRepositoryImpl {
             token_model.is_mobile,
             token_model.browser,
             token_model.app_version,
-            token_model.model
+            token_model.model,
+            token_model.family_id,
+            token_model.parent_token_id,
+            token_model.rotated_at,
+            token_model.created_at,
+            token_model.last_activity_at,
+            token_model.last_ip,
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -53,6 +75,7 @@ impl TokenRepository for TokenRepositoryImpl {
         user_id: Uuid,
         token_id: Uuid,
     ) -> Result<Option<UserToken>, AuthDomainError> {
+        let mut conn = self.connection().await?;
         let token_model = sqlx::query_as!(
             UserTokenModel,
             r#"
@@ -63,7 +86,7 @@ impl TokenRepository for TokenRepositoryImpl {
             user_id,
             token_id,
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -74,6 +97,7 @@ impl TokenRepository for TokenRepositoryImpl {
     }
 
     async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserToken>, AuthDomainError> {
+        let mut conn = self.connection().await?;
         let tokens = sqlx::query_as!(
             UserTokenModel,
             r#"
@@ -83,7 +107,7 @@ impl TokenRepository for TokenRepositoryImpl {
             "#,
             user_id
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -94,6 +118,7 @@ impl TokenRepository for TokenRepositoryImpl {
     }
 
     async fn delete_by_token_id(&self, token_id: Uuid) -> Result<(), AuthDomainError> {
+        let mut conn = self.connection().await?;
         sqlx::query!(
             r#"
             DELETE
@@ -102,7 +127,7 @@ impl TokenRepository for TokenRepositoryImpl {
             "#,
             token_id
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -112,68 +137,203 @@ impl TokenRepository for TokenRepositoryImpl {
         Ok(())
     }
 
-    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<(), AuthDomainError> {
-        sqlx::query!(
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let token_ids = sqlx::query_scalar!(
             r#"
             DELETE
             FROM user_tokens
             WHERE user_id = $1
+            RETURNING token_id
             "#,
             user_id
         )
-        .execute(&self.pool)
+        .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
             AuthDomainError::InvalidToken
         })?;
 
-        Ok(())
+        Ok(token_ids)
+    }
+
+    async fn mark_as_rotated(
+        &self,
+        token_id: Uuid,
+        rotated_at: DateTime<Utc>,
+    ) -> Result<bool, AuthDomainError> {
+        // The condition on rotated_at makes concurrent rotations of the same token race safely
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_tokens
+            SET rotated_at = $2
+            WHERE token_id = $1 AND rotated_at IS NULL
+            "#,
+            token_id,
+            rotated_at
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::InvalidToken
+        })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn delete_all_by_family_id(&self, family_id: Uuid) -> Result<Vec<Uuid>, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let token_ids = sqlx::query_scalar!(
+            r#"
+            DELETE
+            FROM user_tokens
+            WHERE family_id = $1
+            RETURNING token_id
+            "#,
+            family_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::InvalidToken
+        })?;
+
+        Ok(token_ids)
+    }
+
+    async fn is_known_device(
+        &self,
+        user_id: Uuid,
+        device_info: &DeviceInfo,
+    ) -> Result<bool, AuthDomainError> {
+        // The app version changes with every update, so it isn't part of the device. The
+        // sessions are deleted when revoked or expired, the events the user caused remain.
+        let mut conn = self.connection().await?;
+        let is_known = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM user_tokens
+                WHERE user_id = $1
+                    AND os IS NOT DISTINCT FROM $2
+                    AND model IS NOT DISTINCT FROM $3
+                    AND browser IS NOT DISTINCT FROM $4
+            ) OR EXISTS (
+                SELECT 1
+                FROM security_events
+                WHERE user_id = $1
+                    AND actor_id = $1
+                    AND outcome = 'success'
+                    AND os IS NOT DISTINCT FROM $2
+                    AND model IS NOT DISTINCT FROM $3
+                    AND browser IS NOT DISTINCT FROM $4
+            ) AS "is_known!"
+            "#,
+            user_id,
+            device_info.os,
+            device_info.model,
+            device_info.browser,
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(is_known)
     }
 }
 
 #[derive(Clone)]
 pub struct TokenServiceImpl {
-    secret_key: Vec<u8>,
+    key_set: JwtKeySet,
+    issuer: String,
+    audience: String,
 }
 
 impl TokenServiceImpl {
-    pub fn new(secret_key: Vec<u8>) -> Self {
-        Self { secret_key }
+    pub fn new(key_set: JwtKeySet, issuer: String, audience: String) -> Self {
+        Self {
+            key_set,
+            issuer,
+            audience,
+        }
+    }
+
+    fn encode_token(
+        &self,
+        claims: &Claims,
+        token_type: TokenType,
+    ) -> Result<String, AuthDomainError> {
+        if claims.token_type != token_type {
+            return Err(AuthDomainError::InvalidToken);
+        }
+
+        let signing_key = self.key_set.signing_key();
+        let mut header = Header::new(signing_key.algorithm);
+        header.kid = Some(signing_key.kid.clone());
+
+        encode(&header, claims, &signing_key.encoding_key)
+            .map_err(|_| AuthDomainError::InvalidToken)
     }
 }
 
 impl TokenService for TokenServiceImpl {
     fn generate_access_token(&self, claims: &Claims) -> Result<String, AuthDomainError> {
-        encode(
-            &Header::default(),
-            claims,
-            &EncodingKey::from_secret(&self.secret_key),
-        )
-        .map_err(|_| AuthDomainError::InvalidToken)
+        self.encode_token(claims, TokenType::Access)
     }
 
     fn generate_refresh_token(&self, claims: &Claims) -> Result<String, AuthDomainError> {
-        encode(
-            &Header::default(),
-            claims,
-            &EncodingKey::from_secret(&self.secret_key),
-        )
-        .map_err(|_| AuthDomainError::InvalidToken)
+        self.encode_token(claims, TokenType::Refresh)
+    }
+
+    fn generate_mfa_challenge_token(&self, claims: &Claims) -> Result<String, AuthDomainError> {
+        self.encode_token(claims, TokenType::MfaChallenge)
+    }
+
+    fn generate_data_export_token(&self, claims: &Claims) -> Result<String, AuthDomainError> {
+        self.encode_token(claims, TokenType::DataExport)
     }
 
-    fn decode_token(&self, token: &str) -> Result<Claims, AuthDomainError> {
-        let decoding_key = DecodingKey::from_secret(&self.secret_key);
-        let token_data = decode::<Claims>(token, &decoding_key, &Validation::default())
+    fn generate_sign_in_report_token(&self, claims: &Claims) -> Result<String, AuthDomainError> {
+        self.encode_token(claims, TokenType::SignInReport)
+    }
+
+    fn decode_token(&self, token: &str, token_type: TokenType) -> Result<Claims, AuthDomainError> {
+        // The key is picked from the `kid` header, but the algorithm always comes from our own
+        // configuration so a token can't downgrade itself to another algorithm
+        let header = decode_header(token).map_err(|_| AuthDomainError::InvalidToken)?;
+        let verification_key = header
+            .kid
+            .as_deref()
+            .and_then(|kid| self.key_set.verification_key(kid))
+            .ok_or(AuthDomainError::InvalidToken)?;
+
+        let mut validation = Validation::new(verification_key.algorithm);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+
+        let token_data = decode::<Claims>(token, &verification_key.decoding_key, &validation)
             .map_err(|_| AuthDomainError::InvalidToken)?;
 
+        // A token must never be accepted where another type of token is expected
+        if token_data.claims.token_type != token_type {
+            return Err(AuthDomainError::InvalidToken);
+        }
+
         Ok(token_data.claims)
     }
 
     fn hash_token(&self, token: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(token);
-        format!("{:X}", hasher.finalize())
+        hash_token(token)
     }
-}
 
+    fn public_keys(&self) -> Vec<JsonWebKey> {
+        self.key_set.public_keys()
+    }
+}
@@ -1,19 +1,61 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::core::structs::envelope_cipher::EnvelopeCipher;
+use crate::core::structs::transaction::{self, Connection};
 use crate::features::auth::domain::entities::User;
 use crate::features::auth::domain::errors::AuthDomainError;
 use crate::features::auth::domain::repositories::UserRepository;
 use crate::features::auth::infrastructure::models::UserModel;
-// Unused imports removed
+use crate::features::auth::infrastructure::otp_secrets::{
+    decrypt_otp_column, encrypt_otp_column, OTP_AUTH_URL_COLUMN, OTP_BASE32_COLUMN,
+};
 
 #[derive(Clone)]
 pub struct UserRepositoryImpl {
     pool: sqlx::PgPool,
+    cipher: EnvelopeCipher,
 }
 
 impl UserRepositoryImpl {
-    pub fn new(pool: sqlx::PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: sqlx::PgPool, cipher: EnvelopeCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    fn encrypt(
+        &self,
+        user_id: Uuid,
+        column: &str,
+        value: Option<&str>,
+    ) -> Result<Option<String>, AuthDomainError> {
+        encrypt_otp_column(&self.cipher, user_id, column, value).map_err(|e| {
+            tracing::error!("Encryption error: {}", e);
+            AuthDomainError::DatabaseError
+        })
+    }
+
+    // The OTP secret and auth URL are only decrypted once loaded
+    fn decrypt_user(&self, mut user_model: UserModel) -> Result<User, AuthDomainError> {
+        let decrypt = |column: &str, value: Option<String>| {
+            decrypt_otp_column(&self.cipher, user_model.id, column, value).map_err(|e| {
+                tracing::error!("Decryption error: {}", e);
+                AuthDomainError::DatabaseError
+            })
+        };
+        let otp_base32 = decrypt(OTP_BASE32_COLUMN, user_model.otp_base32.take())?;
+        let otp_auth_url = decrypt(OTP_AUTH_URL_COLUMN, user_model.otp_auth_url.take())?;
+
+        user_model.otp_base32 = otp_base32;
+        user_model.otp_auth_url = otp_auth_url;
+
+        Ok(user_model.into())
+    }
+
+    async fn connection(&self) -> Result<Connection, AuthDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })
     }
 }
 
@@ -21,14 +63,18 @@ impl UserRepositoryImpl {
 impl UserRepository for UserRepositoryImpl {
     async fn create(&self, user: &User) -> Result<(), AuthDomainError> {
         let user_model: UserModel = user.clone().into();
-        
+        let otp_base32 = self.encrypt(user.id, OTP_BASE32_COLUMN, user.otp_base32.as_deref())?;
+        let otp_auth_url =
+            self.encrypt(user.id, OTP_AUTH_URL_COLUMN, user.otp_auth_url.as_deref())?;
+
+        let mut conn = self.connection().await?;
         sqlx::query!(
             r#"
             INSERT INTO users (
                 id, username, password, locale, theme, otp_verified, otp_base32, otp_auth_url,
-                created_at, updated_at, recovery_codes, password_is_expired, is_admin
+                created_at, updated_at, password_is_expired
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
             user_model.id,
             user_model.username,
@@ -36,15 +82,13 @@ impl UserRepository for UserRepositoryImpl {
             user_model.locale,
             user_model.theme,
             user_model.otp_verified,
-            user_model.otp_base32,
-            user_model.otp_auth_url,
+            otp_base32,
+            otp_auth_url,
             user_model.created_at,
             user_model.updated_at,
-            user_model.recovery_codes,
             user_model.password_is_expired,
-            user_model.is_admin,
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -55,6 +99,7 @@ impl UserRepository for UserRepositoryImpl {
     }
 
     async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AuthDomainError> {
+        let mut conn = self.connection().await?;
         let user_model = sqlx::query_as!(
             UserModel,
             r#"
@@ -64,17 +109,18 @@ impl UserRepository for UserRepositoryImpl {
             "#,
             user_id,
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
             AuthDomainError::UserNotFound
         })?;
 
-        Ok(user_model.map(|u| u.into()))
+        user_model.map(|u| self.decrypt_user(u)).transpose()
     }
 
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AuthDomainError> {
+        let mut conn = self.connection().await?;
         let user_model = sqlx::query_as!(
             UserModel,
             r#"
@@ -84,42 +130,38 @@ impl UserRepository for UserRepositoryImpl {
             "#,
             username
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
             AuthDomainError::UserNotFound
         })?;
 
-        Ok(user_model.map(|u| u.into()))
+        user_model.map(|u| self.decrypt_user(u)).transpose()
     }
 
     async fn update(&self, user: &User) -> Result<(), AuthDomainError> {
         let user_model: UserModel = user.clone().into();
-        
+
+        let mut conn = self.connection().await?;
         sqlx::query!(
             r#"
             UPDATE users
-            SET 
-                username = $1, password = $2, locale = $3, theme = $4,
-                otp_verified = $5, otp_base32 = $6, otp_auth_url = $7,
-                updated_at = $8, recovery_codes = $9, password_is_expired = $10, is_admin = $11
-            WHERE id = $12
+            SET
+                username = $1, password = $2, locale = $3, theme = $4, otp_verified = $5,
+                updated_at = $6, password_is_expired = $7
+            WHERE id = $8
             "#,
             user_model.username,
             user_model.password,
             user_model.locale,
             user_model.theme,
             user_model.otp_verified,
-            user_model.otp_base32,
-            user_model.otp_auth_url,
             user_model.updated_at,
-            user_model.recovery_codes,
             user_model.password_is_expired,
-            user_model.is_admin,
             user_model.id,
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -128,5 +170,81 @@ impl UserRepository for UserRepositoryImpl {
 
         Ok(())
     }
-}
 
+    async fn update_otp_secret(
+        &self,
+        user_id: Uuid,
+        otp_base32: Option<&str>,
+        otp_auth_url: Option<&str>,
+    ) -> Result<(), AuthDomainError> {
+        let otp_base32 = self.encrypt(user_id, OTP_BASE32_COLUMN, otp_base32)?;
+        let otp_auth_url = self.encrypt(user_id, OTP_AUTH_URL_COLUMN, otp_auth_url)?;
+
+        // Steps used with the previous secret say nothing about the new one
+        let mut conn = self.connection().await?;
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET otp_base32 = $2, otp_auth_url = $3, otp_last_used_step = NULL
+            WHERE id = $1
+            "#,
+            user_id,
+            otp_base32,
+            otp_auth_url,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn claim_otp_step(&self, user_id: Uuid, step: i64) -> Result<bool, AuthDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET otp_last_used_step = $2
+            WHERE id = $1 AND (otp_last_used_step IS NULL OR otp_last_used_step < $2)
+            "#,
+            user_id,
+            step,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn update_deletion_scheduled_at(
+        &self,
+        user_id: Uuid,
+        deletion_scheduled_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AuthDomainError> {
+        let mut conn = self.connection().await?;
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET deletion_scheduled_at = $2
+            WHERE id = $1
+            "#,
+            user_id,
+            deletion_scheduled_at,
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            AuthDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
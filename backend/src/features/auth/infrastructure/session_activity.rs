@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::configuration::SessionActivitySettings;
+
+#[derive(Debug, Clone)]
+pub struct SessionActivity {
+    pub last_activity_at: DateTime<Utc>,
+    pub last_ip: Option<String>,
+}
+
+/// Buffers the activity of the sessions seen by the token validator, and writes it to
+/// `user_tokens` in batches so that authenticated requests don't each cost a write.
+#[derive(Clone)]
+pub struct SessionActivityWriter {
+    pool: PgPool,
+    settings: SessionActivitySettings,
+    pending: Arc<Mutex<HashMap<Uuid, SessionActivity>>>,
+}
+
+impl SessionActivityWriter {
+    pub fn new(pool: PgPool, settings: SessionActivitySettings) -> Self {
+        Self {
+            pool,
+            settings,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Minimum time between two recorded activities of a session.
+    pub fn debounce(&self) -> Duration {
+        Duration::seconds(self.settings.debounce_seconds)
+    }
+
+    pub async fn record(&self, token_id: Uuid, activity: SessionActivity) {
+        self.pending.lock().await.insert(token_id, activity);
+    }
+
+    /// The activity recorded for a session that wasn't written yet.
+    pub async fn pending_activity(&self, token_id: Uuid) -> Option<SessionActivity> {
+        self.pending.lock().await.get(&token_id).cloned()
+    }
+
+    /// Writes the recorded activity, returns the number of sessions updated. Activity that
+    /// could not be written is kept for the next flush.
+    pub async fn flush(&self) -> Result<u64, sqlx::Error> {
+        let activities: Vec<(Uuid, SessionActivity)> =
+            std::mem::take(&mut *self.pending.lock().await)
+                .into_iter()
+                .collect();
+
+        let mut updated = 0;
+        let batch_size = self.settings.batch_size.max(1);
+
+        for (index, batch) in activities.chunks(batch_size).enumerate() {
+            match self.write_batch(batch).await {
+                Ok(rows_affected) => updated += rows_affected,
+                Err(e) => {
+                    let mut pending = self.pending.lock().await;
+                    // Activity recorded in the meantime is more recent
+                    for (token_id, activity) in &activities[index * batch_size..] {
+                        pending.entry(*token_id).or_insert_with(|| activity.clone());
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Flushes the recorded activity every `flush_interval_seconds`.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                self.settings.flush_interval_seconds,
+            ));
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.flush().await {
+                    tracing::error!("Failed to write the session activity: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn write_batch(&self, batch: &[(Uuid, SessionActivity)]) -> Result<u64, sqlx::Error> {
+        let token_ids: Vec<Uuid> = batch.iter().map(|(token_id, _)| *token_id).collect();
+        let last_activity_dates: Vec<DateTime<Utc>> = batch
+            .iter()
+            .map(|(_, activity)| activity.last_activity_at)
+            .collect();
+        let last_ips: Vec<Option<String>> = batch
+            .iter()
+            .map(|(_, activity)| activity.last_ip.clone())
+            .collect();
+
+        // Tokens deleted in the meantime are skipped, and older activity never replaces newer
+        let result = sqlx::query!(
+            r#"
+            UPDATE user_tokens
+            SET last_activity_at = activity.last_activity_at,
+                last_ip = COALESCE(activity.last_ip, user_tokens.last_ip)
+            FROM UNNEST($1::uuid[], $2::timestamptz[], $3::text[])
+                AS activity(token_id, last_activity_at, last_ip)
+            WHERE user_tokens.token_id = activity.token_id
+                AND (
+                    user_tokens.last_activity_at IS NULL
+                    OR user_tokens.last_activity_at <= activity.last_activity_at
+                )
+            "#,
+            &token_ids,
+            &last_activity_dates,
+            &last_ips as &[Option<String>],
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
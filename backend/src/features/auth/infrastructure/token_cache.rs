@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::features::auth::structs::models::{
+    InMemoryTokenCacheBackend, TokenCacheBackend, TokenCacheMetrics,
+};
+
+pub const TOKEN_CACHE_INVALIDATION_CHANNEL: &str = "token_cache_invalidation";
+
+/// Keeps the tokens in memory, and broadcasts their removal to every instance connected to
+/// the database through `NOTIFY`, so a revoked token is rejected everywhere right away.
+pub struct PostgresTokenCacheBackend {
+    pool: PgPool,
+    local: InMemoryTokenCacheBackend,
+}
+
+impl PostgresTokenCacheBackend {
+    pub fn new(pool: PgPool, local: InMemoryTokenCacheBackend) -> Arc<Self> {
+        Arc::new(Self { pool, local })
+    }
+
+    /// Starts listening to the removals made by the other instances. Returns once listening,
+    /// so that no removal made afterwards is missed.
+    pub async fn listen(self: Arc<Self>) -> Result<JoinHandle<()>, sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(TOKEN_CACHE_INVALIDATION_CHANNEL).await?;
+
+        Ok(tokio::spawn(async move {
+            loop {
+                match listener.try_recv().await {
+                    Ok(Some(notification)) => match notification.payload().parse::<Uuid>() {
+                        Ok(token_id) => self.local.remove_key(token_id).await,
+                        Err(e) => tracing::error!("Invalid token cache invalidation: {}", e),
+                    },
+                    // The connection was lost, the next call reconnects. The removals made in
+                    // the meantime are unknown, so every token is checked again.
+                    Ok(None) => self.local.clear().await,
+                    Err(e) => {
+                        tracing::error!("Token cache listener error: {}", e);
+                        self.local.clear().await;
+                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenCacheBackend for PostgresTokenCacheBackend {
+    async fn update_or_insert_key(
+        &self,
+        key: Uuid,
+        value: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) {
+        self.local
+            .update_or_insert_key(key, value, expires_at)
+            .await;
+    }
+
+    async fn remove_key(&self, key: Uuid) {
+        self.local.remove_key(key).await;
+
+        if let Err(e) = sqlx::query!(
+            "SELECT pg_notify($1, $2)",
+            TOKEN_CACHE_INVALIDATION_CHANNEL,
+            key.to_string()
+        )
+        .execute(&self.pool)
+        .await
+        {
+            tracing::error!("Failed to broadcast the token cache invalidation: {}", e);
+        }
+    }
+
+    async fn get_value_for_key(&self, key: Uuid) -> Option<DateTime<Utc>> {
+        self.local.get_value_for_key(key).await
+    }
+
+    async fn remove_expired(&self, now: DateTime<Utc>) -> usize {
+        self.local.remove_expired(now).await
+    }
+
+    async fn metrics(&self) -> TokenCacheMetrics {
+        self.local.metrics().await
+    }
+}
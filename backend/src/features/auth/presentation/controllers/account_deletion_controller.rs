@@ -0,0 +1,56 @@
+use actix_web::{delete, web, web::ReqData, HttpRequest, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::helpers::too_many_attempts::too_many_attempts_response;
+use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
+use crate::features::auth::application::dto::DeleteAccountRequest;
+use crate::features::auth::application::usecases::DeleteAccountUseCase;
+use crate::features::auth::domain::entities::Claims;
+
+#[delete("/me")]
+pub async fn delete_account(
+    req: HttpRequest,
+    body: web::Json<DeleteAccountRequest>,
+    request_claims: ReqData<Claims>,
+    use_case: web::Data<DeleteAccountUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(request_claims.user_id, body.into_inner(), context)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(e) => {
+            error!("Delete account error: {}", e);
+            match e {
+                crate::features::auth::domain::errors::AuthDomainError::InvalidCredentials => {
+                    HttpResponse::Unauthorized().json(GenericResponse {
+                        code: "INVALID_PASSWORD".to_string(),
+                        message: "Invalid password".to_string(),
+                    })
+                }
+                crate::features::auth::domain::errors::AuthDomainError::InvalidOtp => {
+                    HttpResponse::Unauthorized().json(GenericResponse {
+                        code: "INVALID_ONE_TIME_PASSWORD".to_string(),
+                        message: "Invalid one time password".to_string(),
+                    })
+                }
+                crate::features::auth::domain::errors::AuthDomainError::UserNotFound => {
+                    HttpResponse::NotFound().json(GenericResponse {
+                        code: "USER_NOT_FOUND".to_string(),
+                        message: "User not found".to_string(),
+                    })
+                }
+                _ => HttpResponse::InternalServerError().json(GenericResponse {
+                    code: "ACCOUNT_DELETION_ERROR".to_string(),
+                    message: "Failed to delete account".to_string(),
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use actix_web::{get, web, HttpResponse, Responder};
+
+use crate::features::auth::application::usecases::GetJwksUseCase;
+
+#[get("/.well-known/jwks.json")]
+pub async fn jwks(use_case: web::Data<GetJwksUseCase>) -> impl Responder {
+    HttpResponse::Ok().json(use_case.execute())
+}
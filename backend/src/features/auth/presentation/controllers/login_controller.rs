@@ -1,11 +1,13 @@
 use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use tracing::error;
 
+use crate::core::helpers::account_disabled::account_disabled_response;
+use crate::core::helpers::too_many_attempts::too_many_attempts_response;
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::application::dto::LoginRequest;
 use crate::features::auth::application::usecases::LoginUseCase;
 use crate::features::auth::domain::entities::DeviceInfo;
-use crate::features::profile::helpers::device_info::get_user_agent;
 use crate::features::profile::structs::models::ParsedDeviceInfo;
 
 fn parse_device_info_to_domain(parsed: ParsedDeviceInfo) -> DeviceInfo {
@@ -25,33 +27,35 @@ pub async fn login(
     use_case: web::Data<LoginUseCase>,
 ) -> impl Responder {
     let body = body.into_inner();
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body, device_info).await {
+    match use_case.execute(body, device_info, context).await {
         Ok(Ok(response)) => HttpResponse::Ok().json(response),
         Ok(Err(response)) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Login error: {}", e);
             let (status_code, error_response) = match e {
-                crate::features::auth::domain::errors::AuthDomainError::InvalidCredentials => {
-                    (
-                        actix_web::http::StatusCode::UNAUTHORIZED,
-                        GenericResponse {
-                            code: "INVALID_USERNAME_OR_PASSWORD".to_string(),
-                            message: "Invalid username or password".to_string(),
-                        },
-                    )
-                }
-                crate::features::auth::domain::errors::AuthDomainError::PasswordExpired => {
-                    (
-                        actix_web::http::StatusCode::FORBIDDEN,
-                        GenericResponse {
-                            code: "PASSWORD_MUST_BE_CHANGED".to_string(),
-                            message: "Password must be changed".to_string(),
-                        },
-                    )
-                }
+                crate::features::auth::domain::errors::AuthDomainError::InvalidCredentials => (
+                    actix_web::http::StatusCode::UNAUTHORIZED,
+                    GenericResponse {
+                        code: "INVALID_USERNAME_OR_PASSWORD".to_string(),
+                        message: "Invalid username or password".to_string(),
+                    },
+                ),
+                crate::features::auth::domain::errors::AuthDomainError::PasswordExpired => (
+                    actix_web::http::StatusCode::FORBIDDEN,
+                    GenericResponse {
+                        code: "PASSWORD_MUST_BE_CHANGED".to_string(),
+                        message: "Password must be changed".to_string(),
+                    },
+                ),
                 _ => (
                     actix_web::http::StatusCode::UNAUTHORIZED,
                     GenericResponse {
@@ -64,4 +68,3 @@ pub async fn login(
         }
     }
 }
-
@@ -10,7 +10,10 @@ pub async fn logout(
     request_claims: ReqData<Claims>,
     use_case: web::Data<LogoutUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.jti).await {
+    match use_case
+        .execute(request_claims.user_id, request_claims.jti)
+        .await
+    {
         Ok(_) => HttpResponse::Ok().json(GenericResponse {
             code: "LOGGED_OUT".to_string(),
             message: "".to_string(),
@@ -24,4 +27,3 @@ pub async fn logout(
         }
     }
 }
-
@@ -1,17 +1,23 @@
+pub mod account_deletion_controller;
+pub mod jwks_controller;
 pub mod login_controller;
 pub mod logout_controller;
 pub mod otp_controller;
+pub mod recovery_codes_controller;
 pub mod recovery_controller;
 pub mod refresh_token_controller;
+pub mod sign_in_report_controller;
 pub mod signup_controller;
 
+pub use account_deletion_controller::delete_account;
+pub use jwks_controller::jwks;
 pub use login_controller::login;
 pub use logout_controller::logout;
 pub use otp_controller::{disable_otp, generate_otp, validate_otp, verify_otp};
+pub use recovery_codes_controller::{get_remaining_recovery_codes, regenerate_recovery_codes};
 pub use recovery_controller::{
-    recover_account_using_2fa, recover_account_using_password,
-    recover_account_without_2fa_enabled,
+    recover_account_using_2fa, recover_account_using_password, recover_account_without_2fa_enabled,
 };
 pub use refresh_token_controller::refresh_token;
+pub use sign_in_report_controller::report_sign_in;
 pub use signup_controller::signup;
-
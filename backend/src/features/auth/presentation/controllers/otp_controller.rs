@@ -5,13 +5,15 @@ use actix_web::{
 };
 use tracing::error;
 
+use crate::core::helpers::account_disabled::account_disabled_response;
+use crate::core::helpers::too_many_attempts::too_many_attempts_response;
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::application::dto::{ValidateOtpRequest, VerifyOtpRequest};
 use crate::features::auth::application::usecases::{
     DisableOtpUseCase, GenerateOtpUseCase, ValidateOtpUseCase, VerifyOtpUseCase,
 };
 use crate::features::auth::domain::entities::{Claims, DeviceInfo};
-use crate::features::profile::helpers::device_info::get_user_agent;
 use crate::features::profile::structs::models::ParsedDeviceInfo;
 
 fn parse_device_info_to_domain(parsed: ParsedDeviceInfo) -> DeviceInfo {
@@ -52,11 +54,17 @@ pub async fn generate_otp(
 
 #[post("/verify")]
 pub async fn verify_otp(
+    req: HttpRequest,
     body: web::Json<VerifyOtpRequest>,
     request_claims: ReqData<Claims>,
     use_case: web::Data<VerifyOtpUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.user_id, body.into_inner()).await {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(request_claims.user_id, body.into_inner(), context)
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Verify OTP error: {}", e);
@@ -89,41 +97,51 @@ pub async fn validate_otp(
     body: web::Json<ValidateOtpRequest>,
     use_case: web::Data<ValidateOtpUseCase>,
 ) -> impl Responder {
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body.into_inner(), device_info).await {
+    match use_case
+        .execute(body.into_inner(), device_info, context)
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Validate OTP error: {}", e);
             let (status_code, error_response) = match e {
-                crate::features::auth::domain::errors::AuthDomainError::InvalidOtp => {
-                    (
-                        actix_web::http::StatusCode::UNAUTHORIZED,
-                        GenericResponse {
-                            code: "INVALID_ONE_TIME_PASSWORD".to_string(),
-                            message: "Invalid one time password".to_string(),
-                        },
-                    )
-                }
-                crate::features::auth::domain::errors::AuthDomainError::OtpNotEnabled => {
-                    (
-                        actix_web::http::StatusCode::UNAUTHORIZED,
-                        GenericResponse {
-                            code: "TWO_FACTOR_AUTHENTICATION_NOT_ENABLED".to_string(),
-                            message: "Two factor authentication is not enabled".to_string(),
-                        },
-                    )
-                }
-                crate::features::auth::domain::errors::AuthDomainError::UserNotFound => {
-                    (
-                        actix_web::http::StatusCode::NOT_FOUND,
-                        GenericResponse {
-                            code: "USER_NOT_FOUND".to_string(),
-                            message: "User not found".to_string(),
-                        },
-                    )
-                }
+                crate::features::auth::domain::errors::AuthDomainError::InvalidOtp => (
+                    actix_web::http::StatusCode::UNAUTHORIZED,
+                    GenericResponse {
+                        code: "INVALID_ONE_TIME_PASSWORD".to_string(),
+                        message: "Invalid one time password".to_string(),
+                    },
+                ),
+                crate::features::auth::domain::errors::AuthDomainError::OtpNotEnabled => (
+                    actix_web::http::StatusCode::UNAUTHORIZED,
+                    GenericResponse {
+                        code: "TWO_FACTOR_AUTHENTICATION_NOT_ENABLED".to_string(),
+                        message: "Two factor authentication is not enabled".to_string(),
+                    },
+                ),
+                crate::features::auth::domain::errors::AuthDomainError::InvalidMfaChallenge => (
+                    actix_web::http::StatusCode::UNAUTHORIZED,
+                    GenericResponse {
+                        code: "INVALID_MFA_CHALLENGE".to_string(),
+                        message: "Invalid or expired MFA challenge, log in again".to_string(),
+                    },
+                ),
+                crate::features::auth::domain::errors::AuthDomainError::UserNotFound => (
+                    actix_web::http::StatusCode::NOT_FOUND,
+                    GenericResponse {
+                        code: "USER_NOT_FOUND".to_string(),
+                        message: "User not found".to_string(),
+                    },
+                ),
                 _ => (
                     actix_web::http::StatusCode::UNAUTHORIZED,
                     GenericResponse {
@@ -139,10 +157,13 @@ pub async fn validate_otp(
 
 #[get("/disable")]
 pub async fn disable_otp(
+    req: HttpRequest,
     request_claims: ReqData<Claims>,
     use_case: web::Data<DisableOtpUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.user_id).await {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(request_claims.user_id, context).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Disable OTP error: {}", e);
@@ -153,4 +174,3 @@ pub async fn disable_otp(
         }
     }
 }
-
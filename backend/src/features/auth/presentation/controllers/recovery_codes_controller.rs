@@ -0,0 +1,69 @@
+use actix_web::{get, post, web, web::ReqData, HttpRequest, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::helpers::too_many_attempts::too_many_attempts_response;
+use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
+use crate::features::auth::application::dto::RegenerateRecoveryCodesRequest;
+use crate::features::auth::application::usecases::{
+    GetRemainingRecoveryCodesUseCase, RegenerateRecoveryCodesUseCase,
+};
+use crate::features::auth::domain::entities::Claims;
+
+#[post("/me/recovery-codes")]
+pub async fn regenerate_recovery_codes(
+    req: HttpRequest,
+    body: web::Json<RegenerateRecoveryCodesRequest>,
+    request_claims: ReqData<Claims>,
+    use_case: web::Data<RegenerateRecoveryCodesUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(request_claims.user_id, body.into_inner(), context)
+        .await
+    {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(e) => {
+            error!("Regenerate recovery codes error: {}", e);
+            match e {
+                crate::features::auth::domain::errors::AuthDomainError::InvalidCredentials => {
+                    HttpResponse::Unauthorized().json(GenericResponse {
+                        code: "INVALID_PASSWORD".to_string(),
+                        message: "Invalid password".to_string(),
+                    })
+                }
+                crate::features::auth::domain::errors::AuthDomainError::UserNotFound => {
+                    HttpResponse::NotFound().json(GenericResponse {
+                        code: "USER_NOT_FOUND".to_string(),
+                        message: "User not found".to_string(),
+                    })
+                }
+                _ => HttpResponse::InternalServerError().json(GenericResponse {
+                    code: "RECOVERY_CODES_REGENERATION_ERROR".to_string(),
+                    message: "Failed to regenerate recovery codes".to_string(),
+                }),
+            }
+        }
+    }
+}
+
+#[get("/me/recovery-codes")]
+pub async fn get_remaining_recovery_codes(
+    request_claims: ReqData<Claims>,
+    use_case: web::Data<GetRemainingRecoveryCodesUseCase>,
+) -> impl Responder {
+    match use_case.execute(request_claims.user_id).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Get remaining recovery codes error: {}", e);
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: "RECOVERY_CODES_ERROR".to_string(),
+                message: "Failed to get the remaining recovery codes".to_string(),
+            })
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use tracing::error;
 
+use crate::core::helpers::account_disabled::account_disabled_response;
+use crate::core::helpers::too_many_attempts::too_many_attempts_response;
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::application::dto::{
     RecoverAccountUsing2FARequest, RecoverAccountUsingPasswordRequest,
     RecoverAccountWithout2FAEnabledRequest,
@@ -11,7 +14,6 @@ use crate::features::auth::application::usecases::{
     RecoverAccountWithout2FAEnabledUseCase,
 };
 use crate::features::auth::domain::entities::DeviceInfo;
-use crate::features::profile::helpers::device_info::get_user_agent;
 use crate::features::profile::structs::models::ParsedDeviceInfo;
 
 fn parse_device_info_to_domain(parsed: ParsedDeviceInfo) -> DeviceInfo {
@@ -31,11 +33,17 @@ pub async fn recover_account_without_2fa_enabled(
     use_case: web::Data<RecoverAccountWithout2FAEnabledUseCase>,
 ) -> impl Responder {
     let body = body.into_inner();
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body, device_info).await {
+    match use_case.execute(body, device_info, context).await {
         Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Recover account without 2FA error: {}", e);
             let error_response = match e {
@@ -62,11 +70,17 @@ pub async fn recover_account_using_password(
     use_case: web::Data<RecoverAccountUsingPasswordUseCase>,
 ) -> impl Responder {
     let body = body.into_inner();
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body, device_info).await {
+    match use_case.execute(body, device_info, context).await {
         Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Recover account using password error: {}", e);
             let error_response = match e {
@@ -107,11 +121,17 @@ pub async fn recover_account_using_2fa(
     use_case: web::Data<RecoverAccountUsing2FAUseCase>,
 ) -> impl Responder {
     let body = body.into_inner();
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body, device_info).await {
+    match use_case.execute(body, device_info, context).await {
         Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::TooManyAttempts {
+            retry_after_seconds,
+        }) => too_many_attempts_response(retry_after_seconds),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Recover account using 2FA error: {}", e);
             let error_response = match e {
@@ -144,4 +164,3 @@ pub async fn recover_account_using_2fa(
         }
     }
 }
-
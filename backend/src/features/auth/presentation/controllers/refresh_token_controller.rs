@@ -1,11 +1,12 @@
 use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use tracing::error;
 
+use crate::core::helpers::account_disabled::account_disabled_response;
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::application::dto::RefreshTokenRequest;
 use crate::features::auth::application::usecases::RefreshTokenUseCase;
 use crate::features::auth::domain::entities::DeviceInfo;
-use crate::features::profile::helpers::device_info::get_user_agent;
 use crate::features::profile::structs::models::ParsedDeviceInfo;
 
 fn parse_device_info_to_domain(parsed: ParsedDeviceInfo) -> DeviceInfo {
@@ -24,11 +25,17 @@ pub async fn refresh_token(
     body: web::Json<RefreshTokenRequest>,
     use_case: web::Data<RefreshTokenUseCase>,
 ) -> impl Responder {
-    let parsed_device_info = get_user_agent(req).await;
-    let device_info = parse_device_info_to_domain(parsed_device_info);
+    let context = get_request_context(req).await;
+    let device_info = parse_device_info_to_domain(context.device_info.clone());
 
-    match use_case.execute(body.into_inner(), device_info).await {
+    match use_case
+        .execute(body.into_inner(), device_info, context)
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
+        Err(crate::features::auth::domain::errors::AuthDomainError::AccountDisabled) => {
+            account_disabled_response()
+        }
         Err(e) => {
             error!("Refresh token error: {}", e);
             let error_response = match e {
@@ -38,7 +45,8 @@ pub async fn refresh_token(
                         message: "Refresh token expired".to_string(),
                     }
                 }
-                crate::features::auth::domain::errors::AuthDomainError::InvalidToken => {
+                crate::features::auth::domain::errors::AuthDomainError::InvalidToken
+                | crate::features::auth::domain::errors::AuthDomainError::RefreshTokenReused => {
                     GenericResponse {
                         code: "INVALID_REFRESH_TOKEN".to_string(),
                         message: "Invalid refresh token".to_string(),
@@ -53,4 +61,3 @@ pub async fn refresh_token(
         }
     }
 }
-
@@ -0,0 +1,38 @@
+use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
+use crate::features::auth::application::dto::ReportSignInRequest;
+use crate::features::auth::application::usecases::ReportSignInUseCase;
+
+#[post("/sign-ins/report")]
+pub async fn report_sign_in(
+    req: HttpRequest,
+    body: web::Json<ReportSignInRequest>,
+    use_case: web::Data<ReportSignInUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(body.into_inner(), context).await {
+        Ok(_) => HttpResponse::Ok().json(GenericResponse {
+            code: "SIGN_IN_REPORTED".to_string(),
+            message: "The session was revoked, you should change your password".to_string(),
+        }),
+        Err(e) => {
+            error!("Report sign-in error: {}", e);
+            match e {
+                crate::features::auth::domain::errors::AuthDomainError::InvalidSignInReportLink => {
+                    HttpResponse::NotFound().json(GenericResponse {
+                        code: "INVALID_SIGN_IN_REPORT_LINK".to_string(),
+                        message: "This link is invalid or has expired".to_string(),
+                    })
+                }
+                _ => HttpResponse::InternalServerError().json(GenericResponse {
+                    code: "SIGN_IN_REPORT_ERROR".to_string(),
+                    message: "Failed to report the sign-in".to_string(),
+                }),
+            }
+        }
+    }
+}
@@ -1,29 +1,386 @@
-use std::{collections::HashMap, sync::Arc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
-use tokio::sync::RwLock;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
-#[derive(Default, Clone)]
+use crate::core::helpers::mock_now::now;
+
+pub const DEFAULT_TOKEN_CACHE_MAX_ENTRIES: usize = 100_000;
+pub const DEFAULT_LOCKOUT_CACHE_MAX_ENTRIES: usize = 100_000;
+
+/// Storage of the last activity of the access tokens validated recently. A cached token is
+/// accepted without checking that it was not revoked, so revoking a token has to remove it
+/// from every instance's cache.
+#[async_trait::async_trait]
+pub trait TokenCacheBackend: Send + Sync {
+    /// `expires_at` is the expiry of the token, the entry is evicted afterwards.
+    async fn update_or_insert_key(
+        &self,
+        key: Uuid,
+        value: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    );
+    async fn remove_key(&self, key: Uuid);
+    async fn get_value_for_key(&self, key: Uuid) -> Option<DateTime<Utc>>;
+    /// Evicts the entries of the tokens expired at `now`, returns the number evicted.
+    async fn remove_expired(&self, now: DateTime<Utc>) -> usize;
+    async fn metrics(&self) -> TokenCacheMetrics;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenCacheMetrics {
+    pub size: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl TokenCacheMetrics {
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+struct CachedToken {
+    last_activity: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    // Position in the recency order, the lowest is the least recently used
+    recency: u64,
+}
+
+#[derive(Default)]
+struct TokenCacheEntries {
+    tokens: HashMap<Uuid, CachedToken>,
+    by_recency: BTreeMap<u64, Uuid>,
+    next_recency: u64,
+}
+
+impl TokenCacheEntries {
+    fn touch(&mut self, key: Uuid) {
+        let recency = self.next_recency;
+        if let Some(token) = self.tokens.get_mut(&key) {
+            self.by_recency.remove(&token.recency);
+            self.by_recency.insert(recency, key);
+            token.recency = recency;
+            self.next_recency += 1;
+        }
+    }
+
+    fn remove(&mut self, key: Uuid) -> bool {
+        match self.tokens.remove(&key) {
+            Some(token) => {
+                self.by_recency.remove(&token.recency);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Cache local to the process, enough when a single instance is deployed. Holds at most
+/// `max_entries` tokens, the least recently used one being evicted to make room.
+pub struct InMemoryTokenCacheBackend {
+    max_entries: usize,
+    entries: Mutex<TokenCacheEntries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl Default for InMemoryTokenCacheBackend {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_CACHE_MAX_ENTRIES)
+    }
+}
+
+impl InMemoryTokenCacheBackend {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            entries: Mutex::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn clear(&self) {
+        let mut entries = self.entries.lock().await;
+        entries.tokens.clear();
+        entries.by_recency.clear();
+    }
+}
+
+#[async_trait::async_trait]
+impl TokenCacheBackend for InMemoryTokenCacheBackend {
+    async fn update_or_insert_key(
+        &self,
+        key: Uuid,
+        value: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) {
+        let mut entries = self.entries.lock().await;
+
+        if let Some(token) = entries.tokens.get_mut(&key) {
+            token.last_activity = value;
+            token.expires_at = expires_at;
+            entries.touch(key);
+            return;
+        }
+
+        while entries.tokens.len() >= self.max_entries {
+            let Some((_, least_recently_used)) = entries.by_recency.pop_first() else {
+                break;
+            };
+            entries.tokens.remove(&least_recently_used);
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let recency = entries.next_recency;
+        entries.next_recency += 1;
+        entries.by_recency.insert(recency, key);
+        entries.tokens.insert(
+            key,
+            CachedToken {
+                last_activity: value,
+                expires_at,
+                recency,
+            },
+        );
+    }
+
+    async fn remove_key(&self, key: Uuid) {
+        self.entries.lock().await.remove(key);
+    }
+
+    async fn get_value_for_key(&self, key: Uuid) -> Option<DateTime<Utc>> {
+        let mut entries = self.entries.lock().await;
+
+        let value = match entries.tokens.get(&key) {
+            Some(token) if token.expires_at <= now() => {
+                entries.remove(key);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            Some(token) => Some(token.last_activity),
+            None => None,
+        };
+
+        match value {
+            Some(_) => {
+                entries.touch(key);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        value
+    }
+
+    async fn remove_expired(&self, now: DateTime<Utc>) -> usize {
+        let mut entries = self.entries.lock().await;
+
+        let expired: Vec<Uuid> = entries
+            .tokens
+            .iter()
+            .filter(|(_, token)| token.expires_at <= now)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in &expired {
+            entries.remove(*key);
+        }
+
+        self.evictions
+            .fetch_add(expired.len() as u64, Ordering::Relaxed);
+        expired.len()
+    }
+
+    async fn metrics(&self) -> TokenCacheMetrics {
+        TokenCacheMetrics {
+            size: self.entries.lock().await.tokens.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct TokenCache {
-    data: Arc<RwLock<HashMap<Uuid, DateTime<Utc>>>>,
+    backend: Arc<dyn TokenCacheBackend>,
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new(Arc::new(InMemoryTokenCacheBackend::default()))
+    }
 }
 
 impl TokenCache {
-    pub async fn update_or_insert_key(&self, key: Uuid, value: DateTime<Utc>) {
-        self.data
-            .write()
-            .await
-            .entry(key)
-            .and_modify(|v| *v = value)
-            .or_insert(value);
+    pub fn new(backend: Arc<dyn TokenCacheBackend>) -> Self {
+        Self { backend }
+    }
+
+    pub async fn update_or_insert_key(
+        &self,
+        key: Uuid,
+        value: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) {
+        self.backend
+            .update_or_insert_key(key, value, expires_at)
+            .await;
     }
 
     pub async fn remove_key(&self, key: Uuid) {
-        self.data.write().await.remove(&key);
+        self.backend.remove_key(key).await;
     }
 
     pub async fn get_value_for_key(&self, key: Uuid) -> Option<DateTime<Utc>> {
-        self.data.read().await.get(&key).cloned()
+        self.backend.get_value_for_key(key).await
+    }
+
+    pub async fn remove_expired(&self) -> usize {
+        self.backend.remove_expired(now()).await
+    }
+
+    pub async fn metrics(&self) -> TokenCacheMetrics {
+        self.backend.metrics().await
+    }
+
+    /// Evicts the expired tokens every `interval`, and reports the cache metrics.
+    pub fn spawn_sweeper(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+                let evicted = self.remove_expired().await;
+                let metrics = self.metrics().await;
+                tracing::info!(
+                    evicted,
+                    size = metrics.size,
+                    hits = metrics.hits,
+                    misses = metrics.misses,
+                    evictions = metrics.evictions,
+                    hit_rate = metrics.hit_rate(),
+                    "Swept the token cache"
+                );
+            }
+        })
+    }
+}
+
+#[derive(Default)]
+struct Lockouts {
+    locked_until: HashMap<String, DateTime<Utc>>,
+    by_end: BTreeSet<(DateTime<Utc>, String)>,
+}
+
+impl Lockouts {
+    fn remove(&mut self, key: &str) {
+        if let Some(locked_until) = self.locked_until.remove(key) {
+            self.by_end.remove(&(locked_until, key.to_string()));
+        }
+    }
+}
+
+/// In-memory copy of the active lockouts, so locked out subjects are rejected without
+/// hitting the database. The `login_attempts` table remains the source of truth, so when
+/// `max_entries` lockouts are cached, the one ending first is dropped to make room.
+#[derive(Clone)]
+pub struct LockoutCache {
+    max_entries: usize,
+    data: Arc<Mutex<Lockouts>>,
+}
+
+impl Default for LockoutCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCKOUT_CACHE_MAX_ENTRIES)
+    }
+}
+
+impl LockoutCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            data: Arc::default(),
+        }
+    }
+
+    pub async fn insert(&self, key: String, locked_until: DateTime<Utc>) {
+        let mut data = self.data.lock().await;
+
+        data.remove(&key);
+        while data.locked_until.len() >= self.max_entries {
+            let Some((_, ending_first)) = data.by_end.pop_first() else {
+                break;
+            };
+            data.locked_until.remove(&ending_first);
+        }
+
+        data.by_end.insert((locked_until, key.clone()));
+        data.locked_until.insert(key, locked_until);
+    }
+
+    pub async fn remove(&self, key: &str) {
+        self.data.lock().await.remove(key);
+    }
+
+    /// The end of the lockout of `key`, if it is still locked out at `now`. An ended lockout
+    /// is removed.
+    pub async fn get_locked_until(&self, key: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut data = self.data.lock().await;
+        let locked_until = *data.locked_until.get(key)?;
+
+        if locked_until > now {
+            return Some(locked_until);
+        }
+        data.remove(key);
+
+        None
+    }
+
+    /// Removes the lockouts ended at `now`, returns the number removed.
+    pub async fn remove_expired(&self, now: DateTime<Utc>) -> usize {
+        let mut data = self.data.lock().await;
+        let mut removed = 0;
+
+        while let Some((locked_until, key)) = data.by_end.first().cloned() {
+            if locked_until > now {
+                break;
+            }
+            data.remove(&key);
+            removed += 1;
+        }
+
+        removed
+    }
+
+    pub async fn size(&self) -> usize {
+        self.data.lock().await.locked_until.len()
+    }
+
+    /// Removes the ended lockouts every `interval`.
+    pub fn spawn_sweeper(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+
+            loop {
+                interval.tick().await;
+                let removed = self.remove_expired(now()).await;
+                tracing::debug!(removed, "Swept the lockout cache");
+            }
+        })
     }
 }
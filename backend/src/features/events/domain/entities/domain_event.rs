@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Something that happened to an account, published for other parts of the system to react
+/// to once the change that caused it is committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DomainEvent {
+    UserSignedUp { user_id: Uuid },
+    PasswordChanged { user_id: Uuid },
+    OtpEnabled { user_id: Uuid },
+    OtpDisabled { user_id: Uuid },
+    // Recovered without the password or the second factor, which were reset along the way
+    AccountRecovered { user_id: Uuid },
+    SessionRevoked { user_id: Uuid, token_id: Uuid },
+}
+
+impl DomainEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::UserSignedUp { .. } => "USER_SIGNED_UP",
+            DomainEvent::PasswordChanged { .. } => "PASSWORD_CHANGED",
+            DomainEvent::OtpEnabled { .. } => "OTP_ENABLED",
+            DomainEvent::OtpDisabled { .. } => "OTP_DISABLED",
+            DomainEvent::AccountRecovered { .. } => "ACCOUNT_RECOVERED",
+            DomainEvent::SessionRevoked { .. } => "SESSION_REVOKED",
+        }
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        match self {
+            DomainEvent::UserSignedUp { user_id }
+            | DomainEvent::PasswordChanged { user_id }
+            | DomainEvent::OtpEnabled { user_id }
+            | DomainEvent::OtpDisabled { user_id }
+            | DomainEvent::AccountRecovered { user_id }
+            | DomainEvent::SessionRevoked { user_id, .. } => *user_id,
+        }
+    }
+
+    /// One event per revoked session, since their ids are what consumers key on.
+    pub fn sessions_revoked(user_id: Uuid, token_ids: &[Uuid]) -> Vec<DomainEvent> {
+        token_ids
+            .iter()
+            .map(|token_id| DomainEvent::SessionRevoked {
+                user_id,
+                token_id: *token_id,
+            })
+            .collect()
+    }
+}
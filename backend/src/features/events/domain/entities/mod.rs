@@ -0,0 +1,5 @@
+pub mod domain_event;
+pub mod outbox_message;
+
+pub use domain_event::DomainEvent;
+pub use outbox_message::OutboxMessage;
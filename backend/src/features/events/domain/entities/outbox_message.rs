@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::events::domain::entities::DomainEvent;
+
+/// An event waiting in the outbox until every handler has processed it.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub event: DomainEvent,
+    // Deliveries started so far, including the one in progress
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OutboxMessage {
+    pub fn new(event: DomainEvent) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event,
+            attempts: 0,
+            created_at: now(),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum EventsDomainError {
+    #[error("Handler {handler} failed: {message}")]
+    HandlerFailed {
+        handler: &'static str,
+        message: String,
+    },
+
+    #[error("Serialization error")]
+    SerializationError,
+
+    #[error("Database error")]
+    DatabaseError,
+}
@@ -0,0 +1,11 @@
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::errors::EventsDomainError;
+
+/// Reacts to the events dispatched from the outbox. An event is delivered again after any
+/// handler fails on it, so handling the same event twice must be harmless.
+#[async_trait::async_trait]
+pub trait EventHandler: Send + Sync {
+    // Identifies the handler in the logs and in the errors stored in the outbox
+    fn name(&self) -> &'static str;
+    async fn handle(&self, event: &DomainEvent) -> Result<(), EventsDomainError>;
+}
@@ -0,0 +1,5 @@
+pub mod event_handler;
+pub mod outbox_repository;
+
+pub use event_handler::EventHandler;
+pub use outbox_repository::OutboxRepository;
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::events::domain::entities::OutboxMessage;
+use crate::features::events::domain::errors::EventsDomainError;
+
+#[async_trait::async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Joins the transaction of the caller, so the messages are only stored along with the
+    /// change they describe.
+    async fn save(&self, messages: &[OutboxMessage]) -> Result<(), EventsDomainError>;
+    /// Takes up to `limit` messages due at `now`, oldest first, and hides them from the other
+    /// dispatchers until `leased_until` in case this one stops before settling them. The dead
+    /// messages are never taken.
+    async fn lease_pending(
+        &self,
+        now: DateTime<Utc>,
+        leased_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxMessage>, EventsDomainError>;
+    async fn mark_as_delivered(
+        &self,
+        id: Uuid,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError>;
+    async fn mark_as_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError>;
+    async fn mark_as_dead(
+        &self,
+        id: Uuid,
+        error: &str,
+        dead_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError>;
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::errors::EventsDomainError;
+use crate::features::events::domain::repositories::{EventHandler, OutboxRepository};
+
+#[derive(Debug, Clone)]
+pub struct DispatchSettings {
+    pub batch_size: i64,
+    // How long a batch is hidden from the other dispatchers while it is being delivered
+    pub lease: Duration,
+    // Grows with each failed attempt, up to `max_retry_delay`
+    pub retry_delay: Duration,
+    pub max_retry_delay: Duration,
+    // An event still failing after this many attempts is marked as dead and no longer retried
+    pub max_attempts: i32,
+}
+
+/// Delivers the events of the outbox to every registered handler, at least once.
+pub struct EventDispatcher {
+    outbox_repository: Box<dyn OutboxRepository>,
+    handlers: Vec<Arc<dyn EventHandler>>,
+    settings: DispatchSettings,
+}
+
+impl EventDispatcher {
+    pub fn new(outbox_repository: Box<dyn OutboxRepository>, settings: DispatchSettings) -> Self {
+        Self {
+            outbox_repository,
+            handlers: Vec::new(),
+            settings,
+        }
+    }
+
+    pub fn with_handler(mut self, handler: Arc<dyn EventHandler>) -> Self {
+        self.handlers.push(handler);
+        self
+    }
+
+    /// Delivers a batch of the events that are due, and returns how many were delivered. An
+    /// event that a handler fails on is retried later, including by the handlers that already
+    /// succeeded, until it runs out of attempts.
+    pub async fn dispatch_pending(&self) -> Result<usize, EventsDomainError> {
+        let now_time = now();
+        let messages = self
+            .outbox_repository
+            .lease_pending(
+                now_time,
+                now_time + self.settings.lease,
+                self.settings.batch_size.max(1),
+            )
+            .await?;
+        let mut delivered = 0;
+
+        for message in messages {
+            match self.deliver(&message.event).await {
+                Ok(()) => {
+                    self.outbox_repository
+                        .mark_as_delivered(message.id, now())
+                        .await?;
+                    delivered += 1;
+                }
+                Err(e) if message.attempts >= self.settings.max_attempts.max(1) => {
+                    tracing::error!(
+                        "Gave up delivering the event {} of user {} after {} attempts: {}",
+                        message.event.name(),
+                        message.event.user_id(),
+                        message.attempts,
+                        e
+                    );
+                    self.outbox_repository
+                        .mark_as_dead(message.id, &e.to_string(), now())
+                        .await?;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to deliver the event {} of user {} (attempt {}): {}",
+                        message.event.name(),
+                        message.event.user_id(),
+                        message.attempts,
+                        e
+                    );
+                    let retry_delay = (self.settings.retry_delay * message.attempts.max(1))
+                        .min(self.settings.max_retry_delay);
+                    let retry_at = now() + retry_delay;
+                    self.outbox_repository
+                        .mark_as_failed(message.id, &e.to_string(), retry_at)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    async fn deliver(&self, event: &DomainEvent) -> Result<(), EventsDomainError> {
+        for handler in &self.handlers {
+            handler.handle(event).await.map_err(|e| match e {
+                EventsDomainError::HandlerFailed { .. } => e,
+                e => EventsDomainError::HandlerFailed {
+                    handler: handler.name(),
+                    message: e.to_string(),
+                },
+            })?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod event_dispatcher;
+pub mod outbox;
+
+pub use event_dispatcher::{DispatchSettings, EventDispatcher};
+pub use outbox::Outbox;
@@ -0,0 +1,25 @@
+use crate::features::events::domain::entities::{DomainEvent, OutboxMessage};
+use crate::features::events::domain::errors::EventsDomainError;
+use crate::features::events::domain::repositories::OutboxRepository;
+
+/// Publishes the events of the auth, profile and admin use cases.
+pub struct Outbox {
+    outbox_repository: Box<dyn OutboxRepository>,
+}
+
+impl Outbox {
+    pub fn new(outbox_repository: Box<dyn OutboxRepository>) -> Self {
+        Self { outbox_repository }
+    }
+
+    /// Meant to be called in the transaction of the change the events describe: they are then
+    /// dispatched if, and only if, it is committed.
+    pub async fn publish(&self, events: Vec<DomainEvent>) -> Result<(), EventsDomainError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let messages: Vec<OutboxMessage> = events.into_iter().map(OutboxMessage::new).collect();
+        self.outbox_repository.save(&messages).await
+    }
+}
@@ -0,0 +1,20 @@
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::errors::EventsDomainError;
+use crate::features::events::domain::repositories::EventHandler;
+
+/// Writes each event to the logs, until the consumers that need them are registered.
+#[derive(Clone, Default)]
+pub struct LogEventHandler;
+
+#[async_trait::async_trait]
+impl EventHandler for LogEventHandler {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), EventsDomainError> {
+        tracing::info!("Domain event {} of user {}", event.name(), event.user_id());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod outbox_message;
+
+pub use outbox_message::OutboxMessageModel;
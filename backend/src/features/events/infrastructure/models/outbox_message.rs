@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::features::events::domain::entities::OutboxMessage;
+
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct OutboxMessageModel {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<OutboxMessageModel> for OutboxMessage {
+    type Error = String;
+
+    fn try_from(model: OutboxMessageModel) -> Result<Self, Self::Error> {
+        let event = serde_json::from_str(&model.payload)
+            .map_err(|e| format!("Unknown event {} ({}): {}", model.event_type, model.id, e))?;
+
+        Ok(Self {
+            id: model.id,
+            event,
+            attempts: model.attempts,
+            created_at: model.created_at,
+        })
+    }
+}
+
+impl TryFrom<&OutboxMessage> for OutboxMessageModel {
+    type Error = serde_json::Error;
+
+    fn try_from(message: &OutboxMessage) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: message.id,
+            event_type: message.event.name().to_string(),
+            payload: serde_json::to_string(&message.event)?,
+            attempts: message.attempts,
+            created_at: message.created_at,
+        })
+    }
+}
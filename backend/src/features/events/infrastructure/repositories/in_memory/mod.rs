@@ -0,0 +1,3 @@
+pub mod outbox_repository;
+
+pub use outbox_repository::InMemoryOutboxRepository;
@@ -0,0 +1,136 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, OutboxEventRow, Tables};
+use crate::features::events::domain::entities::OutboxMessage;
+use crate::features::events::domain::errors::EventsDomainError;
+use crate::features::events::domain::repositories::OutboxRepository;
+
+#[derive(Clone)]
+pub struct InMemoryOutboxRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self) -> Result<MutexGuard<'_, Tables>, EventsDomainError> {
+        self.database.write("outbox_events").map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxRepository for InMemoryOutboxRepository {
+    async fn save(&self, messages: &[OutboxMessage]) -> Result<(), EventsDomainError> {
+        let mut tables = self.write()?;
+
+        tables
+            .outbox_events
+            .extend(messages.iter().map(|message| OutboxEventRow {
+                message: message.clone(),
+                last_error: None,
+                available_at: message.created_at,
+                delivered_at: None,
+                dead_at: None,
+            }));
+
+        Ok(())
+    }
+
+    async fn lease_pending(
+        &self,
+        now: DateTime<Utc>,
+        leased_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxMessage>, EventsDomainError> {
+        let mut tables = self.write()?;
+
+        let mut pending: Vec<&mut OutboxEventRow> = tables
+            .outbox_events
+            .iter_mut()
+            .filter(|row| {
+                row.delivered_at.is_none() && row.dead_at.is_none() && row.available_at <= now
+            })
+            .collect();
+        pending.sort_by_key(|row| (row.available_at, row.message.created_at));
+
+        let mut messages: Vec<OutboxMessage> = pending
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|row| {
+                row.message.attempts += 1;
+                row.available_at = leased_until;
+                row.message.clone()
+            })
+            .collect();
+        messages.sort_by_key(|message| message.created_at);
+
+        Ok(messages)
+    }
+
+    async fn mark_as_delivered(
+        &self,
+        id: Uuid,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        let mut tables = self.write()?;
+
+        if let Some(row) = tables
+            .outbox_events
+            .iter_mut()
+            .find(|row| row.message.id == id)
+        {
+            row.delivered_at = Some(delivered_at);
+            row.last_error = None;
+        }
+
+        Ok(())
+    }
+
+    async fn mark_as_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        let mut tables = self.write()?;
+
+        if let Some(row) = tables
+            .outbox_events
+            .iter_mut()
+            .find(|row| row.message.id == id)
+        {
+            row.last_error = Some(error.to_string());
+            row.available_at = retry_at;
+        }
+
+        Ok(())
+    }
+
+    async fn mark_as_dead(
+        &self,
+        id: Uuid,
+        error: &str,
+        dead_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        let mut tables = self.write()?;
+
+        if let Some(row) = tables
+            .outbox_events
+            .iter_mut()
+            .find(|row| row.message.id == id)
+        {
+            row.last_error = Some(error.to_string());
+            row.dead_at = Some(dead_at);
+        }
+
+        Ok(())
+    }
+}
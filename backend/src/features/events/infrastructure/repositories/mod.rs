@@ -0,0 +1,6 @@
+pub mod outbox_repository_impl;
+
+pub use outbox_repository_impl::OutboxRepositoryImpl;
+
+#[cfg(feature = "test-util")]
+pub mod in_memory;
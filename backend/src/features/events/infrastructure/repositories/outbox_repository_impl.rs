@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::transaction;
+use crate::features::events::domain::entities::OutboxMessage;
+use crate::features::events::domain::errors::EventsDomainError;
+use crate::features::events::domain::repositories::OutboxRepository;
+use crate::features::events::infrastructure::models::OutboxMessageModel;
+
+#[derive(Clone)]
+pub struct OutboxRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl OutboxRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxRepository for OutboxRepositoryImpl {
+    async fn save(&self, messages: &[OutboxMessage]) -> Result<(), EventsDomainError> {
+        let mut conn = transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })?;
+
+        for message in messages {
+            let model = OutboxMessageModel::try_from(message).map_err(|e| {
+                tracing::error!("Serialization error: {}", e);
+                EventsDomainError::SerializationError
+            })?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO outbox_events (id, event_type, payload, attempts, available_at,
+                    created_at)
+                VALUES ($1, $2, $3, $4, $5, $5)
+                "#,
+                model.id,
+                model.event_type,
+                model.payload,
+                model.attempts,
+                model.created_at,
+            )
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Database error: {}", e);
+                EventsDomainError::DatabaseError
+            })?;
+        }
+
+        Ok(())
+    }
+
+    async fn lease_pending(
+        &self,
+        now: DateTime<Utc>,
+        leased_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<OutboxMessage>, EventsDomainError> {
+        let models = sqlx::query_as!(
+            OutboxMessageModel,
+            r#"
+            UPDATE outbox_events
+            SET attempts = attempts + 1, available_at = $2
+            WHERE id IN (
+                SELECT id
+                FROM outbox_events
+                WHERE delivered_at IS NULL AND dead_at IS NULL AND available_at <= $1
+                ORDER BY available_at, created_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, event_type, payload, attempts, created_at
+            "#,
+            now,
+            leased_until,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })?;
+
+        // Events of a type this version doesn't know, written by a newer one, are left for it
+        let mut messages: Vec<OutboxMessage> = models
+            .into_iter()
+            .filter_map(|model| {
+                OutboxMessage::try_from(model)
+                    .map_err(|e| tracing::warn!("{}", e))
+                    .ok()
+            })
+            .collect();
+        messages.sort_by_key(|message| message.created_at);
+
+        Ok(messages)
+    }
+
+    async fn mark_as_delivered(
+        &self,
+        id: Uuid,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET delivered_at = $2, last_error = NULL
+            WHERE id = $1
+            "#,
+            id,
+            delivered_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn mark_as_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET last_error = $2, available_at = $3
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn mark_as_dead(
+        &self,
+        id: Uuid,
+        error: &str,
+        dead_at: DateTime<Utc>,
+    ) -> Result<(), EventsDomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE outbox_events
+            SET last_error = $2, dead_at = $3
+            WHERE id = $1
+            "#,
+            id,
+            error,
+            dead_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            EventsDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+
+use crate::features::events::domain::services::EventDispatcher;
+
+/// Dispatches the pending events every `interval`, starting right away.
+pub fn spawn_event_dispatcher(
+    dispatcher: Arc<EventDispatcher>,
+    interval: std::time::Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+
+        loop {
+            interval.tick().await;
+            // Keeps going while events are delivered, so a backlog isn't left for the next tick
+            loop {
+                match dispatcher.dispatch_pending().await {
+                    Ok(0) => break,
+                    Ok(delivered) => tracing::debug!("Delivered {} events", delivered),
+                    Err(e) => {
+                        tracing::error!("Event dispatch error: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,3 @@
+pub mod maintenance_response;
+
+pub use maintenance_response::MaintenanceResponse;
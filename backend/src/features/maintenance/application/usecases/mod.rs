@@ -0,0 +1,3 @@
+pub mod run_maintenance_use_case;
+
+pub use run_maintenance_use_case::{PurgeSettings, RunMaintenanceUseCase};
@@ -0,0 +1,111 @@
+use chrono::Duration;
+use futures_util::future::BoxFuture;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::maintenance::application::dto::MaintenanceResponse;
+use crate::features::maintenance::domain::entities::MaintenanceReport;
+use crate::features::maintenance::domain::errors::MaintenanceDomainError;
+use crate::features::maintenance::domain::repositories::MaintenanceRepository;
+
+#[derive(Debug, Clone)]
+pub struct PurgeSettings {
+    pub batch_size: i64,
+    // Failed login attempts are kept as long as they count towards a lockout
+    pub login_attempt_window: Duration,
+}
+
+/// Purges the data that is no longer used: expired sessions, MFA challenges and data exports,
+/// failed login attempts that can't lead to a lockout anymore, accounts whose deletion is due,
+/// and outbox events already delivered.
+pub struct RunMaintenanceUseCase {
+    maintenance_repository: Box<dyn MaintenanceRepository>,
+    settings: PurgeSettings,
+}
+
+impl RunMaintenanceUseCase {
+    pub fn new(
+        maintenance_repository: Box<dyn MaintenanceRepository>,
+        settings: PurgeSettings,
+    ) -> Self {
+        Self {
+            maintenance_repository,
+            settings,
+        }
+    }
+
+    pub async fn execute(&self) -> Result<MaintenanceResponse, MaintenanceDomainError> {
+        let report = self.run().await?;
+
+        Ok(MaintenanceResponse {
+            code: "MAINTENANCE_COMPLETED".to_string(),
+            expired_tokens: report.expired_tokens,
+            expired_mfa_challenges: report.expired_mfa_challenges,
+            expired_data_exports: report.expired_data_exports,
+            stale_login_attempts: report.stale_login_attempts,
+            deleted_accounts: report.deleted_accounts,
+            delivered_events: report.delivered_events,
+        })
+    }
+
+    pub async fn run(&self) -> Result<MaintenanceReport, MaintenanceDomainError> {
+        let now_time = now();
+        let batch_size = self.settings.batch_size.max(1);
+        let repository = &self.maintenance_repository;
+
+        let expired_tokens = purge_in_batches(batch_size, || {
+            repository.delete_expired_tokens(now_time, batch_size)
+        })
+        .await?;
+        let expired_mfa_challenges = purge_in_batches(batch_size, || {
+            repository.delete_expired_mfa_challenges(now_time, batch_size)
+        })
+        .await?;
+        let expired_data_exports = purge_in_batches(batch_size, || {
+            repository.delete_expired_data_exports(now_time, batch_size)
+        })
+        .await?;
+        let window_started_before = now_time - self.settings.login_attempt_window;
+        let stale_login_attempts = purge_in_batches(batch_size, || {
+            repository.delete_stale_login_attempts(now_time, window_started_before, batch_size)
+        })
+        .await?;
+
+        let deleted_accounts = purge_in_batches(batch_size, || {
+            repository.delete_accounts_due(now_time, batch_size)
+        })
+        .await?;
+        let delivered_events = purge_in_batches(batch_size, || {
+            repository.delete_delivered_events(now_time, batch_size)
+        })
+        .await?;
+
+        Ok(MaintenanceReport {
+            expired_tokens,
+            expired_mfa_challenges,
+            expired_data_exports,
+            stale_login_attempts,
+            deleted_accounts,
+            delivered_events,
+        })
+    }
+}
+
+// Deletes batches until one isn't full
+async fn purge_in_batches<'a, F>(
+    batch_size: i64,
+    mut delete_batch: F,
+) -> Result<u64, MaintenanceDomainError>
+where
+    F: FnMut() -> BoxFuture<'a, Result<u64, MaintenanceDomainError>>,
+{
+    let mut deleted = 0;
+
+    loop {
+        let deleted_in_batch = delete_batch().await?;
+        deleted += deleted_in_batch;
+
+        if deleted_in_batch < batch_size as u64 {
+            return Ok(deleted);
+        }
+    }
+}
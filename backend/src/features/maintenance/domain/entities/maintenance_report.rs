@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Number of rows purged by each step of a maintenance run.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub expired_tokens: u64,
+    pub expired_mfa_challenges: u64,
+    pub expired_data_exports: u64,
+    pub stale_login_attempts: u64,
+    pub deleted_accounts: u64,
+    pub delivered_events: u64,
+}
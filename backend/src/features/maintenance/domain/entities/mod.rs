@@ -0,0 +1,3 @@
+pub mod maintenance_report;
+
+pub use maintenance_report::MaintenanceReport;
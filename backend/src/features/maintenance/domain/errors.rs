@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MaintenanceDomainError {
+    #[error("Database error")]
+    DatabaseError,
+}
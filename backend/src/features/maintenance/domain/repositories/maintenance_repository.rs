@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::maintenance::domain::errors::MaintenanceDomainError;
+
+/// Each method deletes at most `limit` rows and returns the number deleted, so large purges
+/// are split in short transactions.
+#[async_trait::async_trait]
+pub trait MaintenanceRepository: Send + Sync {
+    /// Sessions whose refresh token expired, including their rotated tokens.
+    async fn delete_expired_tokens(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+    async fn delete_expired_mfa_challenges(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+    /// Data exports that can't be downloaded anymore, along with their archive.
+    async fn delete_expired_data_exports(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+    /// Failed attempts that are neither locking a subject out nor counted in a window started
+    /// after `window_started_before`.
+    async fn delete_stale_login_attempts(
+        &self,
+        now: DateTime<Utc>,
+        window_started_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+    /// Accounts whose deletion was scheduled before `now`, along with all their data.
+    async fn delete_accounts_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+    /// Outbox events that every handler has processed.
+    async fn delete_delivered_events(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError>;
+}
@@ -0,0 +1,3 @@
+pub mod maintenance_repository;
+
+pub use maintenance_repository::MaintenanceRepository;
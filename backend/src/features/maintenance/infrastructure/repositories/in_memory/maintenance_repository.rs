@@ -0,0 +1,143 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::maintenance::domain::errors::MaintenanceDomainError;
+use crate::features::maintenance::domain::repositories::MaintenanceRepository;
+
+#[derive(Clone)]
+pub struct InMemoryMaintenanceRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryMaintenanceRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, MaintenanceDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })
+    }
+}
+
+// Deletes at most `limit` of the rows matching `predicate`, and returns the number deleted
+fn delete_up_to<T>(rows: &mut Vec<T>, limit: i64, predicate: impl Fn(&T) -> bool) -> u64 {
+    let mut remaining = limit.max(0);
+    let count = rows.len();
+
+    rows.retain(|row| {
+        if remaining > 0 && predicate(row) {
+            remaining -= 1;
+            false
+        } else {
+            true
+        }
+    });
+
+    (count - rows.len()) as u64
+}
+
+#[async_trait::async_trait]
+impl MaintenanceRepository for InMemoryMaintenanceRepository {
+    async fn delete_expired_tokens(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        Ok(delete_up_to(&mut tables.user_tokens, limit, |token| {
+            token.expires_at <= now
+        }))
+    }
+
+    async fn delete_expired_mfa_challenges(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("mfa_challenges")?;
+
+        Ok(delete_up_to(
+            &mut tables.mfa_challenges,
+            limit,
+            |challenge| challenge.expires_at <= now,
+        ))
+    }
+
+    async fn delete_expired_data_exports(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("data_exports")?;
+
+        Ok(delete_up_to(&mut tables.data_exports, limit, |row| {
+            row.export.expires_at <= now
+        }))
+    }
+
+    async fn delete_stale_login_attempts(
+        &self,
+        now: DateTime<Utc>,
+        window_started_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("login_attempts")?;
+
+        Ok(delete_up_to(
+            &mut tables.login_attempts,
+            limit,
+            |attempts| {
+                attempts.window_started_at <= window_started_before
+                    && attempts
+                        .locked_until
+                        .is_none_or(|locked_until| locked_until <= now)
+            },
+        ))
+    }
+
+    async fn delete_accounts_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("users")?;
+
+        let due: Vec<Uuid> = tables
+            .users
+            .iter()
+            .filter(|user| {
+                user.deletion_scheduled_at
+                    .is_some_and(|deletion_scheduled_at| deletion_scheduled_at <= now)
+            })
+            .take(limit.max(0) as usize)
+            .map(|user| user.id)
+            .collect();
+
+        // Sessions, recovery codes and the other data of the user are deleted in cascade
+        for user_id in &due {
+            tables.delete_user(*user_id);
+        }
+
+        Ok(due.len() as u64)
+    }
+
+    async fn delete_delivered_events(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let mut tables = self.write("outbox_events")?;
+
+        Ok(delete_up_to(&mut tables.outbox_events, limit, |row| {
+            row.delivered_at
+                .is_some_and(|delivered_at| delivered_at <= now)
+        }))
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::maintenance::domain::errors::MaintenanceDomainError;
+use crate::features::maintenance::domain::repositories::MaintenanceRepository;
+
+#[derive(Clone)]
+pub struct MaintenanceRepositoryImpl {
+    pool: sqlx::PgPool,
+}
+
+impl MaintenanceRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl MaintenanceRepository for MaintenanceRepositoryImpl {
+    async fn delete_expired_tokens(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM user_tokens
+            WHERE id IN (
+                SELECT id
+                FROM user_tokens
+                WHERE expires_at <= $1
+                LIMIT $2
+            )
+            "#,
+            now,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired_mfa_challenges(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM mfa_challenges
+            WHERE id IN (
+                SELECT id
+                FROM mfa_challenges
+                WHERE expires_at <= $1
+                LIMIT $2
+            )
+            "#,
+            now,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired_data_exports(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM data_exports
+            WHERE id IN (
+                SELECT id
+                FROM data_exports
+                WHERE expires_at <= $1
+                LIMIT $2
+            )
+            "#,
+            now,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_stale_login_attempts(
+        &self,
+        now: DateTime<Utc>,
+        window_started_before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM login_attempts
+            WHERE (scope, key) IN (
+                SELECT scope, key
+                FROM login_attempts
+                WHERE window_started_at <= $2
+                    AND (locked_until IS NULL OR locked_until <= $1)
+                LIMIT $3
+            )
+            "#,
+            now,
+            window_started_before,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_accounts_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        // Sessions, recovery codes and the other data of the user are deleted in cascade
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM users
+            WHERE id IN (
+                SELECT id
+                FROM users
+                WHERE deletion_scheduled_at <= $1
+                LIMIT $2
+            )
+            "#,
+            now,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_delivered_events(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, MaintenanceDomainError> {
+        let result = sqlx::query!(
+            r#"
+            DELETE
+            FROM outbox_events
+            WHERE id IN (
+                SELECT id
+                FROM outbox_events
+                WHERE delivered_at <= $1
+                LIMIT $2
+            )
+            "#,
+            now,
+            limit
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            MaintenanceDomainError::DatabaseError
+        })?;
+
+        Ok(result.rows_affected())
+    }
+}
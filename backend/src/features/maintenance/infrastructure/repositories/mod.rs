@@ -0,0 +1,6 @@
+pub mod maintenance_repository_impl;
+
+pub use maintenance_repository_impl::MaintenanceRepositoryImpl;
+
+#[cfg(feature = "test-util")]
+pub mod in_memory;
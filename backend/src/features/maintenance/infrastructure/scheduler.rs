@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use tokio::task::JoinHandle;
+
+use crate::features::maintenance::application::usecases::RunMaintenanceUseCase;
+
+/// Runs the maintenance every `interval`, starting right away.
+pub fn spawn_maintenance(
+    use_case: Arc<RunMaintenanceUseCase>,
+    interval: std::time::Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(interval);
+
+        loop {
+            interval.tick().await;
+            match use_case.run().await {
+                Ok(report) => tracing::info!(
+                    expired_tokens = report.expired_tokens,
+                    expired_mfa_challenges = report.expired_mfa_challenges,
+                    expired_data_exports = report.expired_data_exports,
+                    stale_login_attempts = report.stale_login_attempts,
+                    deleted_accounts = report.deleted_accounts,
+                    delivered_events = report.delivered_events,
+                    "Ran the maintenance"
+                ),
+                Err(e) => tracing::error!("Maintenance error: {}", e),
+            }
+        }
+    })
+}
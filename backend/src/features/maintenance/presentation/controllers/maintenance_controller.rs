@@ -0,0 +1,24 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::middlewares::permission_guard::PermissionGuard;
+use crate::core::structs::responses::GenericResponse;
+use crate::features::auth::domain::entities::Permission;
+use crate::features::maintenance::application::usecases::RunMaintenanceUseCase;
+
+#[post(
+    "/maintenance/run",
+    wrap = "PermissionGuard::new(Permission::RunMaintenance)"
+)]
+pub async fn run_maintenance(use_case: web::Data<RunMaintenanceUseCase>) -> impl Responder {
+    match use_case.execute().await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Maintenance error: {}", e);
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: "MAINTENANCE_ERROR".to_string(),
+                message: "Failed to run the maintenance".to_string(),
+            })
+        }
+    }
+}
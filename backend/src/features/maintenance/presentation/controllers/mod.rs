@@ -0,0 +1,3 @@
+pub mod maintenance_controller;
+
+pub use maintenance_controller::run_maintenance;
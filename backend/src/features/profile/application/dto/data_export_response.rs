@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::features::audit::application::dto::SecurityEventData;
+use crate::features::profile::application::dto::DeviceData;
+use crate::features::profile::domain::entities::User;
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct DataExportResponse {
+    pub code: String,
+    // Only set once the archive is ready. Posted to the download route, it can be used once
+    pub download_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Debug, Deserialize)]
+pub struct PreferencesData {
+    pub locale: String,
+    pub theme: String,
+}
+
+/// The account of the user, without the 2FA secret: anyone getting hold of the archive could
+/// generate codes with it.
+#[derive(Serialize, Debug, Deserialize)]
+pub struct ProfileData {
+    pub id: Uuid,
+    pub username: String,
+    pub otp_verified: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+    pub password_is_expired: bool,
+}
+
+impl From<User> for ProfileData {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            otp_verified: user.otp_verified,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            password_is_expired: user.password_is_expired,
+        }
+    }
+}
+
+/// Everything the user can download about themselves.
+#[derive(Serialize, Debug, Deserialize)]
+pub struct DataArchive {
+    pub exported_at: DateTime<Utc>,
+    pub profile: ProfileData,
+    pub preferences: PreferencesData,
+    pub devices: Vec<DeviceData>,
+    pub security_events: Vec<SecurityEventData>,
+}
@@ -1,12 +1,15 @@
+pub mod data_export_request;
+pub mod data_export_response;
 pub mod is_otp_enabled_request;
 pub mod is_otp_enabled_response;
 pub mod profile_request;
 pub mod profile_response;
 
+pub use data_export_request::DownloadDataExportRequest;
+pub use data_export_response::{DataArchive, DataExportResponse, PreferencesData, ProfileData};
 pub use is_otp_enabled_request::IsOtpEnabledRequest;
 pub use is_otp_enabled_response::IsOtpEnabledResponse;
 pub use profile_request::{SetPasswordRequest, UpdatePasswordRequest, UpdateProfileRequest};
 pub use profile_response::{
     DeviceData, DeviceDeleteResponse, DeviceInfo, DevicesResponse, ProfileResponse, UserData,
 };
-
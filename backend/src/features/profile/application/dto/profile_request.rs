@@ -17,4 +17,3 @@ pub struct UpdatePasswordRequest {
     pub current_password: String,
     pub new_password: String,
 }
-
@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::features::profile::domain::entities::User;
+
 #[derive(Serialize, Debug, Deserialize)]
 pub struct UserData {
     pub id: Uuid,
@@ -18,6 +20,30 @@ pub struct UserData {
     pub password_is_expired: bool,
 }
 
+impl From<User> for UserData {
+    fn from(user: User) -> Self {
+        // The secret is only shared while 2FA is being set up
+        let (otp_base32, otp_auth_url) = if user.otp_verified {
+            (None, None)
+        } else {
+            (user.otp_base32, user.otp_auth_url)
+        };
+
+        Self {
+            id: user.id,
+            username: user.username,
+            locale: user.locale,
+            theme: user.theme,
+            otp_verified: user.otp_verified,
+            otp_base32,
+            otp_auth_url,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            password_is_expired: user.password_is_expired,
+        }
+    }
+}
+
 #[derive(Serialize, Debug, Deserialize)]
 pub struct ProfileResponse {
     pub code: String,
@@ -38,6 +64,8 @@ pub struct DeviceData {
     pub token_id: Uuid,
     pub parsed_device_info: DeviceInfo,
     pub last_activity_date: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Debug, Deserialize)]
@@ -50,4 +78,3 @@ pub struct DevicesResponse {
 pub struct DeviceDeleteResponse {
     pub code: String,
 }
-
@@ -1,24 +1,73 @@
 use uuid::Uuid;
 
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
 use crate::features::profile::application::dto::DeviceDeleteResponse;
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::DeviceRepository;
 
 pub struct DeleteDeviceUseCase {
     device_repository: Box<dyn DeviceRepository>,
+    token_cache: TokenCache,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl DeleteDeviceUseCase {
-    pub fn new(device_repository: Box<dyn DeviceRepository>) -> Self {
-        Self { device_repository }
+    pub fn new(
+        device_repository: Box<dyn DeviceRepository>,
+        token_cache: TokenCache,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            device_repository,
+            token_cache,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
     }
 
-    pub async fn execute(&self, token_id: Uuid) -> Result<DeviceDeleteResponse, ProfileDomainError> {
-        self.device_repository.delete_by_token_id(token_id).await?;
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+        context: RequestContext,
+    ) -> Result<DeviceDeleteResponse, ProfileDomainError> {
+        self.unit_of_work
+            .run(|| async {
+                self.device_repository
+                    .delete_by_token_id(user_id, token_id)
+                    .await?;
+                self.outbox
+                    .publish(vec![DomainEvent::SessionRevoked { user_id, token_id }])
+                    .await?;
+
+                Ok::<_, ProfileDomainError>(())
+            })
+            .await?;
+        // Cached tokens are accepted without checking the database
+        self.token_cache.remove_key(token_id).await;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user_id,
+                SecurityEventType::DeviceRevoked,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
 
         Ok(DeviceDeleteResponse {
             code: "DEVICE_DELETED".to_string(),
         })
     }
 }
-
@@ -0,0 +1,78 @@
+use uuid::Uuid;
+
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::auth::structs::models::TokenCache;
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+use crate::features::profile::application::dto::DeviceDeleteResponse;
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::DeviceRepository;
+
+pub struct DeleteOtherDevicesUseCase {
+    device_repository: Box<dyn DeviceRepository>,
+    token_cache: TokenCache,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
+}
+
+impl DeleteOtherDevicesUseCase {
+    pub fn new(
+        device_repository: Box<dyn DeviceRepository>,
+        token_cache: TokenCache,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            device_repository,
+            token_cache,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
+    }
+
+    /// Signs the user out of every device but the one making the request.
+    pub async fn execute(
+        &self,
+        user_id: Uuid,
+        current_token_id: Uuid,
+        context: RequestContext,
+    ) -> Result<DeviceDeleteResponse, ProfileDomainError> {
+        let token_ids = self
+            .unit_of_work
+            .run(|| async {
+                let token_ids = self
+                    .device_repository
+                    .delete_all_by_user_id_except_token_id(user_id, current_token_id)
+                    .await?;
+                self.outbox
+                    .publish(DomainEvent::sessions_revoked(user_id, &token_ids))
+                    .await?;
+
+                Ok::<_, ProfileDomainError>(token_ids)
+            })
+            .await?;
+
+        for token_id in token_ids {
+            self.token_cache.remove_key(token_id).await;
+        }
+        self.audit_log
+            .record(SecurityEvent::new(
+                user_id,
+                SecurityEventType::SessionsRevoked,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
+
+        Ok(DeviceDeleteResponse {
+            code: "OTHER_DEVICES_DELETED".to_string(),
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use crate::core::helpers::mock_now::now;
+use crate::features::auth::domain::entities::TokenType;
+use crate::features::auth::domain::repositories::TokenService;
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::DataExportRepository;
+
+pub struct DownloadDataExportUseCase {
+    data_export_repository: Box<dyn DataExportRepository>,
+    token_service: Box<dyn TokenService>,
+}
+
+impl DownloadDataExportUseCase {
+    pub fn new(
+        data_export_repository: Box<dyn DataExportRepository>,
+        token_service: Box<dyn TokenService>,
+    ) -> Self {
+        Self {
+            data_export_repository,
+            token_service,
+        }
+    }
+
+    /// Returns the archive the token refers to. Fails with `InvalidDataExportLink` if the token
+    /// was tampered with, expired or was already used.
+    pub async fn execute(&self, token: &str) -> Result<String, ProfileDomainError> {
+        let claims = self
+            .token_service
+            .decode_token(token, TokenType::DataExport)
+            .map_err(|_| ProfileDomainError::InvalidDataExportLink)?;
+
+        self.data_export_repository
+            .consume(claims.jti, claims.user_id, now())
+            .await?
+            .ok_or(ProfileDomainError::InvalidDataExportLink)
+    }
+}
@@ -1,52 +1,77 @@
 use uuid::Uuid;
 
+use crate::features::auth::infrastructure::session_activity::SessionActivityWriter;
 use crate::features::profile::application::dto::{DeviceData, DeviceInfo, DevicesResponse};
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::DeviceRepository;
 
 pub struct GetDevicesUseCase {
     device_repository: Box<dyn DeviceRepository>,
-    token_cache: crate::features::auth::structs::models::TokenCache,
+    session_activity_writer: SessionActivityWriter,
 }
 
 impl GetDevicesUseCase {
     pub fn new(
         device_repository: Box<dyn DeviceRepository>,
-        token_cache: crate::features::auth::structs::models::TokenCache,
+        session_activity_writer: SessionActivityWriter,
     ) -> Self {
         Self {
             device_repository,
-            token_cache,
+            session_activity_writer,
         }
     }
 
     pub async fn execute(&self, user_id: Uuid) -> Result<DevicesResponse, ProfileDomainError> {
-        let devices = self.device_repository.find_all_by_user_id(user_id).await?;
-
-        let mut device_data = Vec::new();
-        for device in devices {
-            let last_activity = self
-                .token_cache
-                .get_value_for_key(device.token_id)
-                .await;
-
-            device_data.push(DeviceData {
-                token_id: device.token_id,
-                parsed_device_info: DeviceInfo {
-                    os: device.os,
-                    is_mobile: device.is_mobile,
-                    browser: device.browser,
-                    app_version: device.app_version,
-                    model: device.model,
-                },
-                last_activity_date: last_activity,
-            });
-        }
+        let devices = find_devices(
+            self.device_repository.as_ref(),
+            &self.session_activity_writer,
+            user_id,
+        )
+        .await?;
 
         Ok(DevicesResponse {
             code: "DEVICES_FETCHED".to_string(),
-            devices: device_data,
+            devices,
         })
     }
 }
 
+// Shared with the data export
+pub(crate) async fn find_devices(
+    device_repository: &dyn DeviceRepository,
+    session_activity_writer: &SessionActivityWriter,
+    user_id: Uuid,
+) -> Result<Vec<DeviceData>, ProfileDomainError> {
+    let devices = device_repository.find_all_by_user_id(user_id).await?;
+
+    let mut device_data = Vec::new();
+    for device in devices {
+        // Activity not written yet is more recent than the stored one
+        let pending_activity = session_activity_writer
+            .pending_activity(device.token_id)
+            .await;
+        let (last_activity, last_ip) = match pending_activity {
+            Some(activity) => (
+                Some(activity.last_activity_at),
+                activity.last_ip.or(device.last_ip),
+            ),
+            None => (device.last_activity, device.last_ip),
+        };
+
+        device_data.push(DeviceData {
+            token_id: device.token_id,
+            parsed_device_info: DeviceInfo {
+                os: device.os,
+                is_mobile: device.is_mobile,
+                browser: device.browser,
+                app_version: device.app_version,
+                model: device.model,
+            },
+            last_activity_date: last_activity,
+            last_ip,
+            created_at: device.created_at,
+        });
+    }
+
+    Ok(device_data)
+}
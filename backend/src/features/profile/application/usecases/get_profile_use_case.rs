@@ -1,6 +1,6 @@
 use uuid::Uuid;
 
-use crate::features::profile::application::dto::{ProfileResponse, UserData};
+use crate::features::profile::application::dto::ProfileResponse;
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::UserRepository;
 
@@ -22,19 +22,7 @@ impl GetProfileUseCase {
 
         Ok(ProfileResponse {
             code: "PROFILE_FETCHED".to_string(),
-            user: UserData {
-                id: user.id,
-                username: user.username,
-                locale: user.locale,
-                theme: user.theme,
-                otp_verified: user.otp_verified,
-                otp_base32: user.otp_base32,
-                otp_auth_url: user.otp_auth_url,
-                created_at: user.created_at,
-                updated_at: user.updated_at,
-                password_is_expired: user.password_is_expired,
-            },
+            user: user.into(),
         })
     }
 }
-
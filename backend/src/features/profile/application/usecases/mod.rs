@@ -1,16 +1,21 @@
 pub mod delete_device_use_case;
+pub mod delete_other_devices_use_case;
+pub mod download_data_export_use_case;
 pub mod get_devices_use_case;
 pub mod get_profile_use_case;
 pub mod is_otp_enabled_use_case;
+pub mod request_data_export_use_case;
 pub mod set_password_use_case;
 pub mod update_password_use_case;
 pub mod update_profile_use_case;
 
 pub use delete_device_use_case::DeleteDeviceUseCase;
+pub use delete_other_devices_use_case::DeleteOtherDevicesUseCase;
+pub use download_data_export_use_case::DownloadDataExportUseCase;
 pub use get_devices_use_case::GetDevicesUseCase;
 pub use get_profile_use_case::GetProfileUseCase;
 pub use is_otp_enabled_use_case::IsOtpEnabledUseCase;
+pub use request_data_export_use_case::{DataExportLinkSettings, RequestDataExportUseCase};
 pub use set_password_use_case::SetPasswordUseCase;
 pub use update_password_use_case::UpdatePasswordUseCase;
 pub use update_profile_use_case::UpdateProfileUseCase;
-
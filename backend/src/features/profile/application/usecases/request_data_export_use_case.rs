@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::core::helpers::mock_now::now;
+use crate::features::audit::application::dto::SecurityEventData;
+use crate::features::audit::domain::repositories::SecurityEventRepository;
+use crate::features::auth::domain::entities::{Claims, TokenType};
+use crate::features::auth::domain::repositories::TokenService;
+use crate::features::auth::infrastructure::session_activity::SessionActivityWriter;
+use crate::features::profile::application::dto::{
+    DataArchive, DataExportResponse, PreferencesData,
+};
+use crate::features::profile::application::usecases::get_devices_use_case::find_devices;
+use crate::features::profile::domain::entities::{DataExport, DataExportStatus};
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::{
+    DataExportRepository, DeviceRepository, UserRepository,
+};
+
+#[derive(Debug, Clone)]
+pub struct DataExportLinkSettings {
+    // How long the archive can be downloaded once requested
+    pub lifetime: Duration,
+    // How long the archive can take to be built before the export is considered failed
+    pub build_timeout: Duration,
+    pub issuer: String,
+    pub audience: String,
+}
+
+/// Starts building an archive of the data of the user in the background. Asking again returns
+/// the export in progress, or the download link once it is ready.
+pub struct RequestDataExportUseCase {
+    exporter: Arc<DataExporter>,
+    token_service: Box<dyn TokenService>,
+    settings: DataExportLinkSettings,
+}
+
+impl RequestDataExportUseCase {
+    pub fn new(
+        data_export_repository: Box<dyn DataExportRepository>,
+        user_repository: Box<dyn UserRepository>,
+        device_repository: Box<dyn DeviceRepository>,
+        security_event_repository: Box<dyn SecurityEventRepository>,
+        session_activity_writer: SessionActivityWriter,
+        token_service: Box<dyn TokenService>,
+        settings: DataExportLinkSettings,
+    ) -> Self {
+        Self {
+            exporter: Arc::new(DataExporter {
+                data_export_repository,
+                user_repository,
+                device_repository,
+                security_event_repository,
+                session_activity_writer,
+            }),
+            token_service,
+            settings,
+        }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<DataExportResponse, ProfileDomainError> {
+        let now_time = now();
+
+        let latest_export = self
+            .exporter
+            .data_export_repository
+            .find_latest_by_user_id(user_id)
+            .await?;
+        if let Some(export) = latest_export {
+            if export.is_usable_at(now_time, self.settings.build_timeout) {
+                return match export.status {
+                    DataExportStatus::Ready => self.ready_response(&export, now_time),
+                    _ => Ok(DataExportResponse {
+                        code: "DATA_EXPORT_PENDING".to_string(),
+                        download_token: None,
+                        expires_at: export.expires_at,
+                    }),
+                };
+            }
+
+            // The task building it was lost, it can't complete the export anymore
+            if export.status == DataExportStatus::Pending {
+                self.exporter.data_export_repository.fail(export.id).await?;
+            }
+        }
+
+        let export = DataExport {
+            id: Uuid::new_v4(),
+            user_id,
+            status: DataExportStatus::Pending,
+            created_at: now_time,
+            expires_at: now_time
+                .checked_add_signed(self.settings.lifetime)
+                .ok_or(ProfileDomainError::DataExportFailed)?,
+            downloaded_at: None,
+        };
+        self.exporter.data_export_repository.save(&export).await?;
+
+        let exporter = self.exporter.clone();
+        tokio::spawn(async move { exporter.export(export.id, user_id).await });
+
+        Ok(DataExportResponse {
+            code: "DATA_EXPORT_STARTED".to_string(),
+            download_token: None,
+            expires_at: export.expires_at,
+        })
+    }
+
+    fn ready_response(
+        &self,
+        export: &DataExport,
+        now_time: DateTime<Utc>,
+    ) -> Result<DataExportResponse, ProfileDomainError> {
+        // The token expires with the export, its jti refers to the export so it can only be
+        // downloaded once
+        let token = self
+            .token_service
+            .generate_data_export_token(&Claims {
+                exp: export.expires_at.timestamp(),
+                iat: now_time.timestamp(),
+                iss: self.settings.issuer.clone(),
+                aud: self.settings.audience.clone(),
+                jti: export.id,
+                user_id: export.user_id,
+                // Only the session tokens grant permissions
+                permissions: Vec::new(),
+                token_type: TokenType::DataExport,
+            })
+            .map_err(|_| ProfileDomainError::DataExportFailed)?;
+
+        Ok(DataExportResponse {
+            code: "DATA_EXPORT_READY".to_string(),
+            download_token: Some(token),
+            expires_at: export.expires_at,
+        })
+    }
+}
+
+// Owned by the use case and the tasks it spawns
+struct DataExporter {
+    data_export_repository: Box<dyn DataExportRepository>,
+    user_repository: Box<dyn UserRepository>,
+    device_repository: Box<dyn DeviceRepository>,
+    security_event_repository: Box<dyn SecurityEventRepository>,
+    session_activity_writer: SessionActivityWriter,
+}
+
+impl DataExporter {
+    async fn export(&self, export_id: Uuid, user_id: Uuid) {
+        let result = match self.build_archive(user_id).await {
+            Ok(archive) => {
+                self.data_export_repository
+                    .complete(export_id, user_id, &archive)
+                    .await
+            }
+            Err(e) => {
+                tracing::error!("Data export error: {}", e);
+                self.data_export_repository.fail(export_id).await
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!("Failed to store the data export {}: {}", export_id, e);
+        }
+    }
+
+    async fn build_archive(&self, user_id: Uuid) -> Result<String, ProfileDomainError> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or(ProfileDomainError::UserNotFound)?;
+        let devices = find_devices(
+            self.device_repository.as_ref(),
+            &self.session_activity_writer,
+            user_id,
+        )
+        .await?;
+        let security_events = self
+            .security_event_repository
+            .find_all_by_user_id(user_id)
+            .await
+            .map_err(|_| ProfileDomainError::DatabaseError)?;
+
+        let archive = DataArchive {
+            exported_at: now(),
+            preferences: PreferencesData {
+                locale: user.locale.clone(),
+                theme: user.theme.clone(),
+            },
+            profile: user.into(),
+            devices,
+            security_events: security_events
+                .into_iter()
+                .map(SecurityEventData::from)
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&archive).map_err(|e| {
+            tracing::error!("Serialization error: {}", e);
+            ProfileDomainError::DataExportFailed
+        })
+    }
+}
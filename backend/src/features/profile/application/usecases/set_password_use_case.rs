@@ -3,26 +3,47 @@ use rand::rngs::OsRng;
 use uuid::Uuid;
 
 use crate::core::helpers::mock_now::now;
+use crate::core::structs::unit_of_work::UnitOfWork;
+use crate::features::audit::domain::entities::{
+    RequestContext, SecurityEvent, SecurityEventOutcome, SecurityEventType,
+};
+use crate::features::audit::domain::services::AuditLog;
 use crate::features::auth::helpers::password::{
     password_is_long_enough, password_is_strong_enough,
 };
-use crate::features::profile::application::dto::{ProfileResponse, SetPasswordRequest, UserData};
+use crate::features::events::domain::entities::DomainEvent;
+use crate::features::events::domain::services::Outbox;
+use crate::features::profile::application::dto::{ProfileResponse, SetPasswordRequest};
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::UserRepository;
 
 pub struct SetPasswordUseCase {
     user_repository: Box<dyn UserRepository>,
+    audit_log: AuditLog,
+    unit_of_work: Box<dyn UnitOfWork>,
+    outbox: Outbox,
 }
 
 impl SetPasswordUseCase {
-    pub fn new(user_repository: Box<dyn UserRepository>) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: Box<dyn UserRepository>,
+        audit_log: AuditLog,
+        unit_of_work: Box<dyn UnitOfWork>,
+        outbox: Outbox,
+    ) -> Self {
+        Self {
+            user_repository,
+            audit_log,
+            unit_of_work,
+            outbox,
+        }
     }
 
     pub async fn execute(
         &self,
         user_id: Uuid,
         request: SetPasswordRequest,
+        context: RequestContext,
     ) -> Result<ProfileResponse, ProfileDomainError> {
         let mut user = self
             .user_repository
@@ -54,23 +75,28 @@ impl SetPasswordUseCase {
         user.password_is_expired = false;
         user.updated_at = now();
 
-        self.user_repository.update(&user).await?;
+        self.unit_of_work
+            .run(|| async {
+                self.user_repository.update(&user).await?;
+                self.outbox
+                    .publish(vec![DomainEvent::PasswordChanged { user_id }])
+                    .await?;
+
+                Ok::<_, ProfileDomainError>(())
+            })
+            .await?;
+        self.audit_log
+            .record(SecurityEvent::new(
+                user_id,
+                SecurityEventType::PasswordChanged,
+                SecurityEventOutcome::Success,
+                &context,
+            ))
+            .await;
 
         Ok(ProfileResponse {
             code: "PASSWORD_CHANGED".to_string(),
-            user: UserData {
-                id: user.id,
-                username: user.username,
-                locale: user.locale,
-                theme: user.theme,
-                otp_verified: user.otp_verified,
-                otp_base32: user.otp_base32,
-                otp_auth_url: user.otp_auth_url,
-                created_at: user.created_at,
-                updated_at: user.updated_at,
-                password_is_expired: user.password_is_expired,
-            },
+            user: user.into(),
         })
     }
 }
-
@@ -1,7 +1,7 @@
 use uuid::Uuid;
 
 use crate::core::helpers::mock_now::now;
-use crate::features::profile::application::dto::{ProfileResponse, UpdateProfileRequest, UserData};
+use crate::features::profile::application::dto::{ProfileResponse, UpdateProfileRequest};
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::UserRepository;
 
@@ -34,19 +34,7 @@ impl UpdateProfileUseCase {
 
         Ok(ProfileResponse {
             code: "PROFILE_UPDATED".to_string(),
-            user: UserData {
-                id: user.id,
-                username: user.username,
-                locale: user.locale,
-                theme: user.theme,
-                otp_verified: user.otp_verified,
-                otp_base32: user.otp_base32,
-                otp_auth_url: user.otp_auth_url,
-                created_at: user.created_at,
-                updated_at: user.updated_at,
-                password_is_expired: user.password_is_expired,
-            },
+            user: user.into(),
         })
     }
 }
-
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataExportStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+impl DataExportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataExportStatus::Pending => "pending",
+            DataExportStatus::Ready => "ready",
+            DataExportStatus::Failed => "failed",
+        }
+    }
+
+    // An unknown status can't be downloaded
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "pending" => DataExportStatus::Pending,
+            "ready" => DataExportStatus::Ready,
+            _ => DataExportStatus::Failed,
+        }
+    }
+}
+
+/// An archive of the data of a user. The archive itself is only loaded when it is downloaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: DataExportStatus,
+    pub created_at: DateTime<Utc>,
+    // Neither the archive nor its download link can be used after this date
+    pub expires_at: DateTime<Utc>,
+    pub downloaded_at: Option<DateTime<Utc>>,
+}
+
+impl DataExport {
+    /// Whether the export is still being built or can still be downloaded. An export still
+    /// pending after `build_timeout` lost its task, with a restart for instance.
+    pub fn is_usable_at(&self, now: DateTime<Utc>, build_timeout: Duration) -> bool {
+        let is_building = match self.status {
+            DataExportStatus::Pending => now < self.created_at + build_timeout,
+            DataExportStatus::Ready => true,
+            DataExportStatus::Failed => false,
+        };
+
+        is_building && self.downloaded_at.is_none() && now < self.expires_at
+    }
+}
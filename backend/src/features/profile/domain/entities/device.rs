@@ -13,5 +13,6 @@ pub struct Device {
     pub model: Option<String>,
     pub expires_at: DateTime<Utc>,
     pub last_activity: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
-
@@ -1,6 +1,7 @@
+pub mod data_export;
 pub mod device;
 pub mod user;
 
+pub use data_export::{DataExport, DataExportStatus};
 pub use device::Device;
 pub use user::User;
-
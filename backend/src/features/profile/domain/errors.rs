@@ -1,5 +1,8 @@
 use thiserror::Error;
 
+use crate::core::structs::transaction::TransactionError;
+use crate::features::events::domain::errors::EventsDomainError;
+
 #[derive(Error, Debug)]
 pub enum ProfileDomainError {
     #[error("User not found")]
@@ -19,5 +22,27 @@ pub enum ProfileDomainError {
 
     #[error("Password not expired")]
     PasswordNotExpired,
+
+    #[error("Invalid data export link")]
+    InvalidDataExportLink,
+
+    #[error("Data export failed")]
+    DataExportFailed,
+
+    #[error("Database error")]
+    DatabaseError,
 }
 
+impl From<TransactionError> for ProfileDomainError {
+    fn from(e: TransactionError) -> Self {
+        tracing::error!("Transaction error: {}", e);
+        ProfileDomainError::DatabaseError
+    }
+}
+
+impl From<EventsDomainError> for ProfileDomainError {
+    fn from(e: EventsDomainError) -> Self {
+        tracing::error!("Outbox error: {}", e);
+        ProfileDomainError::DatabaseError
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::features::profile::domain::entities::DataExport;
+use crate::features::profile::domain::errors::ProfileDomainError;
+
+#[async_trait::async_trait]
+pub trait DataExportRepository: Send + Sync {
+    async fn save(&self, export: &DataExport) -> Result<(), ProfileDomainError>;
+    async fn find_latest_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<DataExport>, ProfileDomainError>;
+    /// Stores the archive of a pending export, which makes it ready to be downloaded.
+    async fn complete(
+        &self,
+        export_id: Uuid,
+        user_id: Uuid,
+        archive: &str,
+    ) -> Result<(), ProfileDomainError>;
+    async fn fail(&self, export_id: Uuid) -> Result<(), ProfileDomainError>;
+    /// Marks a ready export of the user as downloaded and returns its archive, or `None` if it
+    /// expired or was already downloaded.
+    async fn consume(
+        &self,
+        export_id: Uuid,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, ProfileDomainError>;
+}
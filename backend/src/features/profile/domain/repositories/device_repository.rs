@@ -6,6 +6,17 @@ use crate::features::profile::domain::errors::ProfileDomainError;
 #[async_trait::async_trait]
 pub trait DeviceRepository: Send + Sync {
     async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Device>, ProfileDomainError>;
-    async fn delete_by_token_id(&self, token_id: Uuid) -> Result<(), ProfileDomainError>;
+    /// Fails with `DeviceNotFound` if the token doesn't belong to the user.
+    async fn delete_by_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<(), ProfileDomainError>;
+    /// Deletes every session of the user but the one `token_id` belongs to, and returns the
+    /// ids of the deleted tokens.
+    async fn delete_all_by_user_id_except_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<Vec<Uuid>, ProfileDomainError>;
 }
-
@@ -1,6 +1,7 @@
+pub mod data_export_repository;
 pub mod device_repository;
 pub mod user_repository;
 
+pub use data_export_repository::DataExportRepository;
 pub use device_repository::DeviceRepository;
 pub use user_repository::UserRepository;
-
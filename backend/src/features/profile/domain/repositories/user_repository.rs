@@ -9,4 +9,3 @@ pub trait UserRepository: Send + Sync {
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, ProfileDomainError>;
     async fn update(&self, user: &User) -> Result<(), ProfileDomainError>;
 }
-
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::features::profile::domain::entities::DataExportStatus;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Deserialize, Serialize, Clone, FromRow)]
+pub struct DataExportModel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub downloaded_at: Option<DateTime<Utc>>,
+}
+
+impl From<DataExportModel> for crate::features::profile::domain::entities::DataExport {
+    fn from(model: DataExportModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            status: DataExportStatus::from_name(&model.status),
+            created_at: model.created_at,
+            expires_at: model.expires_at,
+            downloaded_at: model.downloaded_at,
+        }
+    }
+}
+
+impl From<crate::features::profile::domain::entities::DataExport> for DataExportModel {
+    fn from(entity: crate::features::profile::domain::entities::DataExport) -> Self {
+        Self {
+            id: entity.id,
+            user_id: entity.user_id,
+            status: entity.status.as_str().to_string(),
+            created_at: entity.created_at,
+            expires_at: entity.expires_at,
+            downloaded_at: entity.downloaded_at,
+        }
+    }
+}
@@ -15,6 +15,9 @@ pub struct DeviceModel {
     pub browser: Option<String>,
     pub app_version: Option<String>,
     pub model: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub last_ip: Option<String>,
 }
 
 impl From<DeviceModel> for crate::features::profile::domain::entities::Device {
@@ -28,7 +31,9 @@ impl From<DeviceModel> for crate::features::profile::domain::entities::Device {
             app_version: model.app_version,
             model: model.model,
             expires_at: model.expires_at,
-            last_activity: None, // This would need to come from TokenCache separately
+            last_activity: model.last_activity_at,
+            last_ip: model.last_ip,
+            created_at: model.created_at,
         }
     }
 }
@@ -45,7 +50,9 @@ impl From<crate::features::profile::domain::entities::Device> for DeviceModel {
             browser: entity.browser,
             app_version: entity.app_version,
             model: entity.model,
+            created_at: entity.created_at,
+            last_activity_at: entity.last_activity,
+            last_ip: entity.last_ip,
         }
     }
 }
-
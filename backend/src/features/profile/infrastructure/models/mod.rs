@@ -1,6 +1,7 @@
+pub mod data_export;
 pub mod device;
 pub mod user;
 
+pub use data_export::DataExportModel;
 pub use device::DeviceModel;
 pub use user::UserModel;
-
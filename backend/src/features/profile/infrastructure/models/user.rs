@@ -54,4 +54,3 @@ impl From<crate::features::profile::domain::entities::User> for UserModel {
         }
     }
 }
-
@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::envelope_cipher::EnvelopeCipher;
+use crate::features::profile::domain::entities::{DataExport, DataExportStatus};
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::DataExportRepository;
+use crate::features::profile::infrastructure::models::DataExportModel;
+
+// Binds an archive to its user, so it can't be moved to another row
+fn associated_data(user_id: Uuid) -> Vec<u8> {
+    format!("data_exports.archive:{user_id}").into_bytes()
+}
+
+#[derive(Clone)]
+pub struct DataExportRepositoryImpl {
+    pool: sqlx::PgPool,
+    cipher: EnvelopeCipher,
+}
+
+impl DataExportRepositoryImpl {
+    pub fn new(pool: sqlx::PgPool, cipher: EnvelopeCipher) -> Self {
+        Self { pool, cipher }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataExportRepository for DataExportRepositoryImpl {
+    async fn save(&self, export: &DataExport) -> Result<(), ProfileDomainError> {
+        let model: DataExportModel = export.clone().into();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO data_exports (id, user_id, status, created_at, expires_at, downloaded_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            model.id,
+            model.user_id,
+            model.status,
+            model.created_at,
+            model.expires_at,
+            model.downloaded_at,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_latest_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<DataExport>, ProfileDomainError> {
+        let result = sqlx::query_as!(
+            DataExportModel,
+            r#"
+            SELECT id, user_id, status, created_at, expires_at, downloaded_at
+            FROM data_exports
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        Ok(result.map(|model| model.into()))
+    }
+
+    async fn complete(
+        &self,
+        export_id: Uuid,
+        user_id: Uuid,
+        archive: &str,
+    ) -> Result<(), ProfileDomainError> {
+        let archive = self
+            .cipher
+            .encrypt(archive, &associated_data(user_id))
+            .map_err(|e| {
+                tracing::error!("Encryption error: {}", e);
+                ProfileDomainError::DataExportFailed
+            })?;
+
+        sqlx::query!(
+            r#"
+            UPDATE data_exports
+            SET status = $3, archive = $4
+            WHERE id = $1 AND user_id = $2 AND status = $5
+            "#,
+            export_id,
+            user_id,
+            DataExportStatus::Ready.as_str(),
+            archive,
+            DataExportStatus::Pending.as_str(),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn fail(&self, export_id: Uuid) -> Result<(), ProfileDomainError> {
+        sqlx::query!(
+            r#"
+            UPDATE data_exports
+            SET status = $2
+            WHERE id = $1 AND status = $3
+            "#,
+            export_id,
+            DataExportStatus::Failed.as_str(),
+            DataExportStatus::Pending.as_str(),
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        Ok(())
+    }
+
+    async fn consume(
+        &self,
+        export_id: Uuid,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, ProfileDomainError> {
+        // The row is locked so that two concurrent downloads can't both get the archive, which
+        // isn't kept once downloaded
+        let archive = sqlx::query_scalar!(
+            r#"
+            WITH downloadable AS (
+                SELECT id, archive
+                FROM data_exports
+                WHERE id = $1
+                    AND user_id = $2
+                    AND status = $4
+                    AND downloaded_at IS NULL
+                    AND expires_at > $3
+                FOR UPDATE
+            )
+            UPDATE data_exports
+            SET downloaded_at = $3, archive = NULL
+            FROM downloadable
+            WHERE data_exports.id = downloadable.id
+            RETURNING downloadable.archive AS "archive!"
+            "#,
+            export_id,
+            user_id,
+            now,
+            DataExportStatus::Ready.as_str(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        archive
+            .map(|archive| {
+                self.cipher
+                    .decrypt(&archive, &associated_data(user_id))
+                    .map_err(|e| {
+                        tracing::error!("Decryption error: {}", e);
+                        ProfileDomainError::DataExportFailed
+                    })
+            })
+            .transpose()
+    }
+}
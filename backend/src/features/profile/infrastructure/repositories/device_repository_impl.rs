@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+use crate::core::structs::transaction::{self, Connection};
 use crate::features::profile::domain::entities::Device;
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::DeviceRepository;
@@ -14,21 +15,30 @@ impl DeviceRepositoryImpl {
     pub fn new(pool: sqlx::PgPool) -> Self {
         Self { pool }
     }
+
+    async fn connection(&self) -> Result<Connection, ProfileDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })
+    }
 }
 
 #[async_trait::async_trait]
 impl DeviceRepository for DeviceRepositoryImpl {
     async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Device>, ProfileDomainError> {
+        let mut conn = self.connection().await?;
         let devices = sqlx::query_as!(
             DeviceModel,
             r#"
-            SELECT id, user_id, token_id, expires_at, os, is_mobile, browser, app_version, model
+            SELECT id, user_id, token_id, expires_at, os, is_mobile, browser, app_version, model,
+                created_at, last_activity_at, last_ip
             FROM user_tokens
-            WHERE user_id = $1
+            WHERE user_id = $1 AND rotated_at IS NULL
             "#,
             user_id
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -38,23 +48,64 @@ impl DeviceRepository for DeviceRepositoryImpl {
         Ok(devices.into_iter().map(|d| d.into()).collect())
     }
 
-    async fn delete_by_token_id(&self, token_id: Uuid) -> Result<(), ProfileDomainError> {
-        sqlx::query!(
+    async fn delete_by_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<(), ProfileDomainError> {
+        let mut conn = self.connection().await?;
+        let result = sqlx::query!(
             r#"
             DELETE
             FROM user_tokens
-            WHERE token_id = $1
+            WHERE token_id = $1 AND user_id = $2
             "#,
-            token_id
+            token_id,
+            user_id
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
-            ProfileDomainError::DeviceNotFound
+            ProfileDomainError::DatabaseError
         })?;
 
+        if result.rows_affected() == 0 {
+            return Err(ProfileDomainError::DeviceNotFound);
+        }
+
         Ok(())
     }
-}
 
+    async fn delete_all_by_user_id_except_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<Vec<Uuid>, ProfileDomainError> {
+        // The rotated tokens of the current session are kept for refresh token reuse detection
+        let mut conn = self.connection().await?;
+        let token_ids = sqlx::query_scalar!(
+            r#"
+            DELETE
+            FROM user_tokens
+            WHERE user_id = $1
+                AND family_id <> (
+                    SELECT family_id
+                    FROM user_tokens
+                    WHERE token_id = $2 AND user_id = $1
+                )
+            RETURNING token_id
+            "#,
+            user_id,
+            token_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })?;
+
+        Ok(token_ids)
+    }
+}
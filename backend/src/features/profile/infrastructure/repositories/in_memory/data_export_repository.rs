@@ -0,0 +1,111 @@
+use std::sync::MutexGuard;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{DataExportRow, InMemoryDatabase, Tables};
+use crate::features::profile::domain::entities::{DataExport, DataExportStatus};
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::DataExportRepository;
+
+#[derive(Clone)]
+pub struct InMemoryDataExportRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryDataExportRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, ProfileDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })
+    }
+
+    // Only a pending export is settled
+    fn settle(
+        &self,
+        export_id: Uuid,
+        status: DataExportStatus,
+        archive: Option<&str>,
+    ) -> Result<(), ProfileDomainError> {
+        let mut tables = self.write("data_exports")?;
+
+        if let Some(row) = tables.data_exports.iter_mut().find(|row| {
+            row.export.id == export_id && row.export.status == DataExportStatus::Pending
+        }) {
+            row.export.status = status;
+            row.archive = archive.map(str::to_string);
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DataExportRepository for InMemoryDataExportRepository {
+    async fn save(&self, export: &DataExport) -> Result<(), ProfileDomainError> {
+        let mut tables = self.write("data_exports")?;
+
+        tables.data_exports.push(DataExportRow {
+            export: export.clone(),
+            archive: None,
+        });
+
+        Ok(())
+    }
+
+    async fn find_latest_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<DataExport>, ProfileDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .data_exports
+            .iter()
+            .filter(|row| row.export.user_id == user_id)
+            .max_by_key(|row| row.export.created_at)
+            .map(|row| row.export.clone()))
+    }
+
+    async fn complete(
+        &self,
+        export_id: Uuid,
+        _user_id: Uuid,
+        archive: &str,
+    ) -> Result<(), ProfileDomainError> {
+        self.settle(export_id, DataExportStatus::Ready, Some(archive))
+    }
+
+    async fn fail(&self, export_id: Uuid) -> Result<(), ProfileDomainError> {
+        self.settle(export_id, DataExportStatus::Failed, None)
+    }
+
+    async fn consume(
+        &self,
+        export_id: Uuid,
+        user_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, ProfileDomainError> {
+        let mut tables = self.write("data_exports")?;
+
+        let Some(row) = tables.data_exports.iter_mut().find(|row| {
+            row.export.id == export_id
+                && row.export.user_id == user_id
+                && row.export.status == DataExportStatus::Ready
+                && row.export.downloaded_at.is_none()
+                && row.export.expires_at > now
+        }) else {
+            return Ok(None);
+        };
+
+        // The archive isn't kept once downloaded
+        row.export.downloaded_at = Some(now);
+
+        Ok(row.archive.take())
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::MutexGuard;
+
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::UserToken;
+use crate::features::profile::domain::entities::Device;
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::DeviceRepository;
+
+#[derive(Clone)]
+pub struct InMemoryDeviceRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryDeviceRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, ProfileDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })
+    }
+}
+
+fn to_device(token: &UserToken) -> Device {
+    Device {
+        token_id: token.token_id,
+        user_id: token.user_id,
+        os: token.os.clone(),
+        is_mobile: token.is_mobile,
+        browser: token.browser.clone(),
+        app_version: token.app_version.clone(),
+        model: token.model.clone(),
+        expires_at: token.expires_at,
+        last_activity: token.last_activity_at,
+        last_ip: token.last_ip.clone(),
+        created_at: token.created_at,
+    }
+}
+
+#[async_trait::async_trait]
+impl DeviceRepository for InMemoryDeviceRepository {
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> Result<Vec<Device>, ProfileDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .user_tokens
+            .iter()
+            .filter(|token| token.user_id == user_id && token.rotated_at.is_none())
+            .map(to_device)
+            .collect())
+    }
+
+    async fn delete_by_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<(), ProfileDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        let count = tables.user_tokens.len();
+        tables
+            .user_tokens
+            .retain(|token| !(token.token_id == token_id && token.user_id == user_id));
+
+        if tables.user_tokens.len() == count {
+            return Err(ProfileDomainError::DeviceNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_all_by_user_id_except_token_id(
+        &self,
+        user_id: Uuid,
+        token_id: Uuid,
+    ) -> Result<Vec<Uuid>, ProfileDomainError> {
+        let mut tables = self.write("user_tokens")?;
+
+        // Nothing is deleted when the token is unknown, as no family differs from a NULL one
+        let Some(family_id) = tables
+            .user_tokens
+            .iter()
+            .find(|token| token.token_id == token_id && token.user_id == user_id)
+            .map(|token| token.family_id)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let (deleted, kept): (Vec<UserToken>, Vec<UserToken>) =
+            std::mem::take(&mut tables.user_tokens)
+                .into_iter()
+                .partition(|token| token.user_id == user_id && token.family_id != family_id);
+        tables.user_tokens = kept;
+
+        Ok(deleted.into_iter().map(|token| token.token_id).collect())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod data_export_repository;
+pub mod device_repository;
+pub mod user_repository;
+
+pub use data_export_repository::InMemoryDataExportRepository;
+pub use device_repository::InMemoryDeviceRepository;
+pub use user_repository::InMemoryUserRepository;
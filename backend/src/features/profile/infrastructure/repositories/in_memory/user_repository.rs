@@ -0,0 +1,86 @@
+use std::sync::MutexGuard;
+
+use uuid::Uuid;
+
+use crate::core::structs::in_memory_database::{InMemoryDatabase, Tables};
+use crate::features::auth::domain::entities::User as AuthUser;
+use crate::features::profile::domain::entities::User;
+use crate::features::profile::domain::errors::ProfileDomainError;
+use crate::features::profile::domain::repositories::UserRepository;
+
+#[derive(Clone)]
+pub struct InMemoryUserRepository {
+    database: InMemoryDatabase,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(database: InMemoryDatabase) -> Self {
+        Self { database }
+    }
+
+    fn write(&self, table: &str) -> Result<MutexGuard<'_, Tables>, ProfileDomainError> {
+        self.database.write(table).map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })
+    }
+}
+
+fn to_profile_user(user: &AuthUser) -> User {
+    User {
+        id: user.id,
+        username: user.username.clone(),
+        password_hash: user.password_hash.clone(),
+        locale: user.locale.clone(),
+        theme: user.theme.clone(),
+        otp_verified: user.otp_verified,
+        otp_base32: user.otp_base32.clone(),
+        otp_auth_url: user.otp_auth_url.clone(),
+        password_is_expired: user.password_is_expired,
+        created_at: user.created_at,
+        updated_at: user.updated_at,
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, ProfileDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.id == user_id)
+            .map(to_profile_user))
+    }
+
+    async fn find_by_username(&self, username: &str) -> Result<Option<User>, ProfileDomainError> {
+        let tables = self.database.tables();
+
+        Ok(tables
+            .users
+            .iter()
+            .find(|user| user.username == username)
+            .map(to_profile_user))
+    }
+
+    async fn update(&self, user: &User) -> Result<(), ProfileDomainError> {
+        let mut tables = self.write("users")?;
+
+        // The OTP settings are owned by the auth feature
+        if let Some(existing) = tables
+            .users
+            .iter_mut()
+            .find(|existing| existing.id == user.id)
+        {
+            existing.username = user.username.clone();
+            existing.password_hash = user.password_hash.clone();
+            existing.locale = user.locale.clone();
+            existing.theme = user.theme.clone();
+            existing.updated_at = user.updated_at;
+            existing.password_is_expired = user.password_is_expired;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
+pub mod data_export_repository_impl;
 pub mod device_repository_impl;
 pub mod user_repository_impl;
 
+pub use data_export_repository_impl::DataExportRepositoryImpl;
 pub use device_repository_impl::DeviceRepositoryImpl;
 pub use user_repository_impl::UserRepositoryImpl;
 
+#[cfg(feature = "test-util")]
+pub mod in_memory;
@@ -1,5 +1,10 @@
 use uuid::Uuid;
 
+use crate::core::structs::envelope_cipher::EnvelopeCipher;
+use crate::core::structs::transaction::{self, Connection};
+use crate::features::auth::infrastructure::otp_secrets::{
+    decrypt_otp_column, OTP_AUTH_URL_COLUMN, OTP_BASE32_COLUMN,
+};
 use crate::features::profile::domain::entities::User;
 use crate::features::profile::domain::errors::ProfileDomainError;
 use crate::features::profile::domain::repositories::UserRepository;
@@ -8,17 +13,42 @@ use crate::features::profile::infrastructure::models::UserModel;
 #[derive(Clone)]
 pub struct UserRepositoryImpl {
     pool: sqlx::PgPool,
+    cipher: EnvelopeCipher,
 }
 
 impl UserRepositoryImpl {
-    pub fn new(pool: sqlx::PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: sqlx::PgPool, cipher: EnvelopeCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    fn decrypt_user(&self, mut user_model: UserModel) -> Result<User, ProfileDomainError> {
+        let decrypt = |column: &str, value: Option<String>| {
+            decrypt_otp_column(&self.cipher, user_model.id, column, value).map_err(|e| {
+                tracing::error!("Decryption error: {}", e);
+                ProfileDomainError::UserNotFound
+            })
+        };
+        let otp_base32 = decrypt(OTP_BASE32_COLUMN, user_model.otp_base32.take())?;
+        let otp_auth_url = decrypt(OTP_AUTH_URL_COLUMN, user_model.otp_auth_url.take())?;
+
+        user_model.otp_base32 = otp_base32;
+        user_model.otp_auth_url = otp_auth_url;
+
+        Ok(user_model.into())
+    }
+
+    async fn connection(&self) -> Result<Connection, ProfileDomainError> {
+        transaction::acquire(&self.pool).await.map_err(|e| {
+            tracing::error!("Database error: {}", e);
+            ProfileDomainError::DatabaseError
+        })
     }
 }
 
 #[async_trait::async_trait]
 impl UserRepository for UserRepositoryImpl {
     async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, ProfileDomainError> {
+        let mut conn = self.connection().await?;
         let user_model = sqlx::query_as!(
             UserModel,
             r#"
@@ -28,17 +58,18 @@ impl UserRepository for UserRepositoryImpl {
             "#,
             user_id,
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
             ProfileDomainError::UserNotFound
         })?;
 
-        Ok(user_model.map(|u| u.into()))
+        user_model.map(|u| self.decrypt_user(u)).transpose()
     }
 
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, ProfileDomainError> {
+        let mut conn = self.connection().await?;
         let user_model = sqlx::query_as!(
             UserModel,
             r#"
@@ -48,40 +79,38 @@ impl UserRepository for UserRepositoryImpl {
             "#,
             username
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
             ProfileDomainError::UserNotFound
         })?;
 
-        Ok(user_model.map(|u| u.into()))
+        user_model.map(|u| self.decrypt_user(u)).transpose()
     }
 
     async fn update(&self, user: &User) -> Result<(), ProfileDomainError> {
         let user_model: UserModel = user.clone().into();
-        
+
+        // The OTP settings are owned by the auth feature
+        let mut conn = self.connection().await?;
         sqlx::query!(
             r#"
             UPDATE users
-            SET 
+            SET
                 username = $1, password = $2, locale = $3, theme = $4,
-                otp_verified = $5, otp_base32 = $6, otp_auth_url = $7,
-                updated_at = $8, password_is_expired = $9
-            WHERE id = $10
+                updated_at = $5, password_is_expired = $6
+            WHERE id = $7
             "#,
             user_model.username,
             user_model.password,
             user_model.locale,
             user_model.theme,
-            user_model.otp_verified,
-            user_model.otp_base32,
-            user_model.otp_auth_url,
             user_model.updated_at,
             user_model.password_is_expired,
             user_model.id,
         )
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await
         .map_err(|e| {
             tracing::error!("Database error: {}", e);
@@ -91,4 +120,3 @@ impl UserRepository for UserRepositoryImpl {
         Ok(())
     }
 }
-
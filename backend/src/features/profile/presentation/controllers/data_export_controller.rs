@@ -0,0 +1,64 @@
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{get, post, web, web::ReqData, HttpResponse, Responder};
+use tracing::error;
+
+use crate::core::structs::responses::GenericResponse;
+use crate::features::auth::domain::entities::Claims;
+use crate::features::profile::application::dto::DownloadDataExportRequest;
+use crate::features::profile::application::usecases::{
+    DownloadDataExportUseCase, RequestDataExportUseCase,
+};
+
+#[get("/me/export")]
+pub async fn request_data_export(
+    claims: ReqData<Claims>,
+    use_case: web::Data<RequestDataExportUseCase>,
+) -> impl Responder {
+    match use_case.execute(claims.user_id).await {
+        // Until the archive is ready, the client is expected to ask again later
+        Ok(response) if response.download_token.is_none() => {
+            HttpResponse::Accepted().json(response)
+        }
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Request data export error: {}", e);
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: "DATA_EXPORT_ERROR".to_string(),
+                message: "Failed to export the data".to_string(),
+            })
+        }
+    }
+}
+
+// Authenticated by the signed download token, sent in the body so that it isn't logged with
+// the URI
+#[post("")]
+pub async fn download_data_export(
+    body: web::Json<DownloadDataExportRequest>,
+    use_case: web::Data<DownloadDataExportUseCase>,
+) -> impl Responder {
+    match use_case.execute(&body.token).await {
+        Ok(archive) => HttpResponse::Ok()
+            .content_type("application/json")
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename("data-export.json".to_string())],
+            })
+            .body(archive),
+        Err(e) => {
+            error!("Download data export error: {}", e);
+            match e {
+                crate::features::profile::domain::errors::ProfileDomainError::InvalidDataExportLink => {
+                    HttpResponse::NotFound().json(GenericResponse {
+                        code: "INVALID_DATA_EXPORT_LINK".to_string(),
+                        message: "This link is invalid, expired or was already used".to_string(),
+                    })
+                }
+                _ => HttpResponse::InternalServerError().json(GenericResponse {
+                    code: "DATA_EXPORT_DOWNLOAD_ERROR".to_string(),
+                    message: "Failed to download the data export".to_string(),
+                }),
+            }
+        }
+    }
+}
@@ -1,10 +1,13 @@
-use actix_web::{delete, get, web, web::Path, web::ReqData, HttpResponse, Responder};
+use actix_web::{delete, get, web, web::Path, web::ReqData, HttpRequest, HttpResponse, Responder};
 use tracing::error;
 use uuid::Uuid;
 
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::domain::entities::Claims;
-use crate::features::profile::application::usecases::{DeleteDeviceUseCase, GetDevicesUseCase};
+use crate::features::profile::application::usecases::{
+    DeleteDeviceUseCase, DeleteOtherDevicesUseCase, GetDevicesUseCase,
+};
 
 #[get("/")]
 pub async fn get_devices(
@@ -23,21 +26,52 @@ pub async fn get_devices(
     }
 }
 
+// Registered before `delete_device`, which would otherwise match this path
+#[delete("/others")]
+pub async fn delete_other_devices(
+    req: HttpRequest,
+    claims: ReqData<Claims>,
+    use_case: web::Data<DeleteOtherDevicesUseCase>,
+) -> impl Responder {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(claims.user_id, claims.jti, context).await {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(e) => {
+            error!("Delete other devices error: {}", e);
+            HttpResponse::InternalServerError().json(GenericResponse {
+                code: "DEVICES_DELETE_ERROR".to_string(),
+                message: "Failed to delete devices".to_string(),
+            })
+        }
+    }
+}
+
 #[delete("/{token_id}")]
 pub async fn delete_device(
-    _claims: ReqData<Claims>,
+    req: HttpRequest,
+    claims: ReqData<Claims>,
     token_id: Path<Uuid>,
     use_case: web::Data<DeleteDeviceUseCase>,
 ) -> impl Responder {
-    match use_case.execute(*token_id).await {
+    let context = get_request_context(req).await;
+
+    match use_case.execute(claims.user_id, *token_id, context).await {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Delete device error: {}", e);
-            HttpResponse::InternalServerError().json(GenericResponse {
-                code: "DEVICE_DELETE_ERROR".to_string(),
-                message: "Failed to delete device".to_string(),
-            })
+            match e {
+                crate::features::profile::domain::errors::ProfileDomainError::DeviceNotFound => {
+                    HttpResponse::NotFound().json(GenericResponse {
+                        code: "DEVICE_NOT_FOUND".to_string(),
+                        message: "Device not found".to_string(),
+                    })
+                }
+                _ => HttpResponse::InternalServerError().json(GenericResponse {
+                    code: "DEVICE_DELETE_ERROR".to_string(),
+                    message: "Failed to delete device".to_string(),
+                }),
+            }
         }
     }
 }
-
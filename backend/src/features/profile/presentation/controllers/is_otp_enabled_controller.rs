@@ -5,7 +5,7 @@ use crate::core::structs::responses::GenericResponse;
 use crate::features::profile::application::dto::IsOtpEnabledRequest;
 use crate::features::profile::application::usecases::IsOtpEnabledUseCase;
 
-#[post("/is-otp-enabled")]
+#[post("")]
 pub async fn is_otp_enabled(
     body: web::Json<IsOtpEnabledRequest>,
     use_case: web::Data<IsOtpEnabledUseCase>,
@@ -24,4 +24,3 @@ pub async fn is_otp_enabled(
         }
     }
 }
-
@@ -1,10 +1,11 @@
+pub mod data_export_controller;
 pub mod device_controller;
 pub mod is_otp_enabled_controller;
 pub mod password_controller;
 pub mod profile_controller;
 
-pub use device_controller::{delete_device, get_devices};
+pub use data_export_controller::{download_data_export, request_data_export};
+pub use device_controller::{delete_device, delete_other_devices, get_devices};
 pub use is_otp_enabled_controller::is_otp_enabled;
 pub use password_controller::{set_password, update_password};
 pub use profile_controller::{get_profile, update_profile};
-
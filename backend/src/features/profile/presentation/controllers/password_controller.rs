@@ -1,18 +1,25 @@
-use actix_web::{post, web, web::ReqData, HttpResponse, Responder};
+use actix_web::{post, web, web::ReqData, HttpRequest, HttpResponse, Responder};
 use tracing::error;
 
 use crate::core::structs::responses::GenericResponse;
+use crate::features::audit::helpers::request_context::get_request_context;
 use crate::features::auth::domain::entities::Claims;
 use crate::features::profile::application::dto::{SetPasswordRequest, UpdatePasswordRequest};
 use crate::features::profile::application::usecases::{SetPasswordUseCase, UpdatePasswordUseCase};
 
 #[post("/set-password")]
 pub async fn set_password(
+    req: HttpRequest,
     body: web::Json<SetPasswordRequest>,
     request_claims: ReqData<Claims>,
     use_case: web::Data<SetPasswordUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.user_id, body.into_inner()).await {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(request_claims.user_id, body.into_inner(), context)
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Set password error: {}", e);
@@ -41,11 +48,17 @@ pub async fn set_password(
 
 #[post("/update-password")]
 pub async fn update_password(
+    req: HttpRequest,
     body: web::Json<UpdatePasswordRequest>,
     request_claims: ReqData<Claims>,
     use_case: web::Data<UpdatePasswordUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.user_id, body.into_inner()).await {
+    let context = get_request_context(req).await;
+
+    match use_case
+        .execute(request_claims.user_id, body.into_inner(), context)
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Update password error: {}", e);
@@ -65,4 +78,3 @@ pub async fn update_password(
         }
     }
 }
-
@@ -38,7 +38,10 @@ pub async fn update_profile(
     request_claims: ReqData<Claims>,
     use_case: web::Data<UpdateProfileUseCase>,
 ) -> impl Responder {
-    match use_case.execute(request_claims.user_id, body.into_inner()).await {
+    match use_case
+        .execute(request_claims.user_id, body.into_inner())
+        .await
+    {
         Ok(response) => HttpResponse::Ok().json(response),
         Err(e) => {
             error!("Update profile error: {}", e);
@@ -49,4 +52,3 @@ pub async fn update_profile(
         }
     }
 }
-
@@ -12,19 +12,80 @@ pub mod core {
     }
 
     pub mod helpers {
+        pub mod account_disabled;
+        pub mod client_ip;
+        pub mod migrations;
         pub mod mock_now;
+        pub mod too_many_attempts;
     }
 
     pub mod structs {
+        pub mod envelope_cipher;
+        #[cfg(feature = "test-util")]
+        pub mod in_memory_database;
+        pub mod rate_limits;
         pub mod responses;
+        pub mod transaction;
+        pub mod unit_of_work;
     }
 
     pub mod middlewares {
+        pub mod permission_guard;
+        pub mod rate_limiter;
         pub mod token_validator;
     }
 }
 
 pub mod features {
+    pub mod admin {
+        pub mod application {
+            pub mod dto;
+            pub mod usecases;
+        }
+
+        pub mod domain {
+            pub mod entities;
+            pub mod errors;
+            pub mod repositories;
+        }
+
+        pub mod infrastructure {
+            pub mod models;
+            pub mod repositories;
+        }
+
+        pub mod presentation {
+            pub mod controllers;
+        }
+    }
+
+    pub mod audit {
+        pub mod application {
+            pub mod dto;
+            pub mod usecases;
+        }
+
+        pub mod domain {
+            pub mod entities;
+            pub mod errors;
+            pub mod repositories;
+            pub mod services;
+        }
+
+        pub mod helpers {
+            pub mod request_context;
+        }
+
+        pub mod infrastructure {
+            pub mod models;
+            pub mod repositories;
+        }
+
+        pub mod presentation {
+            pub mod controllers;
+        }
+    }
+
     pub mod auth {
         pub mod application {
             pub mod dto;
@@ -35,9 +96,11 @@ pub mod features {
             pub mod entities;
             pub mod errors;
             pub mod repositories;
+            pub mod services;
         }
 
         pub mod helpers {
+            pub mod account_status;
             pub mod errors;
             pub mod password;
             pub mod token;
@@ -45,8 +108,13 @@ pub mod features {
         }
 
         pub mod infrastructure {
+            pub mod keys;
             pub mod models;
+            pub mod notifiers;
+            pub mod otp_secrets;
             pub mod repositories;
+            pub mod session_activity;
+            pub mod token_cache;
         }
 
         pub mod presentation {
@@ -58,6 +126,22 @@ pub mod features {
         }
     }
 
+    pub mod events {
+        pub mod domain {
+            pub mod entities;
+            pub mod errors;
+            pub mod repositories;
+            pub mod services;
+        }
+
+        pub mod infrastructure {
+            pub mod handlers;
+            pub mod models;
+            pub mod repositories;
+            pub mod scheduler;
+        }
+    }
+
     pub mod profile {
         pub mod application {
             pub mod dto;
@@ -87,4 +171,26 @@ pub mod features {
             pub mod models;
         }
     }
+
+    pub mod maintenance {
+        pub mod application {
+            pub mod dto;
+            pub mod usecases;
+        }
+
+        pub mod domain {
+            pub mod entities;
+            pub mod errors;
+            pub mod repositories;
+        }
+
+        pub mod infrastructure {
+            pub mod repositories;
+            pub mod scheduler;
+        }
+
+        pub mod presentation {
+            pub mod controllers;
+        }
+    }
 }
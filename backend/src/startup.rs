@@ -1,35 +1,82 @@
 // Inspired by : https://github.com/actix/actix-web/issues/1147
 
 use std::net::TcpListener;
+use std::sync::Arc;
 
-use crate::configuration::{DatabaseSettings, Settings};
+use crate::configuration::{
+    DatabaseSettings, LoginThrottlingSettings, MaintenanceSettings, NotifierKind, Settings,
+    TokenCacheBackendKind,
+};
+use crate::core::helpers::migrations::run_migrations;
+use crate::core::middlewares::rate_limiter::RateLimiter;
 use crate::core::middlewares::token_validator::TokenValidator;
 use crate::core::routes::health_check::health_check;
+use crate::core::structs::envelope_cipher::EnvelopeCipher;
+use crate::core::structs::rate_limits::RateLimitStore;
+use crate::core::structs::transaction::PgUnitOfWork;
+use crate::features::admin::application::usecases::{
+    ExpireUserPasswordUseCase, GetUserSessionsUseCase, ListUsersUseCase, ResetUserOtpUseCase,
+    RevokeUserSessionsUseCase, SetUserRoleUseCase, SetUserStatusUseCase,
+};
+use crate::features::admin::infrastructure::repositories::{
+    SessionRepositoryImpl, UserRepositoryImpl as AdminUserRepositoryImpl,
+};
+use crate::features::admin::presentation::controllers::{
+    expire_user_password, get_user_sessions, grant_role, list_users, reset_user_otp, revoke_role,
+    revoke_user_sessions, set_user_status,
+};
+use crate::features::audit::application::usecases::ListSecurityEventsUseCase;
+use crate::features::audit::domain::services::AuditLog;
+use crate::features::audit::infrastructure::repositories::SecurityEventRepositoryImpl;
+use crate::features::audit::presentation::controllers::{
+    get_own_security_events, list_security_events,
+};
 use crate::features::auth::application::usecases::{
-    DisableOtpUseCase, GenerateOtpUseCase, LoginUseCase, LogoutUseCase,
-    RecoverAccountUsing2FAUseCase, RecoverAccountUsingPasswordUseCase,
-    RecoverAccountWithout2FAEnabledUseCase, RefreshTokenUseCase, SignupUseCase,
+    DeleteAccountUseCase, DisableOtpUseCase, GenerateOtpUseCase, GetJwksUseCase,
+    GetRemainingRecoveryCodesUseCase, LoginUseCase, LogoutUseCase, RecoverAccountUsing2FAUseCase,
+    RecoverAccountUsingPasswordUseCase, RecoverAccountWithout2FAEnabledUseCase,
+    RefreshTokenUseCase, RegenerateRecoveryCodesUseCase, ReportSignInUseCase, SignupUseCase,
     ValidateOtpUseCase, VerifyOtpUseCase,
 };
+use crate::features::auth::domain::repositories::Notifier;
+use crate::features::auth::domain::services::{
+    LoginThrottleService, LoginThrottleSettings, MfaChallengeService, MfaChallengeSettings,
+    NewDeviceService, NewDeviceSettings, OtpService, RecoveryCodeService, SessionService,
+    SessionSettings, TotpSettings,
+};
+use crate::features::auth::infrastructure::keys::JwtKeySet;
+use crate::features::auth::infrastructure::notifiers::{FileNotifier, LogNotifier};
 use crate::features::auth::infrastructure::repositories::{
-    TokenRepositoryImpl, TokenServiceImpl, UserRepositoryImpl,
+    LoginAttemptRepositoryImpl, MfaChallengeRepositoryImpl, PermissionRepositoryImpl,
+    RecoveryCodeRepositoryImpl, TokenRepositoryImpl, TokenServiceImpl, UserRepositoryImpl,
 };
+use crate::features::auth::infrastructure::session_activity::SessionActivityWriter;
+use crate::features::auth::infrastructure::token_cache::PostgresTokenCacheBackend;
 use crate::features::auth::presentation::controllers::{
-    disable_otp, generate_otp, login, logout, recover_account_using_2fa,
-    recover_account_using_password, recover_account_without_2fa_enabled, refresh_token, signup,
-    validate_otp, verify_otp,
+    delete_account, disable_otp, generate_otp, get_remaining_recovery_codes, jwks, login, logout,
+    recover_account_using_2fa, recover_account_using_password, recover_account_without_2fa_enabled,
+    refresh_token, regenerate_recovery_codes, report_sign_in, signup, validate_otp, verify_otp,
 };
-use crate::features::auth::structs::models::TokenCache;
+use crate::features::auth::structs::models::{InMemoryTokenCacheBackend, LockoutCache, TokenCache};
+use crate::features::events::domain::services::{DispatchSettings, EventDispatcher, Outbox};
+use crate::features::events::infrastructure::handlers::LogEventHandler;
+use crate::features::events::infrastructure::repositories::OutboxRepositoryImpl;
+use crate::features::events::infrastructure::scheduler::spawn_event_dispatcher;
+use crate::features::maintenance::application::usecases::{PurgeSettings, RunMaintenanceUseCase};
+use crate::features::maintenance::infrastructure::repositories::MaintenanceRepositoryImpl;
+use crate::features::maintenance::infrastructure::scheduler::spawn_maintenance;
+use crate::features::maintenance::presentation::controllers::run_maintenance;
 use crate::features::profile::application::usecases::{
-    DeleteDeviceUseCase, GetDevicesUseCase, GetProfileUseCase, IsOtpEnabledUseCase,
-    SetPasswordUseCase, UpdatePasswordUseCase, UpdateProfileUseCase,
+    DataExportLinkSettings, DeleteDeviceUseCase, DeleteOtherDevicesUseCase,
+    DownloadDataExportUseCase, GetDevicesUseCase, GetProfileUseCase, IsOtpEnabledUseCase,
+    RequestDataExportUseCase, SetPasswordUseCase, UpdatePasswordUseCase, UpdateProfileUseCase,
 };
 use crate::features::profile::infrastructure::repositories::{
-    DeviceRepositoryImpl, UserRepositoryImpl as ProfileUserRepositoryImpl,
+    DataExportRepositoryImpl, DeviceRepositoryImpl, UserRepositoryImpl as ProfileUserRepositoryImpl,
 };
 use crate::features::profile::presentation::controllers::{
-    delete_device, get_devices, get_profile, is_otp_enabled, set_password, update_password,
-    update_profile,
+    delete_device, delete_other_devices, download_data_export, get_devices, get_profile,
+    is_otp_enabled, request_data_export, set_password, update_password, update_profile,
 };
 use actix_cors::Cors;
 use actix_http::header::HeaderName;
@@ -38,16 +85,42 @@ use actix_web::dev::{Server, ServiceFactory, ServiceRequest, ServiceResponse};
 use actix_web::http::header;
 use actix_web::middleware::Logger;
 use actix_web::{web, App, Error, HttpServer};
+use chrono::Duration;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{PgPool, Pool, Postgres};
 
-pub fn run(listener: TcpListener, configuration: Settings) -> Result<Server, std::io::Error> {
-    let token_cache = TokenCache::default();
-    let connection_pool = get_connection_pool(&configuration.database);
-    let secret = configuration.application.secret;
+// How often the caches local to the process drop the entries they no longer need
+const IN_MEMORY_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+pub fn run(
+    listener: TcpListener,
+    configuration: Settings,
+    connection_pool: PgPool,
+    token_cache: TokenCache,
+) -> Result<Server, std::io::Error> {
+    let lockout_cache = LockoutCache::default();
+    lockout_cache
+        .clone()
+        .spawn_sweeper(IN_MEMORY_SWEEP_INTERVAL);
+    let rate_limit_store = RateLimitStore::default();
+    rate_limit_store
+        .clone()
+        .spawn_sweeper(IN_MEMORY_SWEEP_INTERVAL);
+    let session_activity_writer = SessionActivityWriter::new(
+        connection_pool.clone(),
+        configuration.session_activity.clone(),
+    );
+    session_activity_writer.clone().spawn();
 
     let server = HttpServer::new(move || {
-        create_app(connection_pool.clone(), secret.clone(), token_cache.clone())
+        create_app(
+            connection_pool.clone(),
+            configuration.clone(),
+            token_cache.clone(),
+            lockout_cache.clone(),
+            rate_limit_store.clone(),
+            session_activity_writer.clone(),
+        )
     })
     .listen(listener)?
     .run();
@@ -57,8 +130,11 @@ pub fn run(listener: TcpListener, configuration: Settings) -> Result<Server, std
 
 pub fn create_app(
     connection_pool: Pool<Postgres>,
-    secret: String,
+    configuration: Settings,
     token_cache: TokenCache,
+    lockout_cache: LockoutCache,
+    rate_limit_store: RateLimitStore,
+    session_activity_writer: SessionActivityWriter,
 ) -> App<
     impl ServiceFactory<
         ServiceRequest,
@@ -71,7 +147,7 @@ pub fn create_app(
     let cors = Cors::default()
         .allow_any_origin()
         // .allowed_origin("localhost:3000")
-        .allowed_methods(vec!["GET", "POST", "DELETE"])
+        .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
         .allowed_headers(vec![
             header::CONTENT_TYPE,
             header::AUTHORIZATION,
@@ -80,82 +156,357 @@ pub fn create_app(
         ])
         .supports_credentials();
 
+    let rate_limits = configuration.rate_limits;
+    let session_settings = SessionSettings {
+        access_token_lifetime: Duration::minutes(
+            configuration.application.access_token_lifetime_minutes,
+        ),
+        refresh_token_lifetime: Duration::days(
+            configuration.application.refresh_token_lifetime_days,
+        ),
+        issuer: configuration.application.token_issuer.clone(),
+        audience: configuration.application.token_audience.clone(),
+    };
+    let mfa_challenge_settings = MfaChallengeSettings {
+        lifetime: Duration::seconds(configuration.application.mfa_challenge_lifetime_seconds),
+        issuer: configuration.application.token_issuer.clone(),
+        audience: configuration.application.token_audience.clone(),
+    };
+    let data_export_link_settings = DataExportLinkSettings {
+        lifetime: Duration::hours(configuration.data_export.lifetime_hours),
+        build_timeout: Duration::minutes(configuration.data_export.build_timeout_minutes),
+        issuer: configuration.application.token_issuer.clone(),
+        audience: configuration.application.token_audience.clone(),
+    };
+    let new_device_settings = NewDeviceSettings {
+        report_link_lifetime: Duration::hours(
+            configuration.new_device_alerts.report_link_lifetime_hours,
+        ),
+        issuer: configuration.application.token_issuer.clone(),
+        audience: configuration.application.token_audience.clone(),
+    };
+    let login_throttle_settings = LoginThrottleSettings {
+        account_max_attempts: configuration.login_throttling.account_max_attempts,
+        ip_max_attempts: configuration.login_throttling.ip_max_attempts,
+        window: Duration::seconds(configuration.login_throttling.window_seconds),
+        lockout: Duration::seconds(configuration.login_throttling.lockout_seconds),
+    };
+    let totp_settings = TotpSettings {
+        algorithm: configuration.otp.algorithm.into(),
+        digits: configuration.otp.digits,
+        skew: configuration.otp.skew,
+        step_seconds: configuration.otp.step_seconds,
+    };
+
     // Initialize repositories
-    let user_repo_impl = UserRepositoryImpl::new(connection_pool.clone());
+    let envelope_cipher =
+        EnvelopeCipher::new(&configuration.encryption).expect("Failed to load encryption keys");
+    let user_repo_impl = UserRepositoryImpl::new(connection_pool.clone(), envelope_cipher.clone());
     let token_repo_impl = TokenRepositoryImpl::new(connection_pool.clone());
-    let token_service_impl = TokenServiceImpl::new(secret.as_bytes().to_vec());
+    let security_event_repo_impl = SecurityEventRepositoryImpl::new(connection_pool.clone());
+    let login_attempt_repo_impl = LoginAttemptRepositoryImpl::new(connection_pool.clone());
+    let mfa_challenge_repo_impl = MfaChallengeRepositoryImpl::new(connection_pool.clone());
+    let recovery_code_repo_impl = RecoveryCodeRepositoryImpl::new(connection_pool.clone());
+    let permission_repo_impl = PermissionRepositoryImpl::new(connection_pool.clone());
+    let outbox_repo_impl = OutboxRepositoryImpl::new(connection_pool.clone());
+    let unit_of_work = PgUnitOfWork::new(connection_pool.clone());
+    let notifier: Arc<dyn Notifier> = match configuration.new_device_alerts.notifier {
+        NotifierKind::Log => Arc::new(LogNotifier),
+        NotifierKind::File => Arc::new(FileNotifier::new(
+            &configuration.new_device_alerts.outbox_path,
+        )),
+    };
+    let jwt_key_set = JwtKeySet::load(&configuration.jwt).expect("Failed to load JWT keys");
+    let token_service_impl = TokenServiceImpl::new(
+        jwt_key_set,
+        configuration.application.token_issuer,
+        configuration.application.token_audience,
+    );
+
+    // Initialize domain services
+    let new_session_service = || {
+        SessionService::new(
+            Box::new(token_repo_impl.clone()),
+            Box::new(token_service_impl.clone()),
+            Box::new(permission_repo_impl.clone()),
+            session_settings.clone(),
+        )
+    };
+    let new_login_throttle_service = || {
+        LoginThrottleService::new(
+            Box::new(login_attempt_repo_impl.clone()),
+            lockout_cache.clone(),
+            login_throttle_settings.clone(),
+        )
+    };
+    let new_mfa_challenge_service = || {
+        MfaChallengeService::new(
+            Box::new(mfa_challenge_repo_impl.clone()),
+            Box::new(token_service_impl.clone()),
+            mfa_challenge_settings.clone(),
+        )
+    };
+    let new_otp_service =
+        || OtpService::new(Box::new(user_repo_impl.clone()), totp_settings.clone());
+    let new_recovery_code_service =
+        || RecoveryCodeService::new(Box::new(recovery_code_repo_impl.clone()));
+    let new_new_device_service = || {
+        NewDeviceService::new(
+            Box::new(token_repo_impl.clone()),
+            Box::new(token_service_impl.clone()),
+            notifier.clone(),
+            new_device_settings.clone(),
+        )
+    };
+    let new_audit_log = || AuditLog::new(Box::new(security_event_repo_impl.clone()));
+    let new_outbox = || Outbox::new(Box::new(outbox_repo_impl.clone()));
 
     // Initialize use cases
     let signup_use_case = SignupUseCase::new(
         Box::new(user_repo_impl.clone()),
-        Box::new(token_repo_impl.clone()),
-        Box::new(token_service_impl.clone()),
-        secret.as_bytes().to_vec(),
+        new_session_service(),
+        new_recovery_code_service(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
     let login_use_case = LoginUseCase::new(
         Box::new(user_repo_impl.clone()),
-        Box::new(token_repo_impl.clone()),
-        Box::new(token_service_impl.clone()),
+        new_session_service(),
+        new_login_throttle_service(),
+        new_mfa_challenge_service(),
+        new_audit_log(),
+        new_new_device_service(),
+        Box::new(unit_of_work.clone()),
     );
     let refresh_token_use_case = RefreshTokenUseCase::new(
+        Box::new(user_repo_impl.clone()),
         Box::new(token_repo_impl.clone()),
         Box::new(token_service_impl.clone()),
+        new_audit_log(),
+        new_session_service(),
+        token_cache.clone(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let get_jwks_use_case = GetJwksUseCase::new(Box::new(token_service_impl.clone()));
+    let generate_otp_use_case =
+        GenerateOtpUseCase::new(Box::new(user_repo_impl.clone()), new_otp_service());
+    let verify_otp_use_case = VerifyOtpUseCase::new(
+        Box::new(user_repo_impl.clone()),
+        new_otp_service(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
-    let generate_otp_use_case = GenerateOtpUseCase::new(Box::new(user_repo_impl.clone()));
-    let verify_otp_use_case = VerifyOtpUseCase::new(Box::new(user_repo_impl.clone()));
     let validate_otp_use_case = ValidateOtpUseCase::new(
         Box::new(user_repo_impl.clone()),
+        new_session_service(),
+        new_login_throttle_service(),
+        new_otp_service(),
+        new_mfa_challenge_service(),
+        new_audit_log(),
+        new_new_device_service(),
+        Box::new(unit_of_work.clone()),
+    );
+    let disable_otp_use_case = DisableOtpUseCase::new(
+        Box::new(user_repo_impl.clone()),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let logout_use_case = LogoutUseCase::new(
+        Box::new(token_repo_impl.clone()),
+        token_cache.clone(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let report_sign_in_use_case = ReportSignInUseCase::new(
         Box::new(token_repo_impl.clone()),
         Box::new(token_service_impl.clone()),
+        token_cache.clone(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
-    let disable_otp_use_case = DisableOtpUseCase::new(Box::new(user_repo_impl.clone()));
-    let logout_use_case = LogoutUseCase::new(Box::new(token_repo_impl.clone()), token_cache.clone());
     let recover_account_without_2fa_enabled_use_case = RecoverAccountWithout2FAEnabledUseCase::new(
         Box::new(user_repo_impl.clone()),
         Box::new(token_repo_impl.clone()),
-        Box::new(token_service_impl.clone()),
+        new_session_service(),
+        new_login_throttle_service(),
+        new_recovery_code_service(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
     let recover_account_using_password_use_case = RecoverAccountUsingPasswordUseCase::new(
         Box::new(user_repo_impl.clone()),
         Box::new(token_repo_impl.clone()),
-        Box::new(token_service_impl.clone()),
+        new_session_service(),
+        new_login_throttle_service(),
+        new_recovery_code_service(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
     let recover_account_using_2fa_use_case = RecoverAccountUsing2FAUseCase::new(
         Box::new(user_repo_impl.clone()),
         Box::new(token_repo_impl.clone()),
-        Box::new(token_service_impl.clone()),
+        new_session_service(),
+        new_login_throttle_service(),
+        new_recovery_code_service(),
+        new_otp_service(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let regenerate_recovery_codes_use_case = RegenerateRecoveryCodesUseCase::new(
+        Box::new(user_repo_impl.clone()),
+        new_login_throttle_service(),
+        new_recovery_code_service(),
+        new_audit_log(),
+    );
+    let get_remaining_recovery_codes_use_case =
+        GetRemainingRecoveryCodesUseCase::new(new_recovery_code_service());
+    let delete_account_use_case = DeleteAccountUseCase::new(
+        Box::new(user_repo_impl.clone()),
+        Box::new(token_repo_impl.clone()),
+        new_login_throttle_service(),
+        new_otp_service(),
+        token_cache.clone(),
+        Duration::days(configuration.account_deletion.grace_period_days),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
     );
 
     // Initialize profile repositories
-    let profile_user_repo_impl = ProfileUserRepositoryImpl::new(connection_pool.clone());
+    let profile_user_repo_impl =
+        ProfileUserRepositoryImpl::new(connection_pool.clone(), envelope_cipher.clone());
     let device_repo_impl = DeviceRepositoryImpl::new(connection_pool.clone());
+    let data_export_repo_impl =
+        DataExportRepositoryImpl::new(connection_pool.clone(), envelope_cipher.clone());
 
     // Initialize profile use cases
     let get_profile_use_case = GetProfileUseCase::new(Box::new(profile_user_repo_impl.clone()));
     let update_profile_use_case =
         UpdateProfileUseCase::new(Box::new(profile_user_repo_impl.clone()));
-    let set_password_use_case = SetPasswordUseCase::new(Box::new(profile_user_repo_impl.clone()));
-    let update_password_use_case =
-        UpdatePasswordUseCase::new(Box::new(profile_user_repo_impl.clone()));
+    let set_password_use_case = SetPasswordUseCase::new(
+        Box::new(profile_user_repo_impl.clone()),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let update_password_use_case = UpdatePasswordUseCase::new(
+        Box::new(profile_user_repo_impl.clone()),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
     let get_devices_use_case = GetDevicesUseCase::new(
         Box::new(device_repo_impl.clone()),
+        session_activity_writer.clone(),
+    );
+    let delete_device_use_case = DeleteDeviceUseCase::new(
+        Box::new(device_repo_impl.clone()),
+        token_cache.clone(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let delete_other_devices_use_case = DeleteOtherDevicesUseCase::new(
+        Box::new(device_repo_impl.clone()),
+        token_cache.clone(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let request_data_export_use_case = RequestDataExportUseCase::new(
+        Box::new(data_export_repo_impl.clone()),
+        Box::new(profile_user_repo_impl.clone()),
+        Box::new(device_repo_impl),
+        Box::new(security_event_repo_impl.clone()),
+        session_activity_writer.clone(),
+        Box::new(token_service_impl.clone()),
+        data_export_link_settings,
+    );
+    let download_data_export_use_case = DownloadDataExportUseCase::new(
+        Box::new(data_export_repo_impl),
+        Box::new(token_service_impl.clone()),
+    );
+    let is_otp_enabled_use_case =
+        IsOtpEnabledUseCase::new(Box::new(profile_user_repo_impl.clone()));
+
+    // Initialize admin repositories
+    let admin_user_repo_impl = AdminUserRepositoryImpl::new(connection_pool.clone());
+    let session_repo_impl = SessionRepositoryImpl::new(connection_pool.clone());
+
+    // Initialize admin use cases
+    let list_users_use_case = ListUsersUseCase::new(Box::new(admin_user_repo_impl.clone()));
+    let get_user_sessions_use_case = GetUserSessionsUseCase::new(
+        Box::new(admin_user_repo_impl.clone()),
+        Box::new(session_repo_impl.clone()),
+        session_activity_writer.clone(),
+    );
+    let revoke_user_sessions_use_case = RevokeUserSessionsUseCase::new(
+        Box::new(admin_user_repo_impl.clone()),
+        Box::new(session_repo_impl.clone()),
+        token_cache.clone(),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let expire_user_password_use_case =
+        ExpireUserPasswordUseCase::new(Box::new(admin_user_repo_impl.clone()), new_audit_log());
+    let reset_user_otp_use_case = ResetUserOtpUseCase::new(
+        Box::new(admin_user_repo_impl.clone()),
+        new_audit_log(),
+        Box::new(unit_of_work.clone()),
+        new_outbox(),
+    );
+    let set_user_role_use_case = SetUserRoleUseCase::new(
+        Box::new(admin_user_repo_impl.clone()),
+        Box::new(session_repo_impl.clone()),
+        token_cache.clone(),
+        Box::new(unit_of_work.clone()),
+        new_audit_log(),
+        new_outbox(),
+    );
+    let set_user_status_use_case = SetUserStatusUseCase::new(
+        Box::new(admin_user_repo_impl),
+        Box::new(session_repo_impl),
         token_cache.clone(),
+        Box::new(unit_of_work.clone()),
+        new_audit_log(),
+        new_outbox(),
+    );
+
+    // Initialize audit use cases
+    let list_security_events_use_case =
+        ListSecurityEventsUseCase::new(Box::new(security_event_repo_impl));
+
+    // Initialize maintenance use cases
+    let run_maintenance_use_case = new_run_maintenance_use_case(
+        &connection_pool,
+        &configuration.maintenance,
+        &configuration.login_throttling,
     );
-    let delete_device_use_case = DeleteDeviceUseCase::new(Box::new(device_repo_impl));
-    let is_otp_enabled_use_case = IsOtpEnabledUseCase::new(Box::new(profile_user_repo_impl.clone()));
 
     App::new()
+        .service(jwks)
         .service(
             web::scope("/api")
                 .service(health_check)
                 .service(
                     web::scope("/auth")
+                        .wrap(RateLimiter::new("auth", rate_limits.auth))
                         .service(signup)
                         .service(login)
                         .service(recover_account_without_2fa_enabled)
                         .service(recover_account_using_password)
                         .service(recover_account_using_2fa)
                         .service(refresh_token)
+                        // Unauthenticated, as whoever signed in may know the password, the link is signed
+                        .service(report_sign_in)
                         .service(
                             web::scope("/logout")
                                 .wrap(TokenValidator {})
@@ -177,41 +528,83 @@ pub fn create_app(
                 )
                 .service(
                     web::scope("/users")
-                        // Scope without middleware applied to routes that don't need it
-                        .service(is_otp_enabled)
+                        // Unauthenticated and cheap to call, so it gets its own tighter limit
+                        .service(
+                            web::scope("/is-otp-enabled")
+                                .wrap(RateLimiter::new(
+                                    "is_otp_enabled",
+                                    rate_limits.is_otp_enabled,
+                                ))
+                                .service(is_otp_enabled),
+                        )
+                        // The token is signed, so it is checked before any database access
+                        .service(web::scope("/me/export/download").service(download_data_export))
                         // Nested scope with middleware for protected routes
                         .service(
                             web::scope("")
+                                .wrap(RateLimiter::new("users", rate_limits.users))
                                 .wrap(TokenValidator {})
                                 .service(get_profile)
                                 .service(update_profile)
                                 .service(set_password)
-                                .service(update_password),
+                                .service(update_password)
+                                .service(delete_account)
+                                .service(request_data_export)
+                                .service(get_own_security_events)
+                                .service(regenerate_recovery_codes)
+                                .service(get_remaining_recovery_codes),
                         ),
                 )
                 .service(
                     web::scope("/devices")
+                        .wrap(RateLimiter::new("devices", rate_limits.devices))
                         .wrap(TokenValidator {})
                         .service(get_devices)
+                        .service(delete_other_devices)
                         .service(delete_device),
+                )
+                .service(
+                    web::scope("/admin")
+                        // Each route checks its own permission
+                        .wrap(TokenValidator {})
+                        .service(
+                            web::scope("/users")
+                                .service(list_users)
+                                .service(get_user_sessions)
+                                .service(revoke_user_sessions)
+                                .service(expire_user_password)
+                                .service(reset_user_otp)
+                                .service(set_user_status)
+                                .service(grant_role)
+                                .service(revoke_role),
+                        )
+                        .service(list_security_events)
+                        .service(run_maintenance),
                 ),
         )
         .wrap(cors)
         .wrap(Logger::default())
         .app_data(web::Data::new(connection_pool))
-        .app_data(web::Data::new(secret))
+        .app_data(web::Data::new(token_service_impl))
         .app_data(web::Data::new(token_cache))
+        .app_data(web::Data::new(session_activity_writer))
+        .app_data(web::Data::new(rate_limit_store))
         .app_data(web::Data::new(signup_use_case))
         .app_data(web::Data::new(login_use_case))
         .app_data(web::Data::new(refresh_token_use_case))
+        .app_data(web::Data::new(get_jwks_use_case))
         .app_data(web::Data::new(generate_otp_use_case))
         .app_data(web::Data::new(verify_otp_use_case))
         .app_data(web::Data::new(validate_otp_use_case))
         .app_data(web::Data::new(disable_otp_use_case))
         .app_data(web::Data::new(logout_use_case))
+        .app_data(web::Data::new(report_sign_in_use_case))
         .app_data(web::Data::new(recover_account_without_2fa_enabled_use_case))
         .app_data(web::Data::new(recover_account_using_password_use_case))
         .app_data(web::Data::new(recover_account_using_2fa_use_case))
+        .app_data(web::Data::new(regenerate_recovery_codes_use_case))
+        .app_data(web::Data::new(get_remaining_recovery_codes_use_case))
+        .app_data(web::Data::new(delete_account_use_case))
         .app_data(web::Data::new(get_profile_use_case))
         .app_data(web::Data::new(update_profile_use_case))
         .app_data(web::Data::new(set_password_use_case))
@@ -219,6 +612,33 @@ pub fn create_app(
         .app_data(web::Data::new(is_otp_enabled_use_case))
         .app_data(web::Data::new(get_devices_use_case))
         .app_data(web::Data::new(delete_device_use_case))
+        .app_data(web::Data::new(delete_other_devices_use_case))
+        .app_data(web::Data::new(request_data_export_use_case))
+        .app_data(web::Data::new(download_data_export_use_case))
+        .app_data(web::Data::new(list_users_use_case))
+        .app_data(web::Data::new(get_user_sessions_use_case))
+        .app_data(web::Data::new(revoke_user_sessions_use_case))
+        .app_data(web::Data::new(expire_user_password_use_case))
+        .app_data(web::Data::new(reset_user_otp_use_case))
+        .app_data(web::Data::new(set_user_role_use_case))
+        .app_data(web::Data::new(set_user_status_use_case))
+        .app_data(web::Data::new(list_security_events_use_case))
+        .app_data(web::Data::new(run_maintenance_use_case))
+}
+
+// Shared by the admin route and the scheduler started by `Application`
+fn new_run_maintenance_use_case(
+    connection_pool: &PgPool,
+    maintenance: &MaintenanceSettings,
+    login_throttling: &LoginThrottlingSettings,
+) -> RunMaintenanceUseCase {
+    RunMaintenanceUseCase::new(
+        Box::new(MaintenanceRepositoryImpl::new(connection_pool.clone())),
+        PurgeSettings {
+            batch_size: maintenance.batch_size,
+            login_attempt_window: Duration::seconds(login_throttling.window_seconds),
+        },
+    )
 }
 
 pub struct Application {
@@ -234,7 +654,63 @@ impl Application {
         );
         let listener = TcpListener::bind(address)?;
         let port = listener.local_addr().unwrap().port();
-        let server = run(listener, configuration).unwrap();
+
+        // The database is migrated before serving any request
+        let connection_pool = get_connection_pool(&configuration.database);
+        let envelope_cipher =
+            EnvelopeCipher::new(&configuration.encryption).map_err(std::io::Error::other)?;
+        run_migrations(&connection_pool, &envelope_cipher)
+            .await
+            .map_err(std::io::Error::other)?;
+
+        let token_cache_settings = &configuration.token_cache;
+        let local_token_cache = InMemoryTokenCacheBackend::new(token_cache_settings.max_entries);
+        let token_cache = match token_cache_settings.backend {
+            TokenCacheBackendKind::Memory => TokenCache::new(Arc::new(local_token_cache)),
+            TokenCacheBackendKind::Postgres => {
+                let backend =
+                    PostgresTokenCacheBackend::new(connection_pool.clone(), local_token_cache);
+                backend
+                    .clone()
+                    .listen()
+                    .await
+                    .map_err(std::io::Error::other)?;
+                TokenCache::new(backend)
+            }
+        };
+        token_cache
+            .clone()
+            .spawn_sweeper(std::time::Duration::from_secs(
+                token_cache_settings.sweep_interval_seconds,
+            ));
+
+        spawn_maintenance(
+            Arc::new(new_run_maintenance_use_case(
+                &connection_pool,
+                &configuration.maintenance,
+                &configuration.login_throttling,
+            )),
+            std::time::Duration::from_secs(configuration.maintenance.interval_seconds),
+        );
+
+        let events = &configuration.events;
+        let dispatcher = EventDispatcher::new(
+            Box::new(OutboxRepositoryImpl::new(connection_pool.clone())),
+            DispatchSettings {
+                batch_size: events.batch_size,
+                lease: Duration::seconds(events.lease_seconds),
+                retry_delay: Duration::seconds(events.retry_delay_seconds),
+                max_retry_delay: Duration::seconds(events.max_retry_delay_seconds),
+                max_attempts: events.max_attempts,
+            },
+        )
+        .with_handler(Arc::new(LogEventHandler));
+        spawn_event_dispatcher(
+            Arc::new(dispatcher),
+            std::time::Duration::from_secs(events.dispatch_interval_seconds),
+        );
+
+        let server = run(listener, configuration, connection_pool, token_cache).unwrap();
 
         Ok(Self { port, server })
     }
@@ -0,0 +1,272 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::{self, ContentType};
+use actix_web::http::Method;
+use actix_web::{test, Error};
+use chrono::{Duration, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::features::admin::application::dto::{
+    AdminActionResponse, SetUserStatusRequest, UsersResponse,
+};
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, RecoverAccountWithout2FAEnabledRequest, RefreshTokenRequest,
+};
+use flutteractixapp::features::auth::domain::entities::AccountStatus;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::admin::users::{admin_and_user_sign_up, admin_sends_a_request, assert_error, assert_ok};
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{
+    user_generates_otp, user_logs_in_with_otp_enabled, user_validates_otp, user_verifies_otp,
+};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app;
+use crate::profile::devices::assert_access_token_is_revoked;
+
+pub async fn admin_sets_user_status(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    admin_access_token: &str,
+    user_id: Uuid,
+    request: SetUserStatusRequest,
+) -> ServiceResponse<impl MessageBody> {
+    admin_sends_a_request(
+        app,
+        admin_access_token,
+        test::TestRequest::put()
+            .uri(&format!("/api/admin/users/{}/status", user_id))
+            .insert_header(ContentType::json())
+            .set_json(&request),
+    )
+    .await
+}
+
+async fn user_tries_to_log_in(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn status_is_set_in_the_database(pool: &PgPool, username: &str, status: &str) {
+    sqlx::query("UPDATE users SET status = $2 WHERE username = $1")
+        .bind(username)
+        .bind(status)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test]
+async fn suspended_user_is_signed_out_and_cannot_log_in(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, user_access_token, user_id) =
+        admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sets_user_status(
+        &app,
+        &admin_access_token,
+        user_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Suspended,
+            reason: Some("Spam".to_string()),
+            until: None,
+        },
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "ACCOUNT_STATUS_UPDATED");
+    assert_access_token_is_revoked(&app, &user_access_token).await;
+    assert_error(user_tries_to_log_in(&app).await, 403, "ACCOUNT_DISABLED").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?search=testusername"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+
+    assert_eq!(response.users[0].status, AccountStatus::Suspended);
+    assert_eq!(response.users[0].status_reason.as_deref(), Some("Spam"));
+}
+
+#[sqlx::test]
+async fn suspension_ends_at_its_end_date(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+    let start = Utc::now();
+
+    let response = admin_sets_user_status(
+        &app,
+        &admin_access_token,
+        user_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Suspended,
+            reason: None,
+            until: Some(start + Duration::days(1)),
+        },
+    )
+    .await;
+    assert_ok::<AdminActionResponse>(response).await;
+    assert_error(user_tries_to_log_in(&app).await, 403, "ACCOUNT_DISABLED").await;
+
+    override_now(Some((start + Duration::days(2)).fixed_offset()));
+    user_logs_in(&app, "testusername", "password1_").await;
+    override_now(None);
+}
+
+#[sqlx::test]
+async fn reactivated_user_can_log_in_again(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+
+    for status in [AccountStatus::Banned, AccountStatus::Active] {
+        let response = admin_sets_user_status(
+            &app,
+            &admin_access_token,
+            user_id,
+            SetUserStatusRequest {
+                status,
+                reason: None,
+                until: None,
+            },
+        )
+        .await;
+        assert_ok::<AdminActionResponse>(response).await;
+    }
+
+    user_logs_in(&app, "testusername", "password1_").await;
+}
+
+#[sqlx::test]
+async fn banned_user_cannot_recover_their_account(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (_, _, recovery_codes) = user_signs_up(&app).await;
+    status_is_set_in_the_database(&pool, "testusername", "banned").await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/recover")
+        .insert_header(ContentType::json())
+        .set_json(&RecoverAccountWithout2FAEnabledRequest {
+            username: "testusername".to_string(),
+            recovery_code: recovery_codes[0].clone(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_error(response, 403, "ACCOUNT_DISABLED").await;
+}
+
+#[sqlx::test]
+async fn disabled_user_cannot_use_their_tokens(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, refresh_token, _) = user_signs_up(&app).await;
+    status_is_set_in_the_database(&pool, "testusername", "suspended").await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    assert_error(response, 403, "ACCOUNT_DISABLED").await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/refresh-token")
+        .insert_header(ContentType::json())
+        .set_json(&RefreshTokenRequest { refresh_token })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    assert_error(response, 403, "ACCOUNT_DISABLED").await;
+}
+
+#[sqlx::test]
+async fn disabled_user_cannot_complete_the_second_factor(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    status_is_set_in_the_database(&pool, "testusername", "banned").await;
+
+    let response = user_validates_otp(&app, &mfa_token, "000000").await;
+
+    assert_error(response, 403, "ACCOUNT_DISABLED").await;
+}
+
+#[sqlx::test]
+async fn admin_cannot_change_their_own_status(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, _) = admin_and_user_sign_up(&app, &pool).await;
+    let admin_id: Uuid = sqlx::query_scalar("SELECT id FROM users WHERE username = $1")
+        .bind("otherusername")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let response = admin_sets_user_status(
+        &app,
+        &admin_access_token,
+        admin_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Suspended,
+            reason: None,
+            until: None,
+        },
+    )
+    .await;
+
+    assert_error(response, 400, "CANNOT_CHANGE_OWN_STATUS").await;
+}
+
+#[sqlx::test]
+async fn status_with_an_end_date_in_the_past_is_rejected(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sets_user_status(
+        &app,
+        &admin_access_token,
+        user_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Suspended,
+            reason: None,
+            until: Some(Utc::now() - Duration::hours(1)),
+        },
+    )
+    .await;
+
+    assert_error(response, 400, "INVALID_ACCOUNT_STATUS").await;
+}
+
+#[sqlx::test]
+async fn status_route_passes_the_cors_preflight(pool: PgPool) {
+    let app = spawn_app(pool).await;
+
+    let req = test::TestRequest::default()
+        .method(Method::OPTIONS)
+        .uri(&format!("/api/admin/users/{}/status", Uuid::new_v4()))
+        .insert_header((header::ORIGIN, "http://localhost:3000"))
+        .insert_header((header::ACCESS_CONTROL_REQUEST_METHOD, "PUT"))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+    let allowed_methods = response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_METHODS)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(allowed_methods.contains("PUT"));
+}
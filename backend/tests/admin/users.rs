@@ -0,0 +1,362 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::{self, ContentType};
+use actix_web::{test, Error};
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::admin::application::dto::{
+    AdminActionResponse, SessionsResponse, UsersResponse,
+};
+use flutteractixapp::features::auth::application::dto::LoginRequest;
+use serde::de::DeserializeOwned;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{user_generates_otp, user_logs_in_with_otp_enabled, user_verifies_otp};
+use crate::auth::signup::user_signs_up;
+use crate::auth::token::user_refreshes_token;
+use crate::helpers::{spawn_app, user_becomes_admin, user_is_granted_role};
+use crate::profile::devices::{another_user_signs_up, assert_access_token_is_revoked};
+use crate::profile::profile::user_has_access_to_protected_route;
+
+pub async fn admin_sends_a_request(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+    req: test::TestRequest,
+) -> ServiceResponse<impl MessageBody> {
+    let req = req
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+pub async fn assert_ok<T: DeserializeOwned>(response: ServiceResponse<impl MessageBody>) -> T {
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    serde_json::from_slice(&body).unwrap()
+}
+
+pub async fn assert_error(response: ServiceResponse<impl MessageBody>, status: u16, code: &str) {
+    assert_eq!(status, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, code);
+}
+
+// "otherusername" is the admin, "testusername" the managed user
+pub async fn admin_and_user_sign_up(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    pool: &PgPool,
+) -> (String, String, Uuid) {
+    let (user_access_token, _, _) = user_signs_up(&app).await;
+    another_user_signs_up(&app).await;
+    user_becomes_admin(pool, "otherusername").await;
+    let (admin_access_token, _) = user_logs_in(&app, "otherusername", "password1_").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?search=testusername"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+    let user_id = response.users[0].id;
+
+    (admin_access_token, user_access_token, user_id)
+}
+
+#[sqlx::test]
+async fn admin_routes_are_forbidden_to_users_who_are_not_admins(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/admin/users"),
+    )
+    .await;
+
+    assert_error(response, 403, "FORBIDDEN").await;
+}
+
+#[sqlx::test]
+async fn admin_can_list_and_search_users(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, _) = admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?page=2&per_page=1"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "USERS_FETCHED");
+    assert_eq!(response.total, 2);
+    assert_eq!(response.page, 2);
+    assert_eq!(response.users.len(), 1);
+    assert_eq!(response.users[0].username, "otherusername");
+    assert_eq!(response.users[0].roles, vec!["admin".to_string()]);
+
+    // Wildcards are matched literally
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?search=%25other"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+
+    assert_eq!(response.total, 0);
+    assert!(response.users.is_empty());
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?search=OTHER"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+
+    assert_eq!(response.total, 1);
+    assert_eq!(response.users[0].username, "otherusername");
+}
+
+#[sqlx::test]
+async fn admin_can_sign_a_user_out_of_every_device(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, user_access_token, user_id) =
+        admin_and_user_sign_up(&app, &pool).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+    user_has_access_to_protected_route(&app, &user_access_token).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri(&format!("/api/admin/users/{}/sessions", user_id)),
+    )
+    .await;
+    let response: SessionsResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "SESSIONS_FETCHED");
+    assert_eq!(response.sessions.len(), 2);
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", user_id)),
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "SESSIONS_REVOKED");
+    assert_access_token_is_revoked(&app, &user_access_token).await;
+}
+
+#[sqlx::test]
+async fn admin_can_expire_the_password_of_a_user(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/password/expire", user_id)),
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "PASSWORD_EXPIRED");
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_error(response, 403, "PASSWORD_MUST_BE_CHANGED").await;
+}
+
+#[sqlx::test]
+async fn admin_can_reset_the_2fa_of_a_user(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, user_access_token, user_id) =
+        admin_and_user_sign_up(&app, &pool).await;
+    let otp_base32 = user_generates_otp(&app, &user_access_token).await;
+    user_verifies_otp(&app, &user_access_token, &otp_base32).await;
+    user_logs_in_with_otp_enabled(&app).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/otp/reset", user_id)),
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "OTP_RESET");
+
+    // The password is enough to log in again
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn admin_can_grant_and_revoke_a_role(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+    let (_, refresh_token) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/roles/admin", user_id)),
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "ROLE_GRANTED");
+
+    // The role applies once the tokens are refreshed
+    let (granted_access_token, _) = user_refreshes_token(&app, &refresh_token).await;
+    let response = admin_sends_a_request(
+        &app,
+        &granted_access_token,
+        test::TestRequest::get().uri("/api/admin/users"),
+    )
+    .await;
+    assert_ok::<UsersResponse>(response).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/roles/admin", user_id)),
+    )
+    .await;
+    let response: AdminActionResponse = assert_ok(response).await;
+
+    assert_eq!(response.code, "ROLE_REVOKED");
+
+    // The tokens issued with the role are revoked
+    assert_access_token_is_revoked(&app, &granted_access_token).await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/admin/users"),
+    )
+    .await;
+    assert_error(response, 403, "FORBIDDEN").await;
+}
+
+#[sqlx::test]
+async fn support_role_can_only_read_user_data(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (_, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+    user_is_granted_role(&pool, "testusername", "support").await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/admin/users"),
+    )
+    .await;
+    assert_ok::<UsersResponse>(response).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri(&format!("/api/admin/users/{}/sessions", user_id)),
+    )
+    .await;
+    assert_ok::<SessionsResponse>(response).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/password/expire", user_id)),
+    )
+    .await;
+    assert_error(response, 403, "FORBIDDEN").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", user_id)),
+    )
+    .await;
+    assert_error(response, 403, "FORBIDDEN").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::post().uri("/api/admin/maintenance/run"),
+    )
+    .await;
+    assert_error(response, 403, "FORBIDDEN").await;
+}
+
+#[sqlx::test]
+async fn unknown_role_cannot_be_granted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/roles/superuser", user_id)),
+    )
+    .await;
+
+    assert_error(response, 404, "ROLE_NOT_FOUND").await;
+}
+
+#[sqlx::test]
+async fn admin_cannot_revoke_their_own_role(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, _) = admin_and_user_sign_up(&app, &pool).await;
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/users?search=otherusername"),
+    )
+    .await;
+    let response: UsersResponse = assert_ok(response).await;
+    let admin_id = response.users[0].id;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/roles/admin", admin_id)),
+    )
+    .await;
+
+    assert_error(response, 400, "CANNOT_REVOKE_OWN_ROLE").await;
+}
+
+#[sqlx::test]
+async fn admin_actions_on_an_unknown_user_are_rejected(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, _) = admin_and_user_sign_up(&app, &pool).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", Uuid::new_v4())),
+    )
+    .await;
+
+    assert_error(response, 404, "USER_NOT_FOUND").await;
+}
@@ -0,0 +1,276 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::ContentType;
+use actix_web::{test, Error};
+use chrono::{Duration, Utc};
+use flutteractixapp::features::admin::application::dto::SetUserStatusRequest;
+use flutteractixapp::features::audit::application::dto::SecurityEventsResponse;
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::LoginRequest;
+use flutteractixapp::features::auth::domain::entities::AccountStatus;
+use sqlx::PgPool;
+
+use crate::admin::account_status::admin_sets_user_status;
+use crate::admin::users::{admin_and_user_sign_up, admin_sends_a_request, assert_error, assert_ok};
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{user_generates_otp, user_verifies_otp};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{spawn_app, user_is_granted_role};
+use crate::profile::update_password::user_updates_password;
+
+async fn user_fails_to_log_in(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "wrongpassword1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+}
+
+async fn user_gets_security_events(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+    query: &str,
+) -> SecurityEventsResponse {
+    let response = admin_sends_a_request(
+        &app,
+        access_token,
+        test::TestRequest::get().uri(&format!("/api/users/me/security-events{}", query)),
+    )
+    .await;
+
+    assert_ok(response).await
+}
+
+#[sqlx::test]
+async fn user_can_page_through_their_security_events(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    user_signs_up(&app).await;
+    user_fails_to_log_in(&app).await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = user_gets_security_events(&app, &access_token, "?limit=1").await;
+
+    assert_eq!(response.code, "SECURITY_EVENTS_FETCHED");
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(
+        response.events[0].event_type,
+        SecurityEventType::LoginSucceeded
+    );
+    assert_eq!(response.events[0].outcome, SecurityEventOutcome::Success);
+    assert_eq!(
+        response.events[0].actor_id,
+        Some(response.events[0].user_id)
+    );
+
+    let cursor = response.next_cursor.unwrap();
+    let response =
+        user_gets_security_events(&app, &access_token, &format!("?limit=1&cursor={}", cursor))
+            .await;
+
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(
+        response.events[0].event_type,
+        SecurityEventType::LoginFailed
+    );
+    assert_eq!(response.events[0].outcome, SecurityEventOutcome::Failure);
+    // Whoever typed the wrong password is not known to be the user
+    assert_eq!(response.events[0].actor_id, None);
+    assert!(response.next_cursor.is_none());
+}
+
+#[sqlx::test]
+async fn security_events_cannot_be_fetched_with_an_invalid_cursor(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/users/me/security-events?cursor=notacursor"),
+    )
+    .await;
+
+    assert_error(response, 400, "INVALID_CURSOR").await;
+}
+
+#[sqlx::test]
+async fn password_and_otp_changes_are_recorded(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    user_updates_password(&app, &access_token, "password1_", "password2_").await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let response = user_gets_security_events(&app, &access_token, "").await;
+    let event_types: Vec<SecurityEventType> = response
+        .events
+        .iter()
+        .map(|event| event.event_type)
+        .collect();
+
+    assert_eq!(
+        event_types,
+        vec![
+            SecurityEventType::OtpEnabled,
+            SecurityEventType::PasswordChanged
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn admin_can_filter_security_events(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+    admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/sessions", user_id)),
+    )
+    .await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri(&format!(
+            "/api/admin/security-events?user_id={}&event_type=SESSIONS_REVOKED",
+            user_id
+        )),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+
+    assert_eq!(response.events.len(), 1);
+    assert_eq!(response.events[0].user_id, user_id);
+    // The admin who revoked the sessions is the actor
+    assert_ne!(response.events[0].actor_id, Some(user_id));
+    assert!(response.events[0].actor_id.is_some());
+
+    // The admin logged in, the user did not
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/security-events?event_type=LOGIN_SUCCEEDED"),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+
+    assert_eq!(response.events.len(), 1);
+    assert_ne!(response.events[0].user_id, user_id);
+}
+
+#[sqlx::test]
+async fn admin_actions_are_recorded_with_the_admin_as_actor(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, _, user_id) = admin_and_user_sign_up(&app, &pool).await;
+    for req in [
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/password/expire", user_id)),
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/otp/reset", user_id)),
+        test::TestRequest::post().uri(&format!("/api/admin/users/{}/roles/support", user_id)),
+        test::TestRequest::delete().uri(&format!("/api/admin/users/{}/roles/support", user_id)),
+    ] {
+        let response = admin_sends_a_request(&app, &admin_access_token, req).await;
+        assert_eq!(200, response.status().as_u16());
+    }
+    let response = admin_sets_user_status(
+        &app,
+        &admin_access_token,
+        user_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Suspended,
+            reason: None,
+            until: Some(Utc::now() + Duration::days(1)),
+        },
+    )
+    .await;
+    assert_eq!(200, response.status().as_u16());
+
+    // Only the admin logged in
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri("/api/admin/security-events?event_type=LOGIN_SUCCEEDED"),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+    let admin_id = response.events[0].user_id;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::get().uri(&format!("/api/admin/security-events?user_id={}", user_id)),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+    let mut event_types: Vec<&str> = response
+        .events
+        .iter()
+        .map(|event| event.event_type.as_str())
+        .collect();
+    event_types.sort();
+
+    assert_eq!(
+        event_types,
+        vec![
+            "ACCOUNT_STATUS_CHANGED",
+            "OTP_DISABLED",
+            "PASSWORD_EXPIRED",
+            "ROLE_GRANTED",
+            "ROLE_REVOKED"
+        ]
+    );
+    assert!(response
+        .events
+        .iter()
+        .all(|event| event.actor_id == Some(admin_id)));
+}
+
+#[sqlx::test]
+async fn security_events_are_only_listed_with_the_permission(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/admin/security-events"),
+    )
+    .await;
+
+    assert_error(response, 403, "FORBIDDEN").await;
+
+    user_is_granted_role(&pool, "testusername", "support").await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/admin/security-events"),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+
+    assert_eq!(response.events.len(), 2);
+}
+
+#[sqlx::test]
+async fn security_events_cannot_be_modified(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+
+    let result = sqlx::query("UPDATE security_events SET outcome = 'failure'")
+        .execute(&pool)
+        .await;
+
+    assert!(result.is_err());
+}
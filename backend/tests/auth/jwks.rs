@@ -0,0 +1,171 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header;
+use actix_web::{test, Error};
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::domain::entities::Claims;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use sqlx::PgPool;
+
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{get_test_configuration, spawn_app, spawn_app_with_configuration};
+use crate::profile::profile::user_has_access_to_protected_route;
+
+async fn fetch_jwks(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) -> JwkSet {
+    let req = test::TestRequest::get()
+        .uri("/.well-known/jwks.json")
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[sqlx::test]
+async fn jwks_publishes_every_verification_key(pool: PgPool) {
+    let app = spawn_app(pool).await;
+
+    let req = test::TestRequest::get()
+        .uri("/.well-known/jwks.json")
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let jwks: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let keys = jwks["keys"].as_array().unwrap();
+
+    assert_eq!(keys.len(), 2);
+
+    let ed25519 = keys.iter().find(|k| k["kid"] == "local-ed25519").unwrap();
+    assert_eq!(ed25519["kty"], "OKP");
+    assert_eq!(ed25519["crv"], "Ed25519");
+    assert_eq!(ed25519["alg"], "EdDSA");
+    assert_eq!(ed25519["use"], "sig");
+
+    let rsa = keys.iter().find(|k| k["kid"] == "local-rsa").unwrap();
+    assert_eq!(rsa["kty"], "RSA");
+    assert_eq!(rsa["alg"], "RS256");
+    assert!(rsa["n"].is_string() && rsa["e"].is_string());
+
+    // Only public material is ever published
+    assert!(keys.iter().all(|k| k.get("d").is_none()));
+}
+
+#[sqlx::test]
+async fn tokens_can_be_verified_using_the_jwks(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let configuration = get_test_configuration();
+
+    let header = decode_header(&access_token).unwrap();
+    assert_eq!(header.alg, Algorithm::EdDSA);
+    assert_eq!(header.kid.as_deref(), Some("local-ed25519"));
+
+    let jwks = fetch_jwks(&app).await;
+    let jwk = jwks.find(&header.kid.unwrap()).unwrap();
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&configuration.application.token_issuer]);
+    validation.set_audience(&[&configuration.application.token_audience]);
+
+    let claims = decode::<Claims>(
+        &access_token,
+        &DecodingKey::from_jwk(jwk).unwrap(),
+        &validation,
+    )
+    .unwrap()
+    .claims;
+
+    assert_eq!(claims.iss, configuration.application.token_issuer);
+}
+
+#[sqlx::test]
+async fn tokens_signed_with_a_previous_key_are_accepted_after_rotation(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.jwt.signing_key_id = "local-rsa".to_string();
+    let app = spawn_app_with_configuration(pool.clone(), configuration).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    assert_eq!(
+        decode_header(&access_token).unwrap().kid.as_deref(),
+        Some("local-rsa")
+    );
+
+    // The signing key is rotated while the previous one is kept for verification
+    let app = spawn_app(pool).await;
+
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn tokens_signed_with_a_retired_key_are_rejected(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.jwt.signing_key_id = "local-rsa".to_string();
+    let app = spawn_app_with_configuration(pool.clone(), configuration).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    // The previous key is no longer configured at all
+    let mut configuration = get_test_configuration();
+    configuration.jwt.keys.retain(|key| key.id != "local-rsa");
+    let app = spawn_app_with_configuration(pool, configuration).await;
+
+    assert_access_token_is_rejected(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn tokens_signed_with_a_shared_secret_are_rejected(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let configuration = get_test_configuration();
+
+    let jwks = fetch_jwks(&app).await;
+    let jwk = jwks.find("local-ed25519").unwrap();
+    let mut validation = Validation::new(Algorithm::EdDSA);
+    validation.set_audience(&[&configuration.application.token_audience]);
+    let claims = decode::<Claims>(
+        &access_token,
+        &DecodingKey::from_jwk(jwk).unwrap(),
+        &validation,
+    )
+    .unwrap()
+    .claims;
+
+    // A known kid must not let the token pick its own algorithm
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some("local-ed25519".to_string());
+    let forged_access_token = encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(b"a-very-long-secret"),
+    )
+    .unwrap();
+
+    assert_access_token_is_rejected(&app, &forged_access_token).await;
+}
+
+async fn assert_access_token_is_rejected(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) {
+    let req = test::TestRequest::default()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_ACCESS_TOKEN");
+}
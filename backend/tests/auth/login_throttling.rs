@@ -0,0 +1,281 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use actix_http::{header, Request};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::ContentType;
+use actix_web::{test, Error};
+use chrono::Utc;
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, LoginWhenOtpEnabledResponse, RecoverAccountWithout2FAEnabledRequest,
+    ValidateOtpRequest,
+};
+use flutteractixapp::features::auth::structs::models::LockoutCache;
+use sqlx::PgPool;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{otp_code, user_generates_otp, user_verifies_otp};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{get_test_configuration, spawn_app, spawn_app_with_configuration};
+
+async fn user_attempts_to_log_in(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    username: &str,
+    password: &str,
+    peer_addr: Option<&str>,
+) -> ServiceResponse<impl MessageBody> {
+    let login_request = LoginRequest {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    let mut req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&login_request);
+    if let Some(peer_addr) = peer_addr {
+        req = req.peer_addr(peer_addr.parse::<SocketAddr>().unwrap());
+    }
+
+    test::call_service(&app, req.to_request()).await
+}
+
+async fn user_fails_to_log_in(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    times: usize,
+) {
+    for _ in 0..times {
+        let response = user_attempts_to_log_in(&app, "testusername", "wrong_password", None).await;
+
+        assert_eq!(401, response.status().as_u16());
+    }
+}
+
+async fn assert_too_many_attempts(response: ServiceResponse<impl MessageBody>, retry_after: i64) {
+    assert_eq!(429, response.status().as_u16());
+    assert_eq!(
+        response.headers().get(header::RETRY_AFTER).unwrap(),
+        &retry_after.to_string()
+    );
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "TOO_MANY_ATTEMPTS");
+}
+
+#[sqlx::test]
+async fn account_is_locked_out_after_too_many_failed_logins(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    user_signs_up(&app).await;
+    user_fails_to_log_in(&app, 5).await;
+
+    // Even the right password is refused while the account is locked out
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", None).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+
+    // The username is matched case-insensitively
+    let response = user_attempts_to_log_in(&app, "TestUsername", "password1_", None).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+
+    override_now(Some(
+        (Utc::now() + Duration::new(10 * 60, 0)).fixed_offset(),
+    ));
+
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", None).await;
+    assert_too_many_attempts(response, 5 * 60).await;
+
+    override_now(Some(
+        (Utc::now() + Duration::new(15 * 60, 1)).fixed_offset(),
+    ));
+
+    user_logs_in(&app, "testusername", "password1_").await;
+}
+
+#[sqlx::test]
+async fn failed_logins_outside_of_the_window_are_forgotten(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    user_signs_up(&app).await;
+    user_fails_to_log_in(&app, 4).await;
+
+    override_now(Some(
+        (Utc::now() + Duration::new(15 * 60, 1)).fixed_offset(),
+    ));
+
+    user_fails_to_log_in(&app, 4).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+}
+
+#[sqlx::test]
+async fn successful_login_resets_the_account_counter(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    user_signs_up(&app).await;
+
+    user_fails_to_log_in(&app, 4).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+    user_fails_to_log_in(&app, 4).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+}
+
+#[sqlx::test]
+async fn lockout_is_kept_across_app_instances(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    user_fails_to_log_in(&app, 5).await;
+
+    // A fresh instance has nothing in memory and must rely on the database
+    let app = spawn_app(pool).await;
+
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", None).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+}
+
+#[sqlx::test]
+async fn client_ip_is_locked_out_across_accounts(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.login_throttling.ip_max_attempts = 3;
+    let app = spawn_app_with_configuration(pool, configuration).await;
+    user_signs_up(&app).await;
+
+    let attacker = Some("203.0.113.7:40000");
+    for username in ["alice", "bob", "carol"] {
+        let response = user_attempts_to_log_in(&app, username, "password1_", attacker).await;
+
+        assert_eq!(401, response.status().as_u16());
+    }
+
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", attacker).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+
+    // Other clients are not affected
+    let response = user_attempts_to_log_in(
+        &app,
+        "testusername",
+        "password1_",
+        Some("198.51.100.1:40000"),
+    )
+    .await;
+    assert_eq!(200, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn failed_logins_with_a_long_username_are_counted(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let username = "a".repeat(300);
+
+    for _ in 0..5 {
+        let response = user_attempts_to_log_in(&app, &username, "password1_", None).await;
+        assert_eq!(401, response.status().as_u16());
+
+        let body = test::read_body(response).await;
+        let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response.code, "INVALID_USERNAME_OR_PASSWORD");
+    }
+
+    let response = user_attempts_to_log_in(&app, &username, "password1_", None).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+}
+
+#[sqlx::test]
+async fn otp_cannot_be_brute_forced(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", None).await;
+    assert_eq!(200, response.status().as_u16());
+    let body = test::read_body(response).await;
+    let response: LoginWhenOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
+
+    let validate_otp = |code: String| {
+        test::TestRequest::post()
+            .uri("/api/auth/otp/validate")
+            .insert_header(ContentType::json())
+            .set_json(&ValidateOtpRequest {
+                code,
+                mfa_token: response.mfa_token.clone(),
+            })
+            .to_request()
+    };
+
+    for code in ["000000", "111111", "222222", "333333", "444444"] {
+        let response = test::call_service(&app, validate_otp(code.to_string())).await;
+
+        assert_eq!(401, response.status().as_u16());
+    }
+
+    let response = test::call_service(&app, validate_otp(otp_code(&otp_base32))).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+
+    // Logging in again with the password does not reset the counter
+    let response = user_attempts_to_log_in(&app, "testusername", "password1_", None).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+}
+
+#[sqlx::test]
+async fn recovery_codes_cannot_be_brute_forced(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (_, _, recovery_codes) = user_signs_up(&app).await;
+
+    let recover = |recovery_code: &str| {
+        test::TestRequest::post()
+            .uri("/api/auth/recover")
+            .insert_header(ContentType::json())
+            .set_json(&RecoverAccountWithout2FAEnabledRequest {
+                username: "testusername".to_string(),
+                recovery_code: recovery_code.to_string(),
+            })
+            .to_request()
+    };
+
+    for _ in 0..5 {
+        let response = test::call_service(&app, recover("WRONGCODE")).await;
+
+        assert_eq!(401, response.status().as_u16());
+    }
+
+    let response = test::call_service(&app, recover(&recovery_codes[0])).await;
+    assert_too_many_attempts(response, 15 * 60).await;
+}
+
+#[tokio::test]
+async fn lockout_cache_forgets_ended_lockouts() {
+    let cache = LockoutCache::default();
+    let now = Utc::now();
+    cache
+        .insert("ended".to_string(), now + chrono::Duration::minutes(1))
+        .await;
+    cache
+        .insert("active".to_string(), now + chrono::Duration::minutes(10))
+        .await;
+    let later = now + chrono::Duration::minutes(5);
+
+    assert_eq!(cache.get_locked_until("ended", later).await, None);
+    assert_eq!(cache.size().await, 1);
+
+    cache
+        .insert("ended".to_string(), now + chrono::Duration::minutes(1))
+        .await;
+    assert_eq!(cache.remove_expired(later).await, 1);
+    assert!(cache.get_locked_until("active", later).await.is_some());
+}
+
+#[tokio::test]
+async fn lockout_cache_drops_the_lockout_ending_first_when_full() {
+    let cache = LockoutCache::new(2);
+    let now = Utc::now();
+
+    for (key, minutes) in [("a", 10), ("b", 5), ("c", 15)] {
+        cache
+            .insert(key.to_string(), now + chrono::Duration::minutes(minutes))
+            .await;
+    }
+
+    assert_eq!(cache.size().await, 2);
+    assert_eq!(cache.get_locked_until("b", now).await, None);
+    assert!(cache.get_locked_until("a", now).await.is_some());
+    assert!(cache.get_locked_until("c", now).await.is_some());
+}
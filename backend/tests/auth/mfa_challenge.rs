@@ -0,0 +1,102 @@
+use actix_http::header;
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceResponse;
+use actix_web::test;
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::{now, override_now};
+use flutteractixapp::core::structs::responses::GenericResponse;
+use sqlx::PgPool;
+
+use crate::auth::otp::{
+    otp_code, user_generates_otp, user_logs_in_with_otp_enabled, user_validates_otp,
+    user_verifies_otp, wait_for_next_otp_step,
+};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app;
+
+async fn assert_invalid_mfa_challenge(response: ServiceResponse<impl MessageBody>) {
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_MFA_CHALLENGE");
+}
+
+#[sqlx::test]
+async fn mfa_token_can_only_be_used_once(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_eq!(200, response.status().as_u16());
+
+    wait_for_next_otp_step();
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_invalid_mfa_challenge(response).await;
+}
+
+#[sqlx::test]
+async fn mfa_token_is_kept_after_a_wrong_otp(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, "000000").await;
+    assert_eq!(401, response.status().as_u16());
+
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_eq!(200, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn expired_mfa_token_is_rejected(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    override_now(Some(now().fixed_offset()));
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+
+    override_now(Some((now() + Duration::seconds(301)).fixed_offset()));
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_invalid_mfa_challenge(response).await;
+}
+
+#[sqlx::test]
+async fn access_token_is_not_accepted_as_mfa_token(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let response = user_validates_otp(&app, &access_token, &otp_code(&otp_base32)).await;
+    assert_invalid_mfa_challenge(response).await;
+}
+
+#[sqlx::test]
+async fn mfa_token_is_not_accepted_as_access_token(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", mfa_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::ContentType;
+use actix_web::{test, Error};
+use flutteractixapp::configuration::NotifierKind;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::audit::application::dto::SecurityEventsResponse;
+use flutteractixapp::features::audit::domain::entities::SecurityEventType;
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, LoginResponse, ReportSignInRequest, ValidateOtpRequest,
+};
+use flutteractixapp::features::auth::domain::entities::NewSignIn;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::admin::users::{admin_sends_a_request, assert_ok};
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{
+    otp_code, user_generates_otp, user_logs_in_with_otp_enabled, user_verifies_otp,
+    wait_for_next_otp_step,
+};
+use crate::auth::signup::user_signs_up;
+use crate::auth::token::user_refreshes_token;
+use crate::helpers::{get_test_configuration, spawn_app_with_configuration};
+use crate::profile::devices::assert_access_token_is_revoked;
+use crate::profile::profile::user_has_access_to_protected_route;
+
+const PHONE: &str = "os=Android; isMobile=true; model=Pixel 8; appVersion=1.0.0";
+
+async fn spawn_app_with_outbox(
+    pool: PgPool,
+) -> (
+    impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    PathBuf,
+) {
+    let mut configuration = get_test_configuration();
+    let outbox_path = std::env::temp_dir().join(format!("{}.jsonl", Uuid::new_v4()));
+    configuration.new_device_alerts.notifier = NotifierKind::File;
+    configuration.new_device_alerts.outbox_path = outbox_path.to_string_lossy().to_string();
+
+    (
+        spawn_app_with_configuration(pool, configuration).await,
+        outbox_path,
+    )
+}
+
+fn sent_notifications(outbox_path: &Path) -> Vec<NewSignIn> {
+    match std::fs::read_to_string(outbox_path) {
+        Ok(outbox) => outbox
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn user_logs_in_from(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    user_agent: &str,
+) -> LoginResponse {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .insert_header(("X-User-Agent", user_agent))
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn user_reports_sign_in(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    token: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/sign-ins/report")
+        .insert_header(ContentType::json())
+        .set_json(&ReportSignInRequest {
+            token: token.to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+#[sqlx::test]
+async fn user_is_notified_of_a_sign_in_from_a_new_device(pool: PgPool) {
+    let (app, outbox_path) = spawn_app_with_outbox(pool).await;
+    user_signs_up(&app).await;
+
+    // The device used to sign up is known
+    user_logs_in(&app, "testusername", "password1_").await;
+    assert!(sent_notifications(&outbox_path).is_empty());
+
+    user_logs_in_from(&app, PHONE).await;
+    let notifications = sent_notifications(&outbox_path);
+
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].username, "testusername");
+    assert_eq!(notifications[0].device_info.os.as_deref(), Some("Android"));
+    assert_eq!(
+        notifications[0].device_info.model.as_deref(),
+        Some("Pixel 8")
+    );
+
+    // Updating the app doesn't make it another device
+    user_logs_in_from(
+        &app,
+        "os=Android; isMobile=true; model=Pixel 8; appVersion=1.1.0",
+    )
+    .await;
+
+    assert_eq!(sent_notifications(&outbox_path).len(), 1);
+}
+
+#[sqlx::test]
+async fn device_stays_known_after_its_session_is_gone(pool: PgPool) {
+    let (app, outbox_path) = spawn_app_with_outbox(pool.clone()).await;
+    user_signs_up(&app).await;
+    user_logs_in_from(&app, PHONE).await;
+
+    sqlx::query("DELETE FROM user_tokens")
+        .execute(&pool)
+        .await
+        .unwrap();
+    user_logs_in_from(&app, PHONE).await;
+
+    assert_eq!(sent_notifications(&outbox_path).len(), 1);
+}
+
+#[sqlx::test]
+async fn user_is_notified_of_a_sign_in_with_otp_from_a_new_device(pool: PgPool) {
+    let (app, outbox_path) = spawn_app_with_outbox(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    wait_for_next_otp_step();
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    // No session is started before the second factor
+    assert!(sent_notifications(&outbox_path).is_empty());
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/otp/validate")
+        .insert_header(ContentType::json())
+        .insert_header(("X-User-Agent", PHONE))
+        .set_json(&ValidateOtpRequest {
+            code: otp_code(&otp_base32),
+            mfa_token,
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(sent_notifications(&outbox_path).len(), 1);
+}
+
+#[sqlx::test]
+async fn user_can_revoke_a_sign_in_they_did_not_make(pool: PgPool) {
+    let (app, outbox_path) = spawn_app_with_outbox(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let response = user_logs_in_from(&app, PHONE).await;
+    // The report still applies once the tokens of the session were rotated
+    let (stolen_access_token, _) = user_refreshes_token(&app, &response.refresh_token).await;
+    let report_token = sent_notifications(&outbox_path)[0].report_token.clone();
+
+    let response = user_reports_sign_in(&app, &report_token).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "SIGN_IN_REPORTED");
+    assert_access_token_is_revoked(&app, &stolen_access_token).await;
+    // The other sessions are left alone
+    user_has_access_to_protected_route(&app, &access_token).await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &access_token,
+        test::TestRequest::get().uri("/api/users/me/security-events"),
+    )
+    .await;
+    let response: SecurityEventsResponse = assert_ok(response).await;
+
+    assert_eq!(
+        response.events[0].event_type,
+        SecurityEventType::SignInReported
+    );
+}
+
+#[sqlx::test]
+async fn sign_in_cannot_be_reported_with_an_invalid_link(pool: PgPool) {
+    let (app, _) = spawn_app_with_outbox(pool).await;
+    let (_, refresh_token, _) = user_signs_up(&app).await;
+
+    // Only a report token is accepted, even though a refresh token is signed the same way
+    let response = user_reports_sign_in(&app, &refresh_token).await;
+
+    assert_eq!(404, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_SIGN_IN_REPORT_LINK");
+}
@@ -3,16 +3,40 @@ use actix_web::body::MessageBody;
 use actix_web::dev::{Service, ServiceResponse};
 use actix_web::http::header::ContentType;
 use actix_web::{test, Error};
+use chrono::Duration;
+use flutteractixapp::configuration::OtpAlgorithm;
+use flutteractixapp::core::helpers::mock_now::{now, override_now};
 use flutteractixapp::core::structs::responses::GenericResponse;
-use flutteractixapp::features::auth::application::dto::{DisableOtpResponse, GenerateOtpResponse, LoginRequest, LoginResponse, LoginWhenOtpEnabledResponse, ValidateOtpRequest, VerifyOtpRequest, VerifyOtpResponse};
+use flutteractixapp::features::auth::application::dto::{
+    DisableOtpResponse, GenerateOtpResponse, LoginRequest, LoginResponse,
+    LoginWhenOtpEnabledResponse, ValidateOtpRequest, VerifyOtpRequest, VerifyOtpResponse,
+};
 use sqlx::PgPool;
 use totp_rs::{Algorithm, Secret, TOTP};
-use uuid::Uuid;
 
 use crate::auth::signup::user_signs_up;
-use crate::helpers::spawn_app;
+use crate::helpers::{get_test_configuration, spawn_app, spawn_app_with_configuration};
 use crate::profile::profile::user_has_access_to_protected_route;
 
+// Codes are generated for the mocked time, so tests can move to another step
+pub fn otp_code(otp_base32: &str) -> String {
+    let totp = TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    totp.generate(now().timestamp() as u64)
+}
+
+// A code is only accepted once, the next one is generated in the following step
+pub fn wait_for_next_otp_step() {
+    override_now(Some((now() + Duration::seconds(30)).fixed_offset()));
+}
+
 pub async fn user_generates_otp(
     app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
     access_token: &str,
@@ -38,20 +62,9 @@ pub async fn user_verifies_otp(
     access_token: &str,
     otp_base32: &str,
 ) {
-    let totp = TOTP::new(
-        Algorithm::SHA1,
-        6,
-        1,
-        30,
-        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
-    )
-    .unwrap();
-
-    let code = totp.generate_current().unwrap();
+    let code = otp_code(otp_base32);
 
-    let verify_request = VerifyOtpRequest {
-        code: code,
-    };
+    let verify_request = VerifyOtpRequest { code };
     let req = test::TestRequest::post()
         .uri("/api/auth/otp/verify")
         .insert_header(ContentType::json())
@@ -66,7 +79,7 @@ pub async fn user_verifies_otp(
     let response: VerifyOtpResponse = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(response.code, "OTP_VERIFIED");
-    assert_eq!(response.otp_verified, true);
+    assert!(response.otp_verified);
 }
 
 #[sqlx::test]
@@ -106,21 +119,12 @@ async fn registered_user_can_validate_otp(pool: PgPool) {
     assert_eq!(response.code, "USER_LOGS_IN_WITH_OTP_ENABLED");
 
     // A TOTP is necessary to log in.
-    let totp = TOTP::new(
-        Algorithm::SHA1,
-        6,
-        1,
-        30,
-        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
-    )
-    .unwrap();
-
-    let code = totp.generate_current().unwrap();
-    let user_id: Uuid = response.user_id.parse().unwrap();
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
 
     let validate_request = ValidateOtpRequest {
-        code: code,
-        user_id: user_id,
+        code,
+        mfa_token: response.mfa_token,
     };
     let req = test::TestRequest::post()
         .uri("/api/auth/otp/validate")
@@ -160,7 +164,7 @@ async fn registered_user_can_disable_otp(pool: PgPool) {
     let response: DisableOtpResponse = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(response.code, "OTP_DISABLED");
-    assert_eq!(response.two_fa_enabled, false);
+    assert!(!response.two_fa_enabled);
 }
 
 #[sqlx::test]
@@ -182,26 +186,185 @@ async fn user_cannot_generate_otp_with_wrong_token(pool: PgPool) {
 }
 
 #[sqlx::test]
-async fn user_cannot_validate_otp_for_a_wrong_user(pool: PgPool) {
+async fn user_cannot_validate_otp_with_an_invalid_mfa_token(pool: PgPool) {
     let app = spawn_app(pool).await;
-    let (access_token, _, _) = user_signs_up(&app).await;
+    user_signs_up(&app).await;
 
-    let validate_request = ValidateOtpRequest {
-        code: "000000".to_string(),
-        user_id: Uuid::new_v4(),
-    };
+    let response = user_validates_otp(&app, "invalid token", "000000").await;
+
+    assert_invalid_mfa_challenge(response).await;
+}
+
+pub async fn user_logs_in_with_otp_enabled(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) -> String {
     let req = test::TestRequest::post()
-        .uri("/api/auth/otp/validate")
+        .uri("/api/auth/login")
         .insert_header(ContentType::json())
-        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
-        .set_json(&validate_request)
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+        })
         .to_request();
     let response = test::call_service(&app, req).await;
 
-    assert_eq!(404, response.status().as_u16());
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: LoginWhenOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
+
+    response.mfa_token
+}
+
+pub async fn user_validates_otp(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    mfa_token: &str,
+    code: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/otp/validate")
+        .insert_header(ContentType::json())
+        .set_json(&ValidateOtpRequest {
+            code: code.to_string(),
+            mfa_token: mfa_token.to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn assert_invalid_otp(response: ServiceResponse<impl MessageBody>) {
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_ONE_TIME_PASSWORD");
+}
+
+async fn assert_invalid_mfa_challenge(response: ServiceResponse<impl MessageBody>) {
+    assert_eq!(401, response.status().as_u16());
 
     let body = test::read_body(response).await;
     let response: GenericResponse = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(response.code, "USER_NOT_FOUND");
+    assert_eq!(response.code, "INVALID_MFA_CHALLENGE");
+}
+
+#[sqlx::test]
+async fn otp_code_cannot_be_used_twice_to_log_in(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &code).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &code).await;
+    assert_invalid_otp(response).await;
+
+    // The code of the next step is accepted
+    wait_for_next_otp_step();
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_eq!(200, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn otp_code_used_for_verification_cannot_be_used_to_log_in(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    let code = otp_code(&otp_base32);
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &code).await;
+    assert_invalid_otp(response).await;
+}
+
+#[sqlx::test]
+async fn older_otp_code_is_rejected_once_a_newer_one_was_used(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let previous_code = otp_code(&otp_base32);
+    wait_for_next_otp_step();
+
+    // The code of the previous step is still within the allowed skew
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(&otp_base32)).await;
+    assert_eq!(200, response.status().as_u16());
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &previous_code).await;
+    assert_invalid_otp(response).await;
+}
+
+#[sqlx::test]
+async fn otp_code_outside_of_the_skew_is_rejected(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
+    wait_for_next_otp_step();
+    wait_for_next_otp_step();
+
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &code).await;
+    assert_invalid_otp(response).await;
+}
+
+#[sqlx::test]
+async fn otp_parameters_are_configurable(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.otp.algorithm = OtpAlgorithm::Sha256;
+    configuration.otp.digits = 8;
+    configuration.otp.step_seconds = 60;
+    let app = spawn_app_with_configuration(pool, configuration).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/auth/otp/generate")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    let body = test::read_body(response).await;
+    let response: GenerateOtpResponse = serde_json::from_slice(&body).unwrap();
+
+    assert!(response
+        .otp_auth_url
+        .ends_with("&algorithm=SHA256&digits=8&period=60"));
+
+    let totp = TOTP::new(
+        Algorithm::SHA256,
+        8,
+        0,
+        60,
+        Secret::Encoded(response.otp_base32).to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/otp/verify")
+        .insert_header(ContentType::json())
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .set_json(&VerifyOtpRequest {
+            code: totp.generate(now().timestamp() as u64),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
 }
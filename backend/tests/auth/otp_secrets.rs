@@ -0,0 +1,234 @@
+use actix_http::{header, Request};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{test, Error};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use flutteractixapp::configuration::{EncryptionKeySettings, Settings};
+use flutteractixapp::core::helpers::migrations::{run_migrations, MigrationError};
+use flutteractixapp::core::structs::envelope_cipher::EnvelopeCipher;
+use flutteractixapp::features::auth::infrastructure::otp_secrets::encrypt_otp_secrets;
+use flutteractixapp::features::profile::application::dto::ProfileResponse;
+use secrecy::Secret;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::otp::{
+    otp_code, user_generates_otp, user_logs_in_with_otp_enabled, user_validates_otp,
+    user_verifies_otp, wait_for_next_otp_step,
+};
+use crate::auth::recovery::recover_account_using_password::user_recovers_account_using_password;
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{get_test_configuration, spawn_app, spawn_app_with_configuration};
+
+async fn stored_otp_secret(pool: &PgPool) -> (Uuid, String, String) {
+    sqlx::query_as("SELECT id, otp_base32, otp_auth_url FROM users WHERE username = 'testusername'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn user_fetches_profile(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> ProfileResponse {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn user_logs_in_with_otp(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    otp_base32: &str,
+) {
+    wait_for_next_otp_step();
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let response = user_validates_otp(&app, &mfa_token, &otp_code(otp_base32)).await;
+
+    assert_eq!(200, response.status().as_u16());
+}
+
+fn with_encryption_keys(current_key_version: u32, versions: &[u32]) -> Settings {
+    let mut configuration = get_test_configuration();
+    configuration.encryption.current_key_version = current_key_version;
+    configuration.encryption.keys = versions
+        .iter()
+        .map(|version| EncryptionKeySettings {
+            version: *version,
+            key: Secret::new(STANDARD.encode([*version as u8; 32])),
+        })
+        .collect();
+    configuration
+}
+
+#[sqlx::test]
+async fn otp_secret_is_stored_encrypted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+
+    let (_, stored_otp_base32, stored_otp_auth_url) = stored_otp_secret(&pool).await;
+
+    assert!(stored_otp_base32.starts_with("v1:"));
+    assert!(stored_otp_auth_url.starts_with("v1:"));
+    assert!(!stored_otp_base32.contains(&otp_base32));
+    assert!(!stored_otp_auth_url.contains(&otp_base32));
+
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    user_logs_in_with_otp(&app, &otp_base32).await;
+}
+
+#[sqlx::test]
+async fn otp_secret_is_removed_from_the_profile_once_verified(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+
+    // Still shown while 2FA is being set up
+    let response = user_fetches_profile(&app, &access_token).await;
+    assert_eq!(response.user.otp_base32, Some(otp_base32.clone()));
+    assert!(response.user.otp_auth_url.is_some());
+
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let response = user_fetches_profile(&app, &access_token).await;
+    assert!(response.user.otp_verified);
+    assert_eq!(response.user.otp_base32, None);
+    assert_eq!(response.user.otp_auth_url, None);
+}
+
+#[sqlx::test]
+async fn otp_secret_is_deleted_when_account_is_recovered_using_password(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    user_recovers_account_using_password(&app, &recovery_codes[0], "password1_").await;
+
+    let (otp_base32, otp_auth_url): (Option<String>, Option<String>) = sqlx::query_as(
+        "SELECT otp_base32, otp_auth_url FROM users WHERE username = 'testusername'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(otp_base32, None);
+    assert_eq!(otp_auth_url, None);
+}
+
+#[sqlx::test]
+async fn encrypted_otp_secret_cannot_be_moved_to_another_user(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    user_generates_otp(&app, &access_token).await;
+    let (user_id, stored_otp_base32, _) = stored_otp_secret(&pool).await;
+
+    let cipher = EnvelopeCipher::new(&get_test_configuration().encryption).unwrap();
+    let associated_data = |user_id: Uuid| format!("users.otp_base32:{user_id}").into_bytes();
+
+    assert!(cipher
+        .decrypt(&stored_otp_base32, &associated_data(user_id))
+        .is_ok());
+    assert!(cipher
+        .decrypt(&stored_otp_base32, &associated_data(Uuid::new_v4()))
+        .is_err());
+}
+
+#[sqlx::test]
+async fn plaintext_otp_secrets_are_encrypted(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    // As stored before secrets were encrypted
+    sqlx::query("UPDATE users SET otp_base32 = $1, otp_auth_url = $2")
+        .bind(&otp_base32)
+        .bind(format!(
+            "otpauth://totp/app:testusername?secret={otp_base32}"
+        ))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let cipher = EnvelopeCipher::new(&get_test_configuration().encryption).unwrap();
+    assert_eq!(encrypt_otp_secrets(&pool, &cipher).await.unwrap(), 1);
+    assert_eq!(encrypt_otp_secrets(&pool, &cipher).await.unwrap(), 0);
+
+    let (_, stored_otp_base32, stored_otp_auth_url) = stored_otp_secret(&pool).await;
+    assert!(stored_otp_base32.starts_with("v1:"));
+    assert!(stored_otp_auth_url.starts_with("v1:"));
+
+    user_logs_in_with_otp(&app, &otp_base32).await;
+}
+
+#[sqlx::test]
+async fn migrations_encrypt_plaintext_otp_secrets_once(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    sqlx::query("UPDATE users SET otp_base32 = $1")
+        .bind(&otp_base32)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let cipher = EnvelopeCipher::new(&get_test_configuration().encryption).unwrap();
+    run_migrations(&pool, &cipher).await.unwrap();
+    let (_, stored_otp_base32, _) = stored_otp_secret(&pool).await;
+    assert!(stored_otp_base32.starts_with("v1:"));
+
+    // Running them again leaves the data as it is
+    run_migrations(&pool, &cipher).await.unwrap();
+    assert_eq!(stored_otp_secret(&pool).await.1, stored_otp_base32);
+
+    user_logs_in_with_otp(&app, &otp_base32).await;
+}
+
+#[sqlx::test]
+async fn migrations_fail_when_an_otp_secret_cannot_be_read(pool: PgPool) {
+    let app = spawn_app_with_configuration(pool.clone(), with_encryption_keys(1, &[1])).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    user_generates_otp(&app, &access_token).await;
+
+    // The key which encrypted the secret was retired too early
+    let cipher = EnvelopeCipher::new(&with_encryption_keys(2, &[2]).encryption).unwrap();
+
+    assert!(matches!(
+        run_migrations(&pool, &cipher).await,
+        Err(MigrationError::OtpSecrets(_))
+    ));
+}
+
+#[sqlx::test]
+async fn otp_secrets_are_moved_to_the_current_key_after_rotation(pool: PgPool) {
+    let app = spawn_app_with_configuration(pool.clone(), with_encryption_keys(1, &[1])).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    // The previous key is kept to read the secrets it encrypted
+    let configuration = with_encryption_keys(2, &[1, 2]);
+    let app = spawn_app_with_configuration(pool.clone(), configuration.clone()).await;
+    user_logs_in_with_otp(&app, &otp_base32).await;
+
+    let cipher = EnvelopeCipher::new(&configuration.encryption).unwrap();
+    assert_eq!(encrypt_otp_secrets(&pool, &cipher).await.unwrap(), 1);
+
+    let (_, stored_otp_base32, stored_otp_auth_url) = stored_otp_secret(&pool).await;
+    assert!(stored_otp_base32.starts_with("v2:"));
+    assert!(stored_otp_auth_url.starts_with("v2:"));
+
+    // Once every secret was moved, the previous key can be retired
+    let app = spawn_app_with_configuration(pool, with_encryption_keys(2, &[2])).await;
+    user_logs_in_with_otp(&app, &otp_base32).await;
+}
@@ -4,11 +4,12 @@ use actix_web::dev::{Service, ServiceResponse};
 use actix_web::http::header::ContentType;
 use actix_web::{test, Error};
 use flutteractixapp::core::structs::responses::GenericResponse;
-use flutteractixapp::features::auth::application::dto::{LoginResponse, RecoverAccountUsing2FARequest};
+use flutteractixapp::features::auth::application::dto::{
+    LoginResponse, RecoverAccountUsing2FARequest,
+};
 use sqlx::PgPool;
-use totp_rs::{Algorithm, Secret, TOTP};
 
-use crate::auth::otp::{user_generates_otp, user_verifies_otp};
+use crate::auth::otp::{otp_code, user_generates_otp, user_verifies_otp, wait_for_next_otp_step};
 use crate::auth::signup::user_signs_up;
 use crate::helpers::spawn_app;
 use crate::profile::profile::user_has_access_to_protected_route;
@@ -48,17 +49,9 @@ async fn user_can_recover_account_using_2fa(pool: PgPool) {
 
     user_verifies_otp(&app, &access_token, &otp_base32).await;
 
-    let totp = TOTP::new(
-        Algorithm::SHA1,
-        6,
-        1,
-        30,
-        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
-    )
-    .unwrap();
-    let code = totp.generate_current().unwrap();
-
     for recovery_code in recovery_codes {
+        wait_for_next_otp_step();
+        let code = otp_code(&otp_base32);
         let (access_token, _) = user_recovers_account_using_2fa(&app, &recovery_code, &code).await;
 
         user_has_access_to_protected_route(&app, &access_token).await;
@@ -128,15 +121,8 @@ async fn user_cannot_recover_account_using_2fa_with_wrong_username(pool: PgPool)
 
     user_verifies_otp(&app, &access_token, &otp_base32).await;
 
-    let totp = TOTP::new(
-        Algorithm::SHA1,
-        6,
-        1,
-        30,
-        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
-    )
-    .unwrap();
-    let code = totp.generate_current().unwrap();
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
     let req = test::TestRequest::post()
         .uri("/api/auth/recover-using-2fa")
         .insert_header(ContentType::json())
@@ -164,19 +150,15 @@ async fn user_cannot_recover_account_using_2fa_using_code_twice(pool: PgPool) {
 
     user_verifies_otp(&app, &access_token, &otp_base32).await;
 
-    let totp = TOTP::new(
-        Algorithm::SHA1,
-        6,
-        1,
-        30,
-        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
-    )
-    .unwrap();
-    let code = totp.generate_current().unwrap();
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
     let (access_token, _) = user_recovers_account_using_2fa(&app, &recovery_codes[0], &code).await;
 
     user_has_access_to_protected_route(&app, &access_token).await;
 
+    // With a fresh OTP code, only the recovery code is reused
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
     let req = test::TestRequest::post()
         .uri("/api/auth/recover-using-2fa")
         .insert_header(ContentType::json())
@@ -4,8 +4,12 @@ use actix_web::dev::{Service, ServiceResponse};
 use actix_web::http::header::ContentType;
 use actix_web::{test, Error};
 use flutteractixapp::core::structs::responses::GenericResponse;
-use flutteractixapp::features::auth::application::dto::{LoginResponse, RecoverAccountUsingPasswordRequest};
-use flutteractixapp::features::profile::application::dto::{IsOtpEnabledRequest, IsOtpEnabledResponse};
+use flutteractixapp::features::auth::application::dto::{
+    LoginResponse, RecoverAccountUsingPasswordRequest,
+};
+use flutteractixapp::features::profile::application::dto::{
+    IsOtpEnabledRequest, IsOtpEnabledResponse,
+};
 use sqlx::PgPool;
 
 use crate::auth::otp::{user_generates_otp, user_verifies_otp};
@@ -72,7 +76,7 @@ async fn user_can_recover_account_using_password(pool: PgPool) {
     let body = test::read_body(response).await;
     let response: IsOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(response.otp_enabled, false);
+    assert!(!response.otp_enabled);
 }
 
 #[sqlx::test]
@@ -4,7 +4,9 @@ use actix_web::dev::{Service, ServiceResponse};
 use actix_web::http::header::ContentType;
 use actix_web::{test, Error};
 use flutteractixapp::core::structs::responses::GenericResponse;
-use flutteractixapp::features::auth::application::dto::{LoginRequest, LoginResponse, RecoverAccountWithout2FAEnabledRequest};
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, LoginResponse, RecoverAccountWithout2FAEnabledRequest,
+};
 use sqlx::PgPool;
 
 use crate::auth::signup::user_signs_up;
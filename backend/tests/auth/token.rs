@@ -6,10 +6,14 @@ use actix_web::dev::{Service, ServiceResponse};
 use actix_web::http::header::{self, ContentType};
 use actix_web::{test, Error};
 use chrono::Utc;
+use flutteractixapp::configuration::get_configuration;
 use flutteractixapp::core::helpers::mock_now::override_now;
 use flutteractixapp::core::structs::responses::GenericResponse;
-use flutteractixapp::features::auth::application::dto::RefreshTokenRequest;
 use flutteractixapp::features::auth::application::dto::refresh_token_response::RefreshTokenResponse;
+use flutteractixapp::features::auth::application::dto::RefreshTokenRequest;
+use flutteractixapp::features::auth::domain::entities::{Claims, TokenType};
+use flutteractixapp::features::auth::infrastructure::keys::JwtKeySet;
+use jsonwebtoken::{decode, encode, Header, Validation};
 use sqlx::PgPool;
 
 use crate::auth::login::user_logs_in;
@@ -148,3 +152,185 @@ async fn refresh_token_becomes_expired_after_7_days(pool: PgPool) {
 
     assert_eq!(profile_response.code, "REFRESH_TOKEN_EXPIRED");
 }
+
+#[sqlx::test]
+async fn tokens_carry_their_type_issuer_and_audience(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, refresh_token, _) = user_signs_up(&app).await;
+
+    let configuration = get_configuration().expect("Failed to read configuration.");
+    let key_set = JwtKeySet::load(&configuration.jwt).unwrap();
+    let verification_key = key_set
+        .verification_key(&configuration.jwt.signing_key_id)
+        .unwrap();
+    let decoding_key = &verification_key.decoding_key;
+    let mut validation = Validation::new(verification_key.algorithm);
+    validation.set_issuer(&[&configuration.application.token_issuer]);
+    validation.set_audience(&[&configuration.application.token_audience]);
+
+    let access_claims = decode::<Claims>(&access_token, decoding_key, &validation)
+        .unwrap()
+        .claims;
+    let refresh_claims = decode::<Claims>(&refresh_token, decoding_key, &validation)
+        .unwrap()
+        .claims;
+
+    assert_eq!(access_claims.token_type, TokenType::Access);
+    assert_eq!(refresh_claims.token_type, TokenType::Refresh);
+    assert!(access_claims.iat <= access_claims.exp);
+    assert!(refresh_claims.exp > access_claims.exp);
+}
+
+#[sqlx::test]
+async fn refresh_token_cannot_be_used_as_access_token(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (_, refresh_token, _) = user_signs_up(&app).await;
+
+    let req = test::TestRequest::default()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", refresh_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_ACCESS_TOKEN");
+}
+
+#[sqlx::test]
+async fn access_token_cannot_be_used_to_refresh(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, refresh_token, _) = user_signs_up(&app).await;
+
+    let refresh_request = RefreshTokenRequest {
+        refresh_token: access_token.to_string(),
+    };
+    let req = test::TestRequest::post()
+        .uri("/api/auth/refresh-token")
+        .insert_header(ContentType::json())
+        .set_json(&refresh_request)
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_REFRESH_TOKEN");
+
+    // The rejected attempt must not have consumed the session
+    let (access_token, _) = user_refreshes_token(&app, &refresh_token).await;
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn token_issued_for_another_audience_is_rejected(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let configuration = get_configuration().expect("Failed to read configuration.");
+    let key_set = JwtKeySet::load(&configuration.jwt).unwrap();
+    let signing_key = key_set.signing_key();
+    let verification_key = key_set.verification_key(&signing_key.kid).unwrap();
+    let mut validation = Validation::new(verification_key.algorithm);
+    validation.set_audience(&[&configuration.application.token_audience]);
+
+    let mut claims = decode::<Claims>(&access_token, &verification_key.decoding_key, &validation)
+        .unwrap()
+        .claims;
+    claims.aud = "another-service".to_string();
+
+    // Signed with our own key, so only the audience check can reject it
+    let mut header = Header::new(signing_key.algorithm);
+    header.kid = Some(signing_key.kid.clone());
+    let forged_access_token = encode(&header, &claims, &signing_key.encoding_key).unwrap();
+
+    let req = test::TestRequest::default()
+        .uri("/api/users/me")
+        .insert_header((
+            header::AUTHORIZATION,
+            format!("Bearer {}", forged_access_token),
+        ))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_ACCESS_TOKEN");
+}
+
+async fn refresh_is_rejected(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    refresh_token: &str,
+) {
+    let refresh_request = RefreshTokenRequest {
+        refresh_token: refresh_token.to_string(),
+    };
+    let req = test::TestRequest::post()
+        .uri("/api/auth/refresh-token")
+        .insert_header(ContentType::json())
+        .set_json(&refresh_request)
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_REFRESH_TOKEN");
+}
+
+#[sqlx::test]
+async fn reusing_a_rotated_refresh_token_revokes_the_whole_family(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (_, initial_refresh_token, _) = user_signs_up(&app).await;
+
+    let (_, second_refresh_token) = user_refreshes_token(&app, &initial_refresh_token).await;
+    let (access_token, latest_refresh_token) =
+        user_refreshes_token(&app, &second_refresh_token).await;
+
+    // An attacker replays a refresh token that was already rotated
+    refresh_is_rejected(&app, &initial_refresh_token).await;
+
+    // Every token of the family is now revoked, including the latest ones
+    refresh_is_rejected(&app, &latest_refresh_token).await;
+
+    let req = test::TestRequest::default()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let reuse_events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM security_events WHERE event_type = 'REFRESH_TOKEN_REUSE_DETECTED'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(reuse_events, 1);
+}
+
+#[sqlx::test]
+async fn refresh_token_reuse_does_not_revoke_other_sessions(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (_, refresh_token, _) = user_signs_up(&app).await;
+    let (_, other_refresh_token) = user_logs_in(&app, "testusername", "password1_").await;
+
+    user_refreshes_token(&app, &refresh_token).await;
+    refresh_is_rejected(&app, &refresh_token).await;
+
+    // The session opened on the other device is still valid
+    let (access_token, _) = user_refreshes_token(&app, &other_refresh_token).await;
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header;
+use actix_web::{test, Error};
+use chrono::Utc;
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::features::auth::infrastructure::token_cache::PostgresTokenCacheBackend;
+use flutteractixapp::features::auth::structs::models::{InMemoryTokenCacheBackend, TokenCache};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::logout::user_logs_out;
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app_with_token_cache;
+use crate::profile::profile::user_has_access_to_protected_route;
+
+async fn protected_route_status(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> u16 {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    test::call_service(&app, req).await.status().as_u16()
+}
+
+#[sqlx::test]
+async fn logout_on_one_instance_revokes_the_access_token_on_the_others(pool: PgPool) {
+    let first_backend =
+        PostgresTokenCacheBackend::new(pool.clone(), InMemoryTokenCacheBackend::default());
+    let first_listener = first_backend.clone().listen().await.unwrap();
+    let second_backend =
+        PostgresTokenCacheBackend::new(pool.clone(), InMemoryTokenCacheBackend::default());
+    let second_listener = second_backend.clone().listen().await.unwrap();
+
+    let first_app = spawn_app_with_token_cache(pool.clone(), TokenCache::new(first_backend)).await;
+    let second_app = spawn_app_with_token_cache(pool, TokenCache::new(second_backend)).await;
+
+    let (access_token, _, _) = user_signs_up(&first_app).await;
+    let (other_access_token, _) = user_logs_in(&first_app, "testusername", "password1_").await;
+
+    // Both instances cache the tokens
+    user_has_access_to_protected_route(&first_app, &access_token).await;
+    user_has_access_to_protected_route(&second_app, &access_token).await;
+    user_has_access_to_protected_route(&second_app, &other_access_token).await;
+
+    user_logs_out(&first_app, &access_token).await;
+
+    // The invalidation is delivered asynchronously
+    let mut status = protected_route_status(&second_app, &access_token).await;
+    for _ in 0..50 {
+        if status == 401 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        status = protected_route_status(&second_app, &access_token).await;
+    }
+
+    assert_eq!(status, 401);
+    user_has_access_to_protected_route(&second_app, &other_access_token).await;
+
+    first_listener.abort();
+    second_listener.abort();
+}
+
+fn token_cache_with_max_entries(max_entries: usize) -> TokenCache {
+    TokenCache::new(Arc::new(InMemoryTokenCacheBackend::new(max_entries)))
+}
+
+#[tokio::test]
+async fn least_recently_used_token_is_evicted_when_the_cache_is_full() {
+    let token_cache = token_cache_with_max_entries(2);
+    let expires_at = Utc::now() + chrono::Duration::minutes(15);
+    let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+    token_cache
+        .update_or_insert_key(first, Utc::now(), expires_at)
+        .await;
+    token_cache
+        .update_or_insert_key(second, Utc::now(), expires_at)
+        .await;
+    // The first token is now more recently used than the second one
+    assert!(token_cache.get_value_for_key(first).await.is_some());
+
+    token_cache
+        .update_or_insert_key(third, Utc::now(), expires_at)
+        .await;
+
+    assert!(token_cache.get_value_for_key(first).await.is_some());
+    assert!(token_cache.get_value_for_key(second).await.is_none());
+    assert!(token_cache.get_value_for_key(third).await.is_some());
+
+    let metrics = token_cache.metrics().await;
+    assert_eq!(metrics.size, 2);
+    assert_eq!(metrics.evictions, 1);
+}
+
+#[tokio::test]
+async fn expired_tokens_are_evicted() {
+    let token_cache = token_cache_with_max_entries(10);
+    let start = Utc::now();
+    let (short_lived, long_lived) = (Uuid::new_v4(), Uuid::new_v4());
+
+    token_cache
+        .update_or_insert_key(short_lived, start, start + chrono::Duration::minutes(5))
+        .await;
+    token_cache
+        .update_or_insert_key(long_lived, start, start + chrono::Duration::minutes(15))
+        .await;
+
+    override_now(Some((start + chrono::Duration::minutes(10)).fixed_offset()));
+
+    assert_eq!(token_cache.remove_expired().await, 1);
+    assert_eq!(token_cache.metrics().await.size, 1);
+    assert!(token_cache.get_value_for_key(long_lived).await.is_some());
+
+    // Also evicted when looked up before the sweeper runs
+    override_now(Some((start + chrono::Duration::minutes(20)).fixed_offset()));
+
+    assert!(token_cache.get_value_for_key(long_lived).await.is_none());
+    assert_eq!(token_cache.metrics().await.size, 0);
+}
+
+#[tokio::test]
+async fn token_cache_reports_its_hit_rate() {
+    let token_cache = token_cache_with_max_entries(10);
+    let token_id = Uuid::new_v4();
+
+    assert_eq!(token_cache.metrics().await.hit_rate(), 0.0);
+
+    assert!(token_cache.get_value_for_key(token_id).await.is_none());
+    token_cache
+        .update_or_insert_key(
+            token_id,
+            Utc::now(),
+            Utc::now() + chrono::Duration::minutes(15),
+        )
+        .await;
+    for _ in 0..3 {
+        assert!(token_cache.get_value_for_key(token_id).await.is_some());
+    }
+
+    let metrics = token_cache.metrics().await;
+    assert_eq!(metrics.hits, 3);
+    assert_eq!(metrics.misses, 1);
+    assert_eq!(metrics.hit_rate(), 0.75);
+}
@@ -0,0 +1,212 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use actix_http::{header, Request};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::ContentType;
+use actix_web::{test, Error};
+use chrono::Utc;
+use flutteractixapp::configuration::{RateLimitKey, RateLimitSettings};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::rate_limits::RateLimitStore;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, SignupRequest, SignupResponse,
+};
+use flutteractixapp::features::profile::application::dto::IsOtpEnabledRequest;
+use sqlx::PgPool;
+
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{get_test_configuration, spawn_app_with_configuration};
+
+fn limit(key: RateLimitKey, burst: u32, requests_per_minute: u32) -> RateLimitSettings {
+    RateLimitSettings {
+        key,
+        burst,
+        requests_per_minute,
+    }
+}
+
+async fn client_checks_if_otp_is_enabled(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    peer_addr: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/users/is-otp-enabled")
+        .insert_header(ContentType::json())
+        .peer_addr(peer_addr.parse::<SocketAddr>().unwrap())
+        .set_json(&IsOtpEnabledRequest {
+            username: "testusername".to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn user_fetches_profile(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn assert_too_many_requests(response: ServiceResponse<impl MessageBody>, retry_after: &str) {
+    assert_eq!(429, response.status().as_u16());
+    assert_eq!(
+        response.headers().get(header::RETRY_AFTER).unwrap(),
+        retry_after
+    );
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "TOO_MANY_REQUESTS");
+}
+
+#[sqlx::test]
+async fn is_otp_enabled_is_rate_limited_per_ip(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.rate_limits.is_otp_enabled = limit(RateLimitKey::Ip, 3, 60);
+    let app = spawn_app_with_configuration(pool, configuration).await;
+
+    let now = Utc::now();
+    override_now(Some(now.fixed_offset()));
+
+    for _ in 0..3 {
+        let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+        assert_eq!(200, response.status().as_u16());
+    }
+
+    let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+    assert_too_many_requests(response, "1").await;
+
+    // Other clients have their own bucket
+    let response = client_checks_if_otp_is_enabled(&app, "198.51.100.1:40000").await;
+    assert_eq!(200, response.status().as_u16());
+
+    // One request per second is refilled
+    override_now(Some((now + Duration::new(1, 0)).fixed_offset()));
+
+    let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+    assert_eq!(429, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn scopes_do_not_share_their_budget(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.rate_limits.is_otp_enabled = limit(RateLimitKey::Ip, 1, 1);
+    let app = spawn_app_with_configuration(pool, configuration).await;
+    override_now(Some(Utc::now().fixed_offset()));
+
+    let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+    assert_eq!(200, response.status().as_u16());
+
+    let response = client_checks_if_otp_is_enabled(&app, "203.0.113.7:40000").await;
+    assert_too_many_requests(response, "60").await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .peer_addr("203.0.113.7:40000".parse::<SocketAddr>().unwrap())
+        .set_json(&LoginRequest {
+            username: "unknown".to_string(),
+            password: "password1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn authenticated_routes_are_rate_limited_per_user(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.rate_limits.users = limit(RateLimitKey::User, 2, 1);
+    let app = spawn_app_with_configuration(pool, configuration).await;
+    override_now(Some(Utc::now().fixed_offset()));
+
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let signup_request = SignupRequest {
+        username: "otherusername".to_string(),
+        password: "password1_".to_string(),
+        locale: "en".to_string(),
+        theme: "dark".to_string(),
+    };
+    let req = test::TestRequest::post()
+        .uri("/api/auth/signup")
+        .insert_header(ContentType::json())
+        .set_json(&signup_request)
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    let body = test::read_body(response).await;
+    let other_user: SignupResponse = serde_json::from_slice(&body).unwrap();
+
+    for _ in 0..2 {
+        let response = user_fetches_profile(&app, &access_token).await;
+        assert_eq!(200, response.status().as_u16());
+    }
+
+    let response = user_fetches_profile(&app, &access_token).await;
+    assert_too_many_requests(response, "60").await;
+
+    // Another user is not affected
+    let response = user_fetches_profile(&app, &other_user.access_token).await;
+    assert_eq!(200, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn limits_that_never_let_a_request_through_are_rejected() {
+    let mut configuration = get_test_configuration();
+    assert!(configuration.rate_limits.validate().is_ok());
+
+    configuration.rate_limits.users = limit(RateLimitKey::User, 5, 0);
+    assert!(configuration.rate_limits.validate().is_err());
+
+    configuration.rate_limits.users = limit(RateLimitKey::User, 0, 60);
+    assert!(configuration.rate_limits.validate().is_err());
+}
+
+#[tokio::test]
+async fn least_recently_used_bucket_is_dropped_when_the_store_is_full() {
+    let store = RateLimitStore::new(2);
+    let now = Utc::now();
+    let acquire = |key: &str| store.try_acquire(key.to_string(), 2.0, 1.0, now);
+
+    for key in ["a", "b", "a", "c"] {
+        assert!(acquire(key).await.is_ok());
+    }
+
+    assert_eq!(store.tracked_buckets().await, 2);
+    // "b" was dropped to make room for "c", its client starts over with a full bucket
+    assert!(acquire("a").await.is_err());
+    assert!(acquire("b").await.is_ok());
+}
+
+#[tokio::test]
+async fn full_buckets_are_swept() {
+    let store = RateLimitStore::default();
+    let now = Utc::now();
+    store
+        .try_acquire("a".to_string(), 2.0, 1.0, now)
+        .await
+        .unwrap();
+    store
+        .try_acquire("b".to_string(), 2.0, 0.1, now)
+        .await
+        .unwrap();
+
+    let removed = store.remove_full(now + chrono::Duration::seconds(2)).await;
+
+    assert_eq!(removed, 1);
+    assert_eq!(store.tracked_buckets().await, 1);
+}
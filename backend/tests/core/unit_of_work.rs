@@ -0,0 +1,190 @@
+use actix_web::http::header::{self, ContentType};
+use actix_web::test;
+use flutteractixapp::core::structs::transaction::{self, PgUnitOfWork, TransactionError};
+use flutteractixapp::core::structs::unit_of_work::UnitOfWork;
+use flutteractixapp::features::admin::application::dto::SetUserStatusRequest;
+use flutteractixapp::features::auth::application::dto::{
+    DeleteAccountRequest, RecoverAccountWithout2FAEnabledRequest,
+};
+use flutteractixapp::features::auth::domain::entities::AccountStatus;
+use sqlx::PgPool;
+
+use crate::admin::users::{admin_and_user_sign_up, admin_sends_a_request, assert_error};
+use crate::auth::recovery::recover_account_without_2fa_enabled::user_recovers_account_without_2fa_enabled;
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app;
+use crate::profile::profile::user_has_access_to_protected_route;
+
+// Makes the next `operation` on `table` fail, as if the database failed midway through a use case
+async fn writes_are_rejected(pool: &PgPool, operation: &str, table: &str) {
+    sqlx::query(
+        r#"
+        CREATE OR REPLACE FUNCTION reject_write() RETURNS TRIGGER AS $$
+        BEGIN
+            RAISE EXCEPTION 'write rejected';
+        END;
+        $$ LANGUAGE plpgsql
+        "#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(&format!(
+        "CREATE TRIGGER {table}_rejected BEFORE {operation} ON {table}
+            FOR EACH ROW EXECUTE FUNCTION reject_write()"
+    ))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn writes_are_accepted(pool: &PgPool, table: &str) {
+    sqlx::query(&format!("DROP TRIGGER {table}_rejected ON {table}"))
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn count(pool: &PgPool, query: &str) -> i64 {
+    let (count,): (i64,) = sqlx::query_as(query).fetch_one(pool).await.unwrap();
+    count
+}
+
+#[sqlx::test]
+async fn account_recovery_is_rolled_back_when_the_user_cannot_be_updated(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+    writes_are_rejected(&pool, "UPDATE", "users").await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/recover")
+        .insert_header(ContentType::json())
+        .set_json(&RecoverAccountWithout2FAEnabledRequest {
+            username: "testusername".to_string(),
+            recovery_code: recovery_codes[0].clone(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+    // The sessions were deleted before the user update failed, and are back
+    user_has_access_to_protected_route(&app, &access_token).await;
+    assert_eq!(
+        count(
+            &pool,
+            "SELECT COUNT(*) FROM recovery_codes WHERE used_at IS NULL"
+        )
+        .await,
+        recovery_codes.len() as i64
+    );
+    assert_eq!(
+        count(
+            &pool,
+            "SELECT COUNT(*) FROM outbox_events WHERE event_type = 'ACCOUNT_RECOVERED'"
+        )
+        .await,
+        0
+    );
+
+    writes_are_accepted(&pool, "users").await;
+    user_recovers_account_without_2fa_enabled(&app, &recovery_codes[0]).await;
+}
+
+#[sqlx::test]
+async fn account_deletion_is_not_scheduled_when_the_sessions_cannot_be_revoked(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    writes_are_rejected(&pool, "DELETE", "user_tokens").await;
+
+    let req = test::TestRequest::delete()
+        .uri("/api/users/me")
+        .insert_header(ContentType::json())
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .set_json(&DeleteAccountRequest {
+            password: "password1_".to_string(),
+            code: None,
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_error(response, 500, "ACCOUNT_DELETION_ERROR").await;
+    assert_eq!(
+        count(
+            &pool,
+            "SELECT COUNT(*) FROM users WHERE deletion_scheduled_at IS NOT NULL"
+        )
+        .await,
+        0
+    );
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn account_status_is_kept_when_the_sessions_cannot_be_revoked(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (admin_access_token, user_access_token, user_id) =
+        admin_and_user_sign_up(&app, &pool).await;
+    writes_are_rejected(&pool, "DELETE", "user_tokens").await;
+
+    let response = admin_sends_a_request(
+        &app,
+        &admin_access_token,
+        test::TestRequest::put()
+            .uri(&format!("/api/admin/users/{}/status", user_id))
+            .insert_header(ContentType::json())
+            .set_json(&SetUserStatusRequest {
+                status: AccountStatus::Suspended,
+                reason: Some("Spam".to_string()),
+                until: None,
+            }),
+    )
+    .await;
+
+    assert_error(response, 500, "ACCOUNT_STATUS_UPDATE_ERROR").await;
+    assert_eq!(
+        count(&pool, "SELECT COUNT(*) FROM users WHERE status <> 'active'").await,
+        0
+    );
+    user_has_access_to_protected_route(&app, &user_access_token).await;
+}
+
+#[sqlx::test]
+async fn nested_unit_of_work_is_part_of_the_outer_one(pool: PgPool) {
+    sqlx::query("CREATE TABLE scratch (value INT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let unit_of_work: Box<dyn UnitOfWork> = Box::new(PgUnitOfWork::new(pool.clone()));
+    let insert = |value: i32| {
+        let pool = pool.clone();
+        async move {
+            let mut conn = transaction::acquire(&pool).await?;
+            sqlx::query("INSERT INTO scratch (value) VALUES ($1)")
+                .bind(value)
+                .execute(&mut *conn)
+                .await?;
+            Ok::<_, TransactionError>(())
+        }
+    };
+
+    let result: Result<(), TransactionError> = unit_of_work
+        .run(|| async {
+            insert(1).await?;
+            unit_of_work.run(|| async { insert(2).await }).await?;
+            Err(sqlx::Error::RowNotFound.into())
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM scratch").await, 0);
+
+    unit_of_work
+        .run(|| async {
+            insert(1).await?;
+            unit_of_work.run(|| async { insert(2).await }).await
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(count(&pool, "SELECT COUNT(*) FROM scratch").await, 2);
+}
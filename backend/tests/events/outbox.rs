@@ -0,0 +1,249 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use actix_web::http::header::ContentType;
+use actix_web::test;
+use chrono::{DateTime, Duration, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::features::auth::application::dto::RecoverAccountUsingPasswordRequest;
+use flutteractixapp::features::events::domain::entities::DomainEvent;
+use flutteractixapp::features::events::domain::errors::EventsDomainError;
+use flutteractixapp::features::events::domain::repositories::EventHandler;
+use flutteractixapp::features::events::domain::services::{DispatchSettings, EventDispatcher};
+use flutteractixapp::features::events::infrastructure::repositories::OutboxRepositoryImpl;
+use sqlx::PgPool;
+
+use crate::auth::otp::{user_generates_otp, user_verifies_otp};
+use crate::auth::recovery::recover_account_using_password::user_recovers_account_using_password;
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app;
+use crate::profile::profile::user_has_access_to_protected_route;
+use crate::profile::update_password::user_updates_password;
+
+// Keeps the events it handles, after failing on the first `failures` ones
+#[derive(Default)]
+struct RecordingHandler {
+    failures: AtomicUsize,
+    events: Mutex<Vec<DomainEvent>>,
+}
+
+#[async_trait::async_trait]
+impl EventHandler for RecordingHandler {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), EventsDomainError> {
+        self.events.lock().unwrap().push(event.clone());
+
+        match self.failures.load(Ordering::SeqCst) {
+            0 => Ok(()),
+            failures => {
+                self.failures.store(failures - 1, Ordering::SeqCst);
+                Err(EventsDomainError::SerializationError)
+            }
+        }
+    }
+}
+
+fn new_dispatcher(pool: &PgPool, handler: Arc<RecordingHandler>) -> EventDispatcher {
+    EventDispatcher::new(
+        Box::new(OutboxRepositoryImpl::new(pool.clone())),
+        DispatchSettings {
+            batch_size: 100,
+            lease: Duration::seconds(60),
+            retry_delay: Duration::seconds(30),
+            max_retry_delay: Duration::hours(1),
+            max_attempts: 3,
+        },
+    )
+    .with_handler(handler)
+}
+
+async fn outbox_events(pool: &PgPool) -> Vec<DomainEvent> {
+    let payloads: Vec<(String,)> =
+        sqlx::query_as("SELECT payload FROM outbox_events ORDER BY created_at")
+            .fetch_all(pool)
+            .await
+            .unwrap();
+
+    payloads
+        .into_iter()
+        .map(|(payload,)| serde_json::from_str(&payload).unwrap())
+        .collect()
+}
+
+fn event_names(events: &[DomainEvent]) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = events.iter().map(|event| event.name()).collect();
+    names.sort();
+    names
+}
+
+#[sqlx::test]
+async fn account_changes_are_published_to_the_outbox(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    user_updates_password(&app, &access_token, "password1_", "password2_").await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let events = outbox_events(&pool).await;
+
+    assert_eq!(
+        event_names(&events),
+        vec!["OTP_ENABLED", "PASSWORD_CHANGED", "USER_SIGNED_UP"]
+    );
+    assert!(events
+        .iter()
+        .all(|event| event.user_id() == events[0].user_id()));
+}
+
+#[sqlx::test]
+async fn account_recovery_publishes_the_revoked_sessions(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    sqlx::query("DELETE FROM outbox_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    user_recovers_account_using_password(&app, &recovery_codes[0], "password1_").await;
+    let events = outbox_events(&pool).await;
+
+    assert_eq!(
+        event_names(&events),
+        vec!["ACCOUNT_RECOVERED", "OTP_DISABLED", "SESSION_REVOKED"]
+    );
+}
+
+#[sqlx::test]
+async fn account_recovery_is_undone_when_its_events_cannot_be_published(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    sqlx::query(
+        r#"
+        CREATE FUNCTION reject_outbox_event() RETURNS TRIGGER AS $$
+        BEGIN
+            RAISE EXCEPTION 'outbox unavailable';
+        END;
+        $$ LANGUAGE plpgsql
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "CREATE TRIGGER outbox_events_unavailable BEFORE INSERT ON outbox_events
+            FOR EACH ROW EXECUTE FUNCTION reject_outbox_event()",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/recover-using-password")
+        .insert_header(ContentType::json())
+        .set_json(&RecoverAccountUsingPasswordRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+            recovery_code: recovery_codes[0].clone(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+    // The sessions, the second factor and the recovery code are left as they were
+    user_has_access_to_protected_route(&app, &access_token).await;
+    let (otp_verified,): (bool,) = sqlx::query_as("SELECT otp_verified FROM users")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(otp_verified);
+
+    sqlx::query("DROP TRIGGER outbox_events_unavailable ON outbox_events")
+        .execute(&pool)
+        .await
+        .unwrap();
+    user_recovers_account_using_password(&app, &recovery_codes[0], "password1_").await;
+}
+
+#[sqlx::test]
+async fn dispatcher_delivers_each_event_once_to_the_handlers(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    user_updates_password(&app, &access_token, "password1_", "password2_").await;
+    let handler = Arc::new(RecordingHandler::default());
+    let dispatcher = new_dispatcher(&pool, handler.clone());
+
+    assert_eq!(dispatcher.dispatch_pending().await.unwrap(), 2);
+    assert_eq!(dispatcher.dispatch_pending().await.unwrap(), 0);
+
+    let events = handler.events.lock().unwrap().clone();
+    assert_eq!(events, outbox_events(&pool).await);
+    assert_eq!(
+        event_names(&events),
+        vec!["PASSWORD_CHANGED", "USER_SIGNED_UP"]
+    );
+}
+
+#[sqlx::test]
+async fn event_is_delivered_again_after_a_handler_failed(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    let handler = Arc::new(RecordingHandler {
+        failures: AtomicUsize::new(1),
+        ..Default::default()
+    });
+    let dispatcher = new_dispatcher(&pool, handler.clone());
+
+    assert_eq!(dispatcher.dispatch_pending().await.unwrap(), 0);
+    let (last_error,): (Option<String>,) = sqlx::query_as("SELECT last_error FROM outbox_events")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(last_error.unwrap().contains("recording"));
+
+    // Not before the retry delay
+    assert_eq!(dispatcher.dispatch_pending().await.unwrap(), 0);
+
+    override_now(Some((Utc::now() + Duration::minutes(1)).fixed_offset()));
+    let delivered = dispatcher.dispatch_pending().await.unwrap();
+    override_now(None);
+
+    assert_eq!(delivered, 1);
+    assert_eq!(
+        event_names(&handler.events.lock().unwrap()),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP"]
+    );
+}
+
+#[sqlx::test]
+async fn event_is_given_up_on_after_the_maximum_attempts(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    let handler = Arc::new(RecordingHandler {
+        failures: AtomicUsize::new(usize::MAX),
+        ..Default::default()
+    });
+    let dispatcher = new_dispatcher(&pool, handler.clone());
+
+    for hours in 0..5 {
+        override_now(Some((Utc::now() + Duration::hours(hours)).fixed_offset()));
+        assert_eq!(dispatcher.dispatch_pending().await.unwrap(), 0);
+    }
+    override_now(None);
+
+    assert_eq!(handler.events.lock().unwrap().len(), 3);
+    let (attempts, last_error, dead_at): (i32, Option<String>, Option<DateTime<Utc>>) =
+        sqlx::query_as("SELECT attempts, last_error, dead_at FROM outbox_events")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(attempts, 3);
+    assert!(last_error.unwrap().contains("recording"));
+    assert!(dead_at.is_some());
+}
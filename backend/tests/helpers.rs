@@ -6,32 +6,99 @@ use actix_web::{
     Error,
 };
 use flutteractixapp::{
-    configuration::get_configuration, features::auth::structs::models::TokenCache,
+    configuration::{get_configuration, Settings},
+    core::structs::rate_limits::RateLimitStore,
+    features::auth::infrastructure::session_activity::SessionActivityWriter,
+    features::auth::structs::models::{LockoutCache, TokenCache},
     startup::create_app,
 };
 use sqlx::PgPool;
 use uuid::Uuid;
 
+pub fn get_test_configuration() -> Settings {
+    // Randomise configuration to ensure test isolation
+    let mut c = get_configuration().expect("Failed to read configuration.");
+    // Use a different database for each test case
+    c.database.database_name = Uuid::new_v4().to_string();
+    // Use a random OS port
+    c.application.port = 0;
+    c
+}
+
 pub async fn spawn_app(
     pool: PgPool,
 ) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
-    // Randomise configuration to ensure test isolation
-    let configuration = {
-        let mut c = get_configuration().expect("Failed to read configuration.");
-        // Use a different database for each test case
-        c.database.database_name = Uuid::new_v4().to_string();
-        // Use a random OS port
-        c.application.port = 0;
-        c
-    };
-
-    let token_cache = TokenCache::default();
-    let secret = configuration.application.secret;
+    spawn_app_with_configuration(pool, get_test_configuration()).await
+}
+
+pub async fn spawn_app_with_configuration(
+    pool: PgPool,
+    configuration: Settings,
+) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    let session_activity_writer =
+        SessionActivityWriter::new(pool.clone(), configuration.session_activity.clone());
+
+    spawn_app_with_session_activity_writer(pool, configuration, session_activity_writer).await
+}
+
+// The activity is only written when the test flushes the writer
+pub async fn spawn_app_with_session_activity_writer(
+    pool: PgPool,
+    configuration: Settings,
+    session_activity_writer: SessionActivityWriter,
+) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    spawn_app_with_state(
+        pool,
+        configuration,
+        TokenCache::default(),
+        session_activity_writer,
+    )
+    .await
+}
+
+pub async fn spawn_app_with_token_cache(
+    pool: PgPool,
+    token_cache: TokenCache,
+) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    let configuration = get_test_configuration();
+    let session_activity_writer =
+        SessionActivityWriter::new(pool.clone(), configuration.session_activity.clone());
+
+    spawn_app_with_state(pool, configuration, token_cache, session_activity_writer).await
+}
+
+async fn spawn_app_with_state(
+    pool: PgPool,
+    configuration: Settings,
+    token_cache: TokenCache,
+    session_activity_writer: SessionActivityWriter,
+) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    let lockout_cache = LockoutCache::default();
+    let rate_limit_store = RateLimitStore::default();
 
     init_service(create_app(
         pool.clone(),
-        secret.clone(),
+        configuration,
         token_cache.clone(),
+        lockout_cache.clone(),
+        rate_limit_store.clone(),
+        session_activity_writer,
     ))
     .await
 }
+
+// The tokens issued from now on carry the permissions of the role
+pub async fn user_is_granted_role(pool: &PgPool, username: &str, role: &str) {
+    sqlx::query(
+        "INSERT INTO user_roles (user_id, role) SELECT id, $2 FROM users WHERE username = $1",
+    )
+    .bind(username)
+    .bind(role)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+pub async fn user_becomes_admin(pool: &PgPool, username: &str) {
+    user_is_granted_role(pool, username, "admin").await;
+}
@@ -0,0 +1,147 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::{self, ContentType};
+use actix_web::{test, Error};
+use chrono::{Duration, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::LoginRequest;
+use flutteractixapp::features::maintenance::application::dto::MaintenanceResponse;
+use sqlx::PgPool;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{user_generates_otp, user_logs_in_with_otp_enabled, user_verifies_otp};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{
+    get_test_configuration, spawn_app, spawn_app_with_configuration, user_becomes_admin,
+};
+use crate::profile::devices::another_user_signs_up;
+
+async fn admin_runs_maintenance(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/admin/maintenance/run")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+pub async fn maintenance_is_run(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> MaintenanceResponse {
+    let response = admin_runs_maintenance(&app, access_token).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: MaintenanceResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "MAINTENANCE_COMPLETED");
+    response
+}
+
+async fn count_rows(pool: &PgPool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn expired_sessions_are_purged(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    user_signs_up(&app).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+    user_becomes_admin(&pool, "testusername").await;
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    // Nothing has expired yet
+    let response = maintenance_is_run(&app, &access_token).await;
+    assert_eq!(response.expired_tokens, 0);
+    assert_eq!(count_rows(&pool, "user_tokens").await, 3);
+
+    // Once the refresh tokens have expired, only the session started afterwards is kept
+    override_now(Some((Utc::now() + Duration::days(8)).fixed_offset()));
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let response = maintenance_is_run(&app, &access_token).await;
+    override_now(None);
+
+    assert_eq!(response.expired_tokens, 3);
+    assert_eq!(count_rows(&pool, "user_tokens").await, 1);
+}
+
+#[sqlx::test]
+async fn expired_sessions_are_purged_in_batches(pool: PgPool) {
+    let mut configuration = get_test_configuration();
+    configuration.maintenance.batch_size = 1;
+    let app = spawn_app_with_configuration(pool.clone(), configuration).await;
+    user_signs_up(&app).await;
+    user_logs_in(&app, "testusername", "password1_").await;
+    user_logs_in(&app, "testusername", "password1_").await;
+    user_becomes_admin(&pool, "testusername").await;
+
+    override_now(Some((Utc::now() + Duration::days(8)).fixed_offset()));
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let response = maintenance_is_run(&app, &access_token).await;
+    override_now(None);
+
+    assert_eq!(response.expired_tokens, 3);
+    assert_eq!(count_rows(&pool, "user_tokens").await, 1);
+}
+
+#[sqlx::test]
+async fn stale_login_attempts_and_mfa_challenges_are_purged(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+    user_logs_in_with_otp_enabled(&app).await;
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "wrong_password".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    assert_eq!(401, response.status().as_u16());
+    another_user_signs_up(&app).await;
+    user_becomes_admin(&pool, "otherusername").await;
+    let (admin_access_token, _) = user_logs_in(&app, "otherusername", "password1_").await;
+
+    // The failed attempts still count towards a lockout and the challenge is still valid
+    let response = maintenance_is_run(&app, &admin_access_token).await;
+    assert_eq!(response.expired_mfa_challenges, 0);
+    assert_eq!(response.stale_login_attempts, 0);
+
+    override_now(Some((Utc::now() + Duration::hours(1)).fixed_offset()));
+    let (admin_access_token, _) = user_logs_in(&app, "otherusername", "password1_").await;
+    let response = maintenance_is_run(&app, &admin_access_token).await;
+    override_now(None);
+
+    assert_eq!(response.expired_mfa_challenges, 1);
+    assert!(response.stale_login_attempts > 0);
+    assert_eq!(count_rows(&pool, "mfa_challenges").await, 0);
+    assert_eq!(count_rows(&pool, "login_attempts").await, 0);
+}
+
+#[sqlx::test]
+async fn maintenance_cannot_be_run_by_a_user_who_is_not_admin(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let response = admin_runs_maintenance(&app, &access_token).await;
+
+    assert_eq!(403, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "FORBIDDEN");
+}
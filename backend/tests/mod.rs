@@ -1,7 +1,21 @@
+pub mod admin {
+    pub mod account_status;
+    pub mod users;
+}
+
+pub mod audit {
+    pub mod security_events;
+}
+
 pub mod auth {
+    pub mod jwks;
     pub mod login;
+    pub mod login_throttling;
     pub mod logout;
+    pub mod mfa_challenge;
+    pub mod new_device;
     pub mod otp;
+    pub mod otp_secrets;
     pub mod recovery {
         pub mod recover_account_using_2fa;
         pub mod recover_account_using_password;
@@ -9,17 +23,32 @@ pub mod auth {
     }
     pub mod signup;
     pub mod token;
+    pub mod token_cache;
+}
+
+pub mod events {
+    pub mod outbox;
 }
 
 pub mod profile {
+    pub mod account_deletion;
+    pub mod data_export;
     pub mod devices;
+    #[allow(clippy::module_inception)]
     pub mod profile;
+    pub mod recovery_codes;
     pub mod set_password;
     pub mod update_password;
 }
 
+pub mod maintenance {
+    pub mod run_maintenance;
+}
+
 pub mod core {
     pub mod health_check;
+    pub mod rate_limiting;
+    pub mod unit_of_work;
 }
 
 pub mod helpers;
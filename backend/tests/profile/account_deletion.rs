@@ -0,0 +1,162 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::{self, ContentType};
+use actix_web::{test, Error};
+use chrono::{Duration, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::{
+    AccountDeletionResponse, DeleteAccountRequest, LoginRequest,
+};
+use sqlx::PgPool;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::{otp_code, user_generates_otp, user_verifies_otp, wait_for_next_otp_step};
+use crate::auth::signup::user_signs_up;
+use crate::helpers::{spawn_app, user_becomes_admin};
+use crate::maintenance::run_maintenance::maintenance_is_run;
+use crate::profile::devices::{another_user_signs_up, assert_access_token_is_revoked};
+use crate::profile::profile::user_has_access_to_protected_route;
+
+async fn user_deletes_account(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+    password: &str,
+    code: Option<String>,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::delete()
+        .uri("/api/users/me")
+        .insert_header(ContentType::json())
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .set_json(&DeleteAccountRequest {
+            password: password.to_string(),
+            code,
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn assert_deletion_scheduled(response: ServiceResponse<impl MessageBody>) {
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: AccountDeletionResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "ACCOUNT_DELETION_SCHEDULED");
+    assert!(response.deletion_scheduled_at > Utc::now() + Duration::days(29));
+}
+
+async fn assert_error(response: ServiceResponse<impl MessageBody>, code: &str) {
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, code);
+}
+
+// The other user is an admin, who runs the maintenance once the grace period is over
+async fn maintenance_is_run_after_the_grace_period(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    pool: &PgPool,
+) -> u64 {
+    another_user_signs_up(&app).await;
+    user_becomes_admin(pool, "otherusername").await;
+
+    override_now(Some((Utc::now() + Duration::days(31)).fixed_offset()));
+    let (admin_access_token, _) = user_logs_in(&app, "otherusername", "password1_").await;
+    let response = maintenance_is_run(&app, &admin_access_token).await;
+    override_now(None);
+
+    response.deleted_accounts
+}
+
+async fn user_exists(pool: &PgPool, username: &str) -> bool {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE username = $1)")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn user_is_signed_out_when_scheduling_the_deletion_of_their_account(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let (other_access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    let response = user_deletes_account(&app, &access_token, "password1_", None).await;
+
+    assert_deletion_scheduled(response).await;
+    assert_access_token_is_revoked(&app, &access_token).await;
+    assert_access_token_is_revoked(&app, &other_access_token).await;
+}
+
+#[sqlx::test]
+async fn account_deletion_requires_the_password(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let response = user_deletes_account(&app, &access_token, "wrong_password", None).await;
+
+    assert_error(response, "INVALID_PASSWORD").await;
+    user_has_access_to_protected_route(&app, &access_token).await;
+}
+
+#[sqlx::test]
+async fn account_deletion_requires_the_otp_when_2fa_is_enabled(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+    user_verifies_otp(&app, &access_token, &otp_base32).await;
+
+    let response = user_deletes_account(&app, &access_token, "password1_", None).await;
+    assert_error(response, "INVALID_ONE_TIME_PASSWORD").await;
+
+    wait_for_next_otp_step();
+    let code = otp_code(&otp_base32);
+    let response = user_deletes_account(&app, &access_token, "password1_", Some(code)).await;
+    override_now(None);
+
+    assert_deletion_scheduled(response).await;
+}
+
+#[sqlx::test]
+async fn account_is_deleted_once_the_grace_period_is_over(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let response = user_deletes_account(&app, &access_token, "password1_", None).await;
+    assert_deletion_scheduled(response).await;
+
+    let deleted_accounts = maintenance_is_run_after_the_grace_period(&app, &pool).await;
+
+    assert_eq!(deleted_accounts, 1);
+    assert!(!user_exists(&pool, "testusername").await);
+
+    let req = test::TestRequest::post()
+        .uri("/api/auth/login")
+        .insert_header(ContentType::json())
+        .set_json(&LoginRequest {
+            username: "testusername".to_string(),
+            password: "password1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    assert_error(response, "INVALID_USERNAME_OR_PASSWORD").await;
+}
+
+#[sqlx::test]
+async fn logging_in_again_cancels_the_deletion(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let response = user_deletes_account(&app, &access_token, "password1_", None).await;
+    assert_deletion_scheduled(response).await;
+
+    user_logs_in(&app, "testusername", "password1_").await;
+    let deleted_accounts = maintenance_is_run_after_the_grace_period(&app, &pool).await;
+
+    assert_eq!(deleted_accounts, 0);
+    assert!(user_exists(&pool, "testusername").await);
+}
@@ -0,0 +1,234 @@
+use actix_http::Request;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::{self, ContentType};
+use actix_web::{test, Error};
+use chrono::{Duration, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::envelope_cipher::EnvelopeCipher;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::audit::domain::entities::SecurityEventType;
+use flutteractixapp::features::auth::application::dto::RefreshTokenRequest;
+use flutteractixapp::features::profile::application::dto::{
+    DataArchive, DataExportResponse, DownloadDataExportRequest,
+};
+use sqlx::PgPool;
+
+use crate::auth::login::user_logs_in;
+use crate::auth::otp::user_generates_otp;
+use crate::auth::signup::user_signs_up;
+use crate::auth::token::user_refreshes_token;
+use crate::helpers::spawn_app;
+
+async fn user_requests_data_export(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me/export")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+// The archive is built in the background, so the user asks again until it is ready
+async fn user_gets_data_export_token(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> String {
+    for _ in 0..50 {
+        let response = user_requests_data_export(&app, access_token).await;
+        let status = response.status().as_u16();
+
+        let body = test::read_body(response).await;
+        let response: DataExportResponse = serde_json::from_slice(&body).unwrap();
+
+        if status == 200 {
+            assert_eq!(response.code, "DATA_EXPORT_READY");
+            assert!(response.expires_at > Utc::now() + Duration::hours(23));
+            return response.download_token.unwrap();
+        }
+
+        assert_eq!(202, status);
+        assert!(["DATA_EXPORT_STARTED", "DATA_EXPORT_PENDING"].contains(&response.code.as_str()));
+        assert!(response.download_token.is_none());
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+
+    panic!("The data export is still pending");
+}
+
+async fn user_downloads_data_export(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    download_token: &str,
+) -> ServiceResponse<impl MessageBody> {
+    // The download token stands in for the access token
+    let req = test::TestRequest::post()
+        .uri("/api/users/me/export/download")
+        .insert_header(ContentType::json())
+        .set_json(&DownloadDataExportRequest {
+            token: download_token.to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn assert_archive(response: ServiceResponse<impl MessageBody>) -> DataArchive {
+    assert_eq!(200, response.status().as_u16());
+    assert!(response
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with("attachment"));
+
+    let body = test::read_body(response).await;
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn assert_token_is_rejected(response: ServiceResponse<impl MessageBody>) {
+    assert_eq!(404, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_DATA_EXPORT_LINK");
+}
+
+#[sqlx::test]
+async fn user_can_download_an_archive_of_their_data(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (_, refresh_token, _) = user_signs_up(&app).await;
+
+    // Replaying a rotated refresh token records a security event
+    user_refreshes_token(&app, &refresh_token).await;
+    let req = test::TestRequest::post()
+        .uri("/api/auth/refresh-token")
+        .insert_header(ContentType::json())
+        .set_json(&RefreshTokenRequest { refresh_token })
+        .to_request();
+    test::call_service(&app, req).await;
+
+    let (access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let download_token = user_gets_data_export_token(&app, &access_token).await;
+    let response = user_downloads_data_export(&app, &download_token).await;
+    let archive = assert_archive(response).await;
+
+    assert_eq!(archive.profile.username, "testusername");
+    assert_eq!(archive.preferences.theme, "dark");
+    // The session started at signup was revoked with the reuse
+    assert_eq!(archive.devices.len(), 1);
+    let event_types: Vec<SecurityEventType> = archive
+        .security_events
+        .iter()
+        .map(|event| event.event_type)
+        .collect();
+    assert_eq!(event_types.len(), 2);
+    assert!(event_types.contains(&SecurityEventType::RefreshTokenReuseDetected));
+    assert!(event_types.contains(&SecurityEventType::LoginSucceeded));
+}
+
+#[sqlx::test]
+async fn archive_is_stored_encrypted_without_the_otp_secret(pool: PgPool) {
+    let app = spawn_app(pool.clone()).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, &access_token).await;
+
+    let download_token = user_gets_data_export_token(&app, &access_token).await;
+    let (stored_archive,): (String,) = sqlx::query_as("SELECT archive FROM data_exports")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    assert!(EnvelopeCipher::is_encrypted(&stored_archive));
+    assert!(!stored_archive.contains("testusername"));
+
+    let response = user_downloads_data_export(&app, &download_token).await;
+    assert_eq!(200, response.status().as_u16());
+    let archive = String::from_utf8(test::read_body(response).await.to_vec()).unwrap();
+
+    assert!(archive.contains("testusername"));
+    assert!(!archive.contains(&otp_base32));
+    assert!(!archive.contains("otp_auth_url"));
+}
+
+#[sqlx::test]
+async fn data_export_token_can_only_be_used_once(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let download_token = user_gets_data_export_token(&app, &access_token).await;
+    let response = user_downloads_data_export(&app, &download_token).await;
+    assert_archive(response).await;
+
+    let response = user_downloads_data_export(&app, &download_token).await;
+    assert_token_is_rejected(response).await;
+
+    // Once downloaded, asking again builds a new archive
+    let response = user_requests_data_export(&app, &access_token).await;
+    assert_eq!(202, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: DataExportResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "DATA_EXPORT_STARTED");
+}
+
+#[sqlx::test]
+async fn data_export_token_expires(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let download_token = user_gets_data_export_token(&app, &access_token).await;
+
+    override_now(Some((Utc::now() + Duration::hours(25)).fixed_offset()));
+    let response = user_downloads_data_export(&app, &download_token).await;
+    override_now(None);
+
+    assert_token_is_rejected(response).await;
+}
+
+#[sqlx::test]
+async fn data_export_token_must_be_signed_for_the_export(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    let download_token = user_gets_data_export_token(&app, &access_token).await;
+
+    let tampered_token = format!("{}x", download_token);
+    let response = user_downloads_data_export(&app, &tampered_token).await;
+    assert_token_is_rejected(response).await;
+
+    // A session token is signed with the same keys, but isn't a download token
+    let response = user_downloads_data_export(&app, &access_token).await;
+    assert_token_is_rejected(response).await;
+
+    // The token isn't taken from the URL, which is logged
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/api/users/me/export/download?token={}",
+            download_token
+        ))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+    assert!(!response.status().is_success());
+
+    // The rejected attempts didn't use the token
+    let response = user_downloads_data_export(&app, &download_token).await;
+    assert_archive(response).await;
+}
+
+#[sqlx::test]
+async fn data_export_requires_authentication(pool: PgPool) {
+    let app = spawn_app(pool).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/users/me/export")
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+}
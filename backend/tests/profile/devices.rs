@@ -1,15 +1,23 @@
 use actix_http::Request;
 use actix_web::body::MessageBody;
 use actix_web::dev::{Service, ServiceResponse};
-use actix_web::http::header::{self};
+use actix_web::http::header::{self, ContentType};
 use actix_web::{test, Error};
-use flutteractixapp::features::profile::application::dto::{DeviceData, DeviceDeleteResponse, DevicesResponse};
+use chrono::{Duration, SubsecRound, Utc};
+use flutteractixapp::core::helpers::mock_now::override_now;
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::{SignupRequest, SignupResponse};
+use flutteractixapp::features::auth::infrastructure::session_activity::SessionActivityWriter;
+use flutteractixapp::features::profile::application::dto::{
+    DeviceData, DeviceDeleteResponse, DevicesResponse,
+};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::auth::login::user_logs_in;
 use crate::auth::signup::user_signs_up;
-use crate::helpers::spawn_app;
+use crate::helpers::{get_test_configuration, spawn_app, spawn_app_with_session_activity_writer};
+use crate::profile::profile::user_has_access_to_protected_route;
 
 pub async fn user_gets_list_of_devices(
     app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
@@ -49,6 +57,80 @@ pub async fn user_removes_a_device(
     assert_eq!(response.code, "DEVICE_DELETED");
 }
 
+pub async fn user_removes_other_devices(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) {
+    let req = test::TestRequest::delete()
+        .uri("/api/devices/others")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: DeviceDeleteResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "OTHER_DEVICES_DELETED");
+}
+
+async fn user_sends_a_request_from(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+    ip: &str,
+) {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .peer_addr(format!("{}:12345", ip).parse().unwrap())
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+}
+
+pub async fn assert_access_token_is_revoked(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_ACCESS_TOKEN");
+}
+
+pub async fn another_user_signs_up(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+) -> String {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/signup")
+        .insert_header(ContentType::json())
+        .set_json(&SignupRequest {
+            username: "otherusername".to_string(),
+            password: "password1_".to_string(),
+            locale: "en".to_string(),
+            theme: "dark".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(201, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: SignupResponse = serde_json::from_slice(&body).unwrap();
+
+    response.access_token
+}
+
 #[sqlx::test]
 async fn user_can_remove_session_on_another_device(pool: PgPool) {
     let app = spawn_app(pool).await;
@@ -67,3 +149,165 @@ async fn user_can_remove_session_on_another_device(pool: PgPool) {
 
     user_removes_a_device(&app, &new_access_token, first_device_id).await;
 }
+
+#[sqlx::test]
+async fn removed_device_cannot_access_protected_routes(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (initial_access_token, _, _) = user_signs_up(&app).await;
+
+    // The token is now cached by the token validator
+    user_has_access_to_protected_route(&app, &initial_access_token).await;
+
+    let first_device_id = user_gets_list_of_devices(&app, &initial_access_token).await[0].token_id;
+    let (new_access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+
+    user_removes_a_device(&app, &new_access_token, first_device_id).await;
+
+    assert_access_token_is_revoked(&app, &initial_access_token).await;
+    user_has_access_to_protected_route(&app, &new_access_token).await;
+}
+
+#[sqlx::test]
+async fn user_cannot_remove_a_device_of_another_user(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+    let other_access_token = another_user_signs_up(&app).await;
+
+    let device_id = user_gets_list_of_devices(&app, &access_token).await[0].token_id;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/devices/{}", device_id))
+        .insert_header((
+            header::AUTHORIZATION,
+            format!("Bearer {}", other_access_token),
+        ))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(404, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "DEVICE_NOT_FOUND");
+
+    user_has_access_to_protected_route(&app, &access_token).await;
+    assert_eq!(
+        user_gets_list_of_devices(&app, &access_token).await.len(),
+        1
+    );
+}
+
+#[sqlx::test]
+async fn user_can_sign_out_of_every_other_device(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (first_access_token, _, _) = user_signs_up(&app).await;
+    let (second_access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let (current_access_token, _) = user_logs_in(&app, "testusername", "password1_").await;
+    let other_access_token = another_user_signs_up(&app).await;
+
+    user_has_access_to_protected_route(&app, &first_access_token).await;
+    user_has_access_to_protected_route(&app, &second_access_token).await;
+
+    user_removes_other_devices(&app, &current_access_token).await;
+
+    assert_access_token_is_revoked(&app, &first_access_token).await;
+    assert_access_token_is_revoked(&app, &second_access_token).await;
+    user_has_access_to_protected_route(&app, &current_access_token).await;
+    assert_eq!(
+        user_gets_list_of_devices(&app, &current_access_token)
+            .await
+            .len(),
+        1
+    );
+
+    // Sessions of other users are kept
+    assert_eq!(
+        user_gets_list_of_devices(&app, &other_access_token)
+            .await
+            .len(),
+        1
+    );
+}
+
+#[sqlx::test]
+async fn devices_show_when_and_from_where_sessions_were_last_used(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let signed_up_at = Utc::now();
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    user_sends_a_request_from(&app, &access_token, "10.0.0.1").await;
+
+    let devices = user_gets_list_of_devices(&app, &access_token).await;
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].last_ip, Some("10.0.0.1".to_string()));
+    assert!(devices[0].last_activity_date.unwrap() >= signed_up_at);
+    assert!(devices[0].created_at >= signed_up_at - Duration::seconds(1));
+}
+
+#[sqlx::test]
+async fn session_activity_is_kept_after_a_restart(pool: PgPool) {
+    let configuration = get_test_configuration();
+    let session_activity_writer =
+        SessionActivityWriter::new(pool.clone(), configuration.session_activity.clone());
+    let app = spawn_app_with_session_activity_writer(
+        pool.clone(),
+        configuration,
+        session_activity_writer.clone(),
+    )
+    .await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    user_sends_a_request_from(&app, &access_token, "10.0.0.1").await;
+    let last_activity_date = user_gets_list_of_devices(&app, &access_token).await[0]
+        .last_activity_date
+        .unwrap();
+
+    assert_eq!(session_activity_writer.flush().await.unwrap(), 1);
+
+    // Nothing is cached by the new instance
+    let app = spawn_app(pool).await;
+
+    let devices = user_gets_list_of_devices(&app, &access_token).await;
+    assert_eq!(devices[0].last_ip, Some("10.0.0.1".to_string()));
+    assert!(devices[0].last_activity_date.unwrap() >= last_activity_date);
+}
+
+#[sqlx::test]
+async fn session_activity_is_recorded_once_per_debounce_period(pool: PgPool) {
+    let configuration = get_test_configuration();
+    let session_activity_writer =
+        SessionActivityWriter::new(pool.clone(), configuration.session_activity.clone());
+    let app = spawn_app_with_session_activity_writer(
+        pool.clone(),
+        configuration,
+        session_activity_writer.clone(),
+    )
+    .await;
+    // Stored with a microsecond precision
+    let start = Utc::now().trunc_subsecs(0);
+    override_now(Some(start.fixed_offset()));
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    user_sends_a_request_from(&app, &access_token, "10.0.0.1").await;
+    assert_eq!(session_activity_writer.flush().await.unwrap(), 1);
+
+    override_now(Some((start + Duration::minutes(1)).fixed_offset()));
+    user_sends_a_request_from(&app, &access_token, "10.0.0.2").await;
+    assert_eq!(session_activity_writer.flush().await.unwrap(), 0);
+
+    let devices = user_gets_list_of_devices(&app, &access_token).await;
+    assert_eq!(devices[0].last_ip, Some("10.0.0.1".to_string()));
+    assert_eq!(devices[0].last_activity_date, Some(start));
+
+    override_now(Some((start + Duration::minutes(6)).fixed_offset()));
+    user_sends_a_request_from(&app, &access_token, "10.0.0.2").await;
+    assert_eq!(session_activity_writer.flush().await.unwrap(), 1);
+
+    let devices = user_gets_list_of_devices(&app, &access_token).await;
+    assert_eq!(devices[0].last_ip, Some("10.0.0.2".to_string()));
+    assert_eq!(
+        devices[0].last_activity_date,
+        Some(start + Duration::minutes(6))
+    );
+}
@@ -5,7 +5,9 @@ use actix_web::{
     http::header::ContentType,
     test, Error,
 };
-use flutteractixapp::features::profile::application::dto::{IsOtpEnabledRequest, IsOtpEnabledResponse, ProfileResponse, UpdateProfileRequest};
+use flutteractixapp::features::profile::application::dto::{
+    IsOtpEnabledRequest, IsOtpEnabledResponse, ProfileResponse, UpdateProfileRequest,
+};
 use sqlx::PgPool;
 
 use crate::{
@@ -87,7 +89,7 @@ pub async fn is_otp_enabled_for_user_that_activated_it(pool: PgPool) {
     let response: IsOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(response.code, "OTP_STATUS");
-    assert_eq!(response.otp_enabled, false);
+    assert!(!response.otp_enabled);
 
     // User only generates OTP
     user_generates_otp(&app, &access_token).await;
@@ -107,7 +109,7 @@ pub async fn is_otp_enabled_for_user_that_activated_it(pool: PgPool) {
     let response: IsOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(response.code, "OTP_STATUS");
-    assert_eq!(response.otp_enabled, false);
+    assert!(!response.otp_enabled);
 
     // User generates and validates OTP
     let otp_base32 = user_generates_otp(&app, &access_token).await;
@@ -128,5 +130,5 @@ pub async fn is_otp_enabled_for_user_that_activated_it(pool: PgPool) {
     let response: IsOtpEnabledResponse = serde_json::from_slice(&body).unwrap();
 
     assert_eq!(response.code, "OTP_STATUS");
-    assert_eq!(response.otp_enabled, true);
+    assert!(response.otp_enabled);
 }
@@ -0,0 +1,188 @@
+use actix_http::{header, Request};
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::http::header::ContentType;
+use actix_web::{test, Error};
+use flutteractixapp::core::structs::responses::GenericResponse;
+use flutteractixapp::features::auth::application::dto::{
+    RecoverAccountWithout2FAEnabledRequest, RegenerateRecoveryCodesRequest,
+    RegenerateRecoveryCodesResponse, RemainingRecoveryCodesResponse,
+};
+use sqlx::PgPool;
+
+use crate::auth::recovery::recover_account_without_2fa_enabled::user_recovers_account_without_2fa_enabled;
+use crate::auth::signup::user_signs_up;
+use crate::helpers::spawn_app;
+
+async fn user_regenerates_recovery_codes(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+    password: &str,
+) -> ServiceResponse<impl MessageBody> {
+    let req = test::TestRequest::post()
+        .uri("/api/users/me/recovery-codes")
+        .insert_header(ContentType::json())
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .set_json(&RegenerateRecoveryCodesRequest {
+            password: password.to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await
+}
+
+async fn user_gets_remaining_recovery_codes(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    access_token: &str,
+) -> i64 {
+    let req = test::TestRequest::get()
+        .uri("/api/users/me/recovery-codes")
+        .insert_header((header::AUTHORIZATION, format!("Bearer {}", access_token)))
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: RemainingRecoveryCodesResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "REMAINING_RECOVERY_CODES");
+
+    response.remaining_recovery_codes
+}
+
+async fn user_attempts_to_recover_account(
+    app: impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error>,
+    recovery_code: &str,
+) -> u16 {
+    let req = test::TestRequest::post()
+        .uri("/api/auth/recover")
+        .insert_header(ContentType::json())
+        .set_json(&RecoverAccountWithout2FAEnabledRequest {
+            username: "testusername".to_string(),
+            recovery_code: recovery_code.to_string(),
+        })
+        .to_request();
+
+    test::call_service(&app, req).await.status().as_u16()
+}
+
+#[sqlx::test]
+async fn used_recovery_codes_are_not_counted_as_remaining(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+
+    assert_eq!(
+        5,
+        user_gets_remaining_recovery_codes(&app, &access_token).await
+    );
+
+    let (access_token, _) =
+        user_recovers_account_without_2fa_enabled(&app, &recovery_codes[0]).await;
+
+    assert_eq!(
+        4,
+        user_gets_remaining_recovery_codes(&app, &access_token).await
+    );
+}
+
+#[sqlx::test]
+async fn user_can_regenerate_recovery_codes_after_using_them_all(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (_, _, recovery_codes) = user_signs_up(&app).await;
+
+    let mut access_token = String::new();
+    for recovery_code in &recovery_codes {
+        (access_token, _) = user_recovers_account_without_2fa_enabled(&app, recovery_code).await;
+    }
+    assert_eq!(
+        0,
+        user_gets_remaining_recovery_codes(&app, &access_token).await
+    );
+
+    let response = user_regenerates_recovery_codes(&app, &access_token, "password1_").await;
+    assert_eq!(200, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: RegenerateRecoveryCodesResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "RECOVERY_CODES_REGENERATED");
+    assert_eq!(5, response.recovery_codes.len());
+    assert_eq!(
+        5,
+        user_gets_remaining_recovery_codes(&app, &access_token).await
+    );
+
+    let (access_token, _) =
+        user_recovers_account_without_2fa_enabled(&app, &response.recovery_codes[0]).await;
+    assert_eq!(
+        4,
+        user_gets_remaining_recovery_codes(&app, &access_token).await
+    );
+}
+
+#[sqlx::test]
+async fn previous_recovery_codes_cannot_be_used_after_regeneration(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+
+    let response = user_regenerates_recovery_codes(&app, &access_token, "password1_").await;
+    assert_eq!(200, response.status().as_u16());
+
+    assert_eq!(
+        401,
+        user_attempts_to_recover_account(&app, &recovery_codes[0]).await
+    );
+}
+
+#[sqlx::test]
+async fn user_cannot_regenerate_recovery_codes_with_a_wrong_password(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, recovery_codes) = user_signs_up(&app).await;
+
+    let response = user_regenerates_recovery_codes(&app, &access_token, "wrong_password1_").await;
+    assert_eq!(401, response.status().as_u16());
+
+    let body = test::read_body(response).await;
+    let response: GenericResponse = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(response.code, "INVALID_PASSWORD");
+
+    // The current codes are kept
+    assert_eq!(
+        200,
+        user_attempts_to_recover_account(&app, &recovery_codes[0]).await
+    );
+}
+
+#[sqlx::test]
+async fn password_cannot_be_brute_forced_to_regenerate_recovery_codes(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    let (access_token, _, _) = user_signs_up(&app).await;
+
+    for _ in 0..5 {
+        let response =
+            user_regenerates_recovery_codes(&app, &access_token, "wrong_password1_").await;
+        assert_eq!(401, response.status().as_u16());
+    }
+
+    let response = user_regenerates_recovery_codes(&app, &access_token, "password1_").await;
+    assert_eq!(429, response.status().as_u16());
+}
+
+#[sqlx::test]
+async fn user_cannot_regenerate_recovery_codes_without_access_token(pool: PgPool) {
+    let app = spawn_app(pool).await;
+    user_signs_up(&app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/users/me/recovery-codes")
+        .insert_header(ContentType::json())
+        .set_json(&RegenerateRecoveryCodesRequest {
+            password: "password1_".to_string(),
+        })
+        .to_request();
+    let response = test::call_service(&app, req).await;
+
+    assert_eq!(401, response.status().as_u16());
+}
@@ -0,0 +1,168 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::features::admin::application::dto::{
+    AdminActionResponse, SetUserStatusRequest,
+};
+use flutteractixapp::features::admin::application::usecases::SetUserStatusUseCase;
+use flutteractixapp::features::admin::domain::errors::AdminDomainError;
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::domain::entities::AccountStatus;
+use uuid::Uuid;
+
+use crate::admin::users::{admin_session_repository, admin_user_repository};
+use crate::helpers::{request_context, user_signs_up, user_signs_up_as, InMemoryApp};
+
+async fn admin_sets_user_status(
+    app: &InMemoryApp,
+    admin_id: Uuid,
+    user_id: Uuid,
+    request: SetUserStatusRequest,
+) -> Result<AdminActionResponse, AdminDomainError> {
+    SetUserStatusUseCase::new(
+        admin_user_repository(app),
+        admin_session_repository(app),
+        app.token_cache.clone(),
+        app.unit_of_work(),
+        app.audit_log(),
+        app.outbox(),
+    )
+    .execute(admin_id, user_id, request, request_context())
+    .await
+}
+
+fn suspension() -> SetUserStatusRequest {
+    SetUserStatusRequest {
+        status: AccountStatus::Suspended,
+        reason: Some("Spam".to_string()),
+        until: Some(now() + Duration::days(7)),
+    }
+}
+
+#[tokio::test]
+async fn suspended_user_is_signed_out() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = admin_sets_user_status(&app, admin_id, user_id, suspension())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "ACCOUNT_STATUS_UPDATED");
+    let user = app.database.tables().users[1].clone();
+    assert_eq!(user.status, AccountStatus::Suspended);
+    assert_eq!(user.status_reason, Some("Spam".to_string()));
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP", "SESSION_REVOKED"]
+    );
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::AccountStatusChanged,
+            SecurityEventOutcome::Success
+        )]
+    );
+    assert_eq!(
+        app.database.tables().security_events[0].actor_id,
+        Some(admin_id)
+    );
+}
+
+#[tokio::test]
+async fn reactivated_user_has_their_status_cleared() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    admin_sets_user_status(&app, admin_id, user_id, suspension())
+        .await
+        .unwrap();
+
+    admin_sets_user_status(
+        &app,
+        admin_id,
+        user_id,
+        SetUserStatusRequest {
+            status: AccountStatus::Active,
+            reason: None,
+            until: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    let user = app.database.tables().users[1].clone();
+    assert_eq!(user.status, AccountStatus::Active);
+    assert!(user.status_reason.is_none());
+    assert!(user.status_until.is_none());
+}
+
+#[tokio::test]
+async fn status_already_over_is_rejected() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = admin_sets_user_status(
+        &app,
+        admin_id,
+        user_id,
+        SetUserStatusRequest {
+            until: Some(now() - Duration::days(1)),
+            ..suspension()
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        result,
+        Err(AdminDomainError::InvalidAccountStatus)
+    ));
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn admin_cannot_change_their_own_status() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+
+    let result = admin_sets_user_status(&app, admin_id, admin_id, suspension()).await;
+
+    assert!(matches!(
+        result,
+        Err(AdminDomainError::CannotChangeOwnStatus)
+    ));
+}
+
+#[tokio::test]
+async fn status_is_kept_when_the_sessions_cannot_be_revoked() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("user_tokens");
+
+    let result = admin_sets_user_status(&app, admin_id, user_id, suspension()).await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    assert_eq!(app.database.tables().users[1].status, AccountStatus::Active);
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn status_is_kept_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = admin_sets_user_status(&app, admin_id, user_id, suspension()).await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    assert_eq!(app.database.tables().users[1].status, AccountStatus::Active);
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP"]
+    );
+}
@@ -0,0 +1,387 @@
+use flutteractixapp::features::admin::application::dto::ListUsersQuery;
+use flutteractixapp::features::admin::application::usecases::{
+    ExpireUserPasswordUseCase, GetUserSessionsUseCase, ListUsersUseCase, ResetUserOtpUseCase,
+    RevokeUserSessionsUseCase, SetUserRoleUseCase,
+};
+use flutteractixapp::features::admin::domain::errors::AdminDomainError;
+use flutteractixapp::features::admin::infrastructure::repositories::in_memory::{
+    InMemorySessionRepository, InMemoryUserRepository,
+};
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use uuid::Uuid;
+
+use crate::auth::otp::user_enables_otp;
+use crate::helpers::{request_context, user_signs_up, user_signs_up_as, InMemoryApp};
+
+pub fn admin_user_repository(app: &InMemoryApp) -> Box<InMemoryUserRepository> {
+    Box::new(InMemoryUserRepository::new(app.database.clone()))
+}
+
+pub fn admin_session_repository(app: &InMemoryApp) -> Box<InMemorySessionRepository> {
+    Box::new(InMemorySessionRepository::new(app.database.clone()))
+}
+
+fn revoke_user_sessions_use_case(app: &InMemoryApp) -> RevokeUserSessionsUseCase {
+    RevokeUserSessionsUseCase::new(
+        admin_user_repository(app),
+        admin_session_repository(app),
+        app.token_cache.clone(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn reset_user_otp_use_case(app: &InMemoryApp) -> ResetUserOtpUseCase {
+    ResetUserOtpUseCase::new(
+        admin_user_repository(app),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn set_user_role_use_case(app: &InMemoryApp) -> SetUserRoleUseCase {
+    SetUserRoleUseCase::new(
+        admin_user_repository(app),
+        admin_session_repository(app),
+        app.token_cache.clone(),
+        app.unit_of_work(),
+        app.audit_log(),
+        app.outbox(),
+    )
+}
+
+async fn admin_lists_users(app: &InMemoryApp, search: Option<&str>, per_page: i64) -> Vec<String> {
+    ListUsersUseCase::new(admin_user_repository(app))
+        .execute(ListUsersQuery {
+            search: search.map(str::to_string),
+            page: Some(1),
+            per_page: Some(per_page),
+        })
+        .await
+        .unwrap()
+        .users
+        .into_iter()
+        .map(|user| user.username)
+        .collect()
+}
+
+#[tokio::test]
+async fn admin_lists_users_page_by_page() {
+    let app = InMemoryApp::new();
+    for username in ["alice", "bob", "carol"] {
+        user_signs_up_as(&app, username).await;
+    }
+
+    let response = ListUsersUseCase::new(admin_user_repository(&app))
+        .execute(ListUsersQuery {
+            search: None,
+            page: Some(2),
+            per_page: Some(2),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "USERS_FETCHED");
+    assert_eq!(response.total, 3);
+    assert_eq!(response.users.len(), 1);
+    assert_eq!(response.users[0].username, "carol");
+}
+
+#[tokio::test]
+async fn admin_searches_users_by_username() {
+    let app = InMemoryApp::new();
+    for username in ["alice", "bob", "malicia"] {
+        user_signs_up_as(&app, username).await;
+    }
+
+    assert_eq!(
+        admin_lists_users(&app, Some(" ALI "), 20).await,
+        vec!["alice", "malicia"]
+    );
+    assert_eq!(admin_lists_users(&app, Some(""), 20).await.len(), 3);
+}
+
+#[tokio::test]
+async fn admin_gets_the_sessions_of_a_user() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = GetUserSessionsUseCase::new(
+        admin_user_repository(&app),
+        admin_session_repository(&app),
+        app.session_activity_writer.clone(),
+    )
+    .execute(user_id)
+    .await
+    .unwrap();
+
+    assert_eq!(response.code, "SESSIONS_FETCHED");
+    assert_eq!(response.sessions.len(), 1);
+    assert_eq!(
+        response.sessions[0].parsed_device_info.os,
+        Some("iOS".to_string())
+    );
+}
+
+#[tokio::test]
+async fn admin_revokes_the_sessions_of_a_user() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = revoke_user_sessions_use_case(&app)
+        .execute(admin_id, user_id, request_context())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "SESSIONS_REVOKED");
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(app.session_count(admin_id), 1);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP", "SESSION_REVOKED"]
+    );
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::SessionsRevoked,
+            SecurityEventOutcome::Success
+        )]
+    );
+    assert_eq!(
+        app.database.tables().security_events[0].actor_id,
+        Some(admin_id)
+    );
+}
+
+#[tokio::test]
+async fn sessions_of_an_unknown_user_are_not_found() {
+    let app = InMemoryApp::new();
+
+    let result = GetUserSessionsUseCase::new(
+        admin_user_repository(&app),
+        admin_session_repository(&app),
+        app.session_activity_writer.clone(),
+    )
+    .execute(Uuid::new_v4())
+    .await;
+
+    assert!(matches!(result, Err(AdminDomainError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn admin_expires_the_password_of_a_user() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = ExpireUserPasswordUseCase::new(admin_user_repository(&app), app.audit_log())
+        .execute(admin_id, user_id, request_context())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "PASSWORD_EXPIRED");
+    assert!(app.database.tables().users[1].password_is_expired);
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::PasswordExpired,
+            SecurityEventOutcome::Success
+        )]
+    );
+    assert_eq!(
+        app.database.tables().security_events[0].actor_id,
+        Some(admin_id)
+    );
+}
+
+#[tokio::test]
+async fn admin_resets_the_otp_of_a_user() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let response = reset_user_otp_use_case(&app)
+        .execute(admin_id, user_id, request_context())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "OTP_RESET");
+    let user = app.database.tables().users[1].clone();
+    assert!(!user.otp_verified);
+    assert!(user.otp_base32.is_none());
+    assert_eq!(app.published_events().last(), Some(&"OTP_DISABLED"));
+    let reset = app
+        .database
+        .tables()
+        .security_events
+        .iter()
+        .find(|event| event.event_type == SecurityEventType::OtpDisabled)
+        .cloned()
+        .unwrap();
+    assert_eq!(reset.user_id, user_id);
+    assert_eq!(reset.actor_id, Some(admin_id));
+}
+
+#[tokio::test]
+async fn admin_grants_and_revokes_a_role() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = set_user_role_use_case(&app)
+        .execute(admin_id, user_id, "support", true, request_context())
+        .await
+        .unwrap();
+    assert_eq!(response.code, "ROLE_GRANTED");
+    assert_eq!(
+        app.database.tables().user_roles,
+        vec![(user_id, "support".to_string())]
+    );
+    assert_eq!(app.session_count(user_id), 1);
+
+    let response = set_user_role_use_case(&app)
+        .execute(admin_id, user_id, "support", false, request_context())
+        .await
+        .unwrap();
+    assert_eq!(response.code, "ROLE_REVOKED");
+    assert!(app.database.tables().user_roles.is_empty());
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(app.published_events().last(), Some(&"SESSION_REVOKED"));
+
+    assert_eq!(
+        app.security_events(user_id),
+        vec![
+            (
+                SecurityEventType::RoleGranted,
+                SecurityEventOutcome::Success
+            ),
+            (
+                SecurityEventType::RoleRevoked,
+                SecurityEventOutcome::Success
+            ),
+        ]
+    );
+    assert!(app
+        .database
+        .tables()
+        .security_events
+        .iter()
+        .all(|event| event.actor_id == Some(admin_id)));
+}
+
+#[tokio::test]
+async fn unknown_role_is_not_granted() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = set_user_role_use_case(&app)
+        .execute(admin_id, user_id, "superuser", true, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::RoleNotFound)));
+}
+
+#[tokio::test]
+async fn admin_cannot_revoke_their_own_role() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    app.database
+        .tables()
+        .user_roles
+        .push((admin_id, "admin".to_string()));
+
+    let result = set_user_role_use_case(&app)
+        .execute(admin_id, admin_id, "admin", false, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::CannotRevokeOwnRole)));
+    assert_eq!(app.database.tables().user_roles.len(), 1);
+}
+
+#[tokio::test]
+async fn role_is_kept_when_the_sessions_cannot_be_revoked() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database
+        .tables()
+        .user_roles
+        .push((user_id, "support".to_string()));
+    app.database.reject_writes_to("user_tokens");
+
+    let result = set_user_role_use_case(&app)
+        .execute(admin_id, user_id, "support", false, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    assert_eq!(app.database.tables().user_roles.len(), 1);
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn sessions_are_kept_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = revoke_user_sessions_use_case(&app)
+        .execute(admin_id, user_id, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP"]
+    );
+}
+
+#[tokio::test]
+async fn otp_is_kept_when_the_reset_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+    let published_events = app.published_events();
+    app.database.reject_writes_to("outbox_events");
+
+    let result = reset_user_otp_use_case(&app)
+        .execute(admin_id, user_id, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    let user = app.database.tables().users[1].clone();
+    assert!(user.otp_verified);
+    assert!(user.otp_base32.is_some());
+    assert_eq!(app.published_events(), published_events);
+}
+
+#[tokio::test]
+async fn role_is_kept_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (admin_id, _) = user_signs_up_as(&app, "adminusername").await;
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database
+        .tables()
+        .user_roles
+        .push((user_id, "support".to_string()));
+    app.database.reject_writes_to("outbox_events");
+
+    let result = set_user_role_use_case(&app)
+        .execute(admin_id, user_id, "support", false, request_context())
+        .await;
+
+    assert!(matches!(result, Err(AdminDomainError::DatabaseError)));
+    assert_eq!(app.database.tables().user_roles.len(), 1);
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "USER_SIGNED_UP"]
+    );
+}
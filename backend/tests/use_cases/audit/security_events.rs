@@ -0,0 +1,163 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::features::audit::application::dto::SecurityEventsResponse;
+use flutteractixapp::features::audit::application::usecases::ListSecurityEventsUseCase;
+use flutteractixapp::features::audit::domain::entities::{
+    SecurityEvent, SecurityEventFilter, SecurityEventOutcome, SecurityEventType,
+};
+use flutteractixapp::features::audit::domain::errors::AuditDomainError;
+use flutteractixapp::features::audit::infrastructure::repositories::in_memory::InMemorySecurityEventRepository;
+use uuid::Uuid;
+
+use crate::helpers::{request_context, InMemoryApp};
+
+async fn security_events_are_recorded(
+    app: &InMemoryApp,
+    user_id: Uuid,
+    events: &[(SecurityEventType, SecurityEventOutcome)],
+) {
+    let audit_log = app.audit_log();
+
+    // A minute apart, oldest first, so the order doesn't depend on the ids
+    for (index, (event_type, outcome)) in events.iter().enumerate() {
+        let mut event = SecurityEvent::new(user_id, *event_type, *outcome, &request_context());
+        event.created_at = now() - Duration::minutes((events.len() - index) as i64);
+
+        audit_log.record(event).await;
+    }
+}
+
+async fn list_security_events(
+    app: &InMemoryApp,
+    filter: SecurityEventFilter,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> Result<SecurityEventsResponse, AuditDomainError> {
+    ListSecurityEventsUseCase::new(Box::new(InMemorySecurityEventRepository::new(
+        app.database.clone(),
+    )))
+    .execute(filter, cursor, limit)
+    .await
+}
+
+fn user_filter(user_id: Uuid) -> SecurityEventFilter {
+    SecurityEventFilter {
+        user_id: Some(user_id),
+        event_type: None,
+        outcome: None,
+    }
+}
+
+#[tokio::test]
+async fn events_are_listed_from_the_most_recent_page_by_page() {
+    let app = InMemoryApp::new();
+    let user_id = Uuid::new_v4();
+    security_events_are_recorded(
+        &app,
+        user_id,
+        &[
+            (
+                SecurityEventType::LoginSucceeded,
+                SecurityEventOutcome::Success,
+            ),
+            (
+                SecurityEventType::PasswordChanged,
+                SecurityEventOutcome::Success,
+            ),
+            (
+                SecurityEventType::LoginFailed,
+                SecurityEventOutcome::Failure,
+            ),
+        ],
+    )
+    .await;
+
+    let first_page = list_security_events(&app, user_filter(user_id), None, Some(2))
+        .await
+        .unwrap();
+    assert_eq!(first_page.code, "SECURITY_EVENTS_FETCHED");
+    assert_eq!(first_page.events.len(), 2);
+    assert_eq!(
+        first_page.events[0].event_type,
+        SecurityEventType::LoginFailed
+    );
+
+    let second_page = list_security_events(
+        &app,
+        user_filter(user_id),
+        first_page.next_cursor.as_deref(),
+        Some(2),
+    )
+    .await
+    .unwrap();
+    assert_eq!(second_page.events.len(), 1);
+    assert_eq!(
+        second_page.events[0].event_type,
+        SecurityEventType::LoginSucceeded
+    );
+    assert!(second_page.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn events_are_filtered() {
+    let app = InMemoryApp::new();
+    let user_id = Uuid::new_v4();
+    security_events_are_recorded(
+        &app,
+        user_id,
+        &[
+            (
+                SecurityEventType::LoginSucceeded,
+                SecurityEventOutcome::Success,
+            ),
+            (
+                SecurityEventType::LoginFailed,
+                SecurityEventOutcome::Failure,
+            ),
+        ],
+    )
+    .await;
+    security_events_are_recorded(
+        &app,
+        Uuid::new_v4(),
+        &[(
+            SecurityEventType::LoginFailed,
+            SecurityEventOutcome::Failure,
+        )],
+    )
+    .await;
+
+    let response = list_security_events(
+        &app,
+        SecurityEventFilter {
+            user_id: None,
+            event_type: None,
+            outcome: Some(SecurityEventOutcome::Failure),
+        },
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.events.len(), 2);
+
+    let response = list_security_events(&app, user_filter(user_id), None, None)
+        .await
+        .unwrap();
+    assert_eq!(response.events.len(), 2);
+}
+
+#[tokio::test]
+async fn invalid_cursor_is_rejected() {
+    let app = InMemoryApp::new();
+
+    let result = list_security_events(
+        &app,
+        user_filter(Uuid::new_v4()),
+        Some("not_a_cursor"),
+        None,
+    )
+    .await;
+
+    assert!(matches!(result, Err(AuditDomainError::InvalidCursor)));
+}
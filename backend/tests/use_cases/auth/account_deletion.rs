@@ -0,0 +1,125 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::features::auth::application::dto::{
+    AccountDeletionResponse, DeleteAccountRequest,
+};
+use flutteractixapp::features::auth::application::usecases::DeleteAccountUseCase;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+use uuid::Uuid;
+
+use crate::auth::otp::{otp_code, user_enables_otp, wait_for_next_otp_step};
+use crate::helpers::{request_context, user_signs_up, InMemoryApp};
+
+fn delete_account_use_case(app: &InMemoryApp) -> DeleteAccountUseCase {
+    DeleteAccountUseCase::new(
+        app.user_repository(),
+        app.token_repository(),
+        app.login_throttle_service(),
+        app.otp_service(),
+        app.token_cache.clone(),
+        Duration::days(app.configuration.account_deletion.grace_period_days),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+async fn user_deletes_account(
+    app: &InMemoryApp,
+    user_id: Uuid,
+    password: &str,
+    code: Option<String>,
+) -> Result<AccountDeletionResponse, AuthDomainError> {
+    delete_account_use_case(app)
+        .execute(
+            user_id,
+            DeleteAccountRequest {
+                password: password.to_string(),
+                code,
+            },
+            request_context(),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn deletion_is_scheduled_after_the_grace_period() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = user_deletes_account(&app, user_id, "password1_", None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "ACCOUNT_DELETION_SCHEDULED");
+    let grace_period = Duration::days(app.configuration.account_deletion.grace_period_days);
+    assert!(response.deletion_scheduled_at > now() + grace_period - Duration::minutes(1));
+    assert_eq!(
+        app.database.tables().users[0].deletion_scheduled_at,
+        Some(response.deletion_scheduled_at)
+    );
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "SESSION_REVOKED"]
+    );
+}
+
+#[tokio::test]
+async fn deletion_requires_the_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_deletes_account(&app, user_id, "wrong_password1", None).await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidCredentials)));
+    assert!(app.database.tables().users[0]
+        .deletion_scheduled_at
+        .is_none());
+}
+
+#[tokio::test]
+async fn deletion_requires_a_code_when_otp_is_enabled() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let otp_base32 = user_enables_otp(&app, user_id).await;
+    wait_for_next_otp_step();
+
+    let result = user_deletes_account(&app, user_id, "password1_", None).await;
+    assert!(matches!(result, Err(AuthDomainError::InvalidOtp)));
+
+    user_deletes_account(&app, user_id, "password1_", Some(otp_code(&otp_base32)))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn deletion_is_not_scheduled_when_the_sessions_cannot_be_revoked() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("user_tokens");
+
+    let result = user_deletes_account(&app, user_id, "password1_", None).await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    assert!(app.database.tables().users[0]
+        .deletion_scheduled_at
+        .is_none());
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn deletion_is_not_scheduled_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = user_deletes_account(&app, user_id, "password1_", None).await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    assert!(app.database.tables().users[0]
+        .deletion_scheduled_at
+        .is_none());
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(app.published_events(), vec!["USER_SIGNED_UP"]);
+}
@@ -0,0 +1,17 @@
+use flutteractixapp::features::auth::application::usecases::GetJwksUseCase;
+
+use crate::helpers::{user_signs_up, InMemoryApp};
+
+#[tokio::test]
+async fn jwks_lists_the_key_the_tokens_are_signed_with() {
+    let app = InMemoryApp::new();
+    let (_, signup) = user_signs_up(&app).await;
+
+    let response = GetJwksUseCase::new(Box::new(app.token_service.clone())).execute();
+
+    let kid = jsonwebtoken::decode_header(&signup.access_token)
+        .unwrap()
+        .kid
+        .unwrap();
+    assert!(response.keys.iter().any(|key| key.kid == kid));
+}
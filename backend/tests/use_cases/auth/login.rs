@@ -0,0 +1,146 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::{
+    LoginRequest, LoginResponse, LoginWhenOtpEnabledResponse,
+};
+use flutteractixapp::features::auth::application::usecases::LoginUseCase;
+use flutteractixapp::features::auth::domain::entities::DeviceInfo;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::auth::otp::user_enables_otp;
+use crate::helpers::{device_info, other_device_info, request_context, user_signs_up, InMemoryApp};
+
+pub fn login_use_case(app: &InMemoryApp) -> LoginUseCase {
+    LoginUseCase::new(
+        app.user_repository(),
+        app.session_service(),
+        app.login_throttle_service(),
+        app.mfa_challenge_service(),
+        app.audit_log(),
+        app.new_device_service(),
+        app.unit_of_work(),
+    )
+}
+
+pub async fn user_logs_in(
+    app: &InMemoryApp,
+    password: &str,
+    device_info: DeviceInfo,
+) -> Result<Result<LoginResponse, LoginWhenOtpEnabledResponse>, AuthDomainError> {
+    login_use_case(app)
+        .execute(
+            LoginRequest {
+                username: "testusername".to_string(),
+                password: password.to_string(),
+            },
+            device_info,
+            request_context(),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn user_logs_in_with_valid_credentials() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = user_logs_in(&app, "password1_", device_info())
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(response.code, "USER_LOGGED_IN_WITHOUT_OTP");
+    assert_eq!(app.session_count(user_id), 2);
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::LoginSucceeded,
+            SecurityEventOutcome::Success
+        )]
+    );
+    // The user signed up from the same device
+    assert!(app.notifier.sent().is_empty());
+}
+
+#[tokio::test]
+async fn login_from_a_new_device_is_notified() {
+    let app = InMemoryApp::new();
+    user_signs_up(&app).await;
+
+    user_logs_in(&app, "password1_", other_device_info())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let sent = app.notifier.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].device_info.os.as_deref(), Some("Windows"));
+}
+
+#[tokio::test]
+async fn login_fails_with_a_wrong_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_logs_in(&app, "wrong_password1", device_info()).await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidCredentials)));
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::LoginFailed,
+            SecurityEventOutcome::Failure
+        )]
+    );
+}
+
+#[tokio::test]
+async fn login_is_locked_after_too_many_failures() {
+    let app = InMemoryApp::new();
+    user_signs_up(&app).await;
+
+    for _ in 0..app.configuration.login_throttling.account_max_attempts {
+        let result = user_logs_in(&app, "wrong_password1", device_info()).await;
+        assert!(matches!(result, Err(AuthDomainError::InvalidCredentials)));
+    }
+    let result = user_logs_in(&app, "password1_", device_info()).await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::TooManyAttempts { .. })
+    ));
+}
+
+#[tokio::test]
+async fn login_with_otp_enabled_asks_for_a_code() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let response = user_logs_in(&app, "password1_", device_info())
+        .await
+        .unwrap()
+        .unwrap_err();
+
+    assert_eq!(response.code, "USER_LOGS_IN_WITH_OTP_ENABLED");
+    assert_eq!(app.database.tables().mfa_challenges.len(), 1);
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn login_cancels_the_scheduled_deletion() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.tables().users[0].deletion_scheduled_at = Some(now() + Duration::days(30));
+
+    user_logs_in(&app, "password1_", device_info())
+        .await
+        .unwrap()
+        .unwrap();
+
+    let tables = app.database.tables();
+    let user = tables.users.iter().find(|user| user.id == user_id).unwrap();
+    assert!(user.deletion_scheduled_at.is_none());
+}
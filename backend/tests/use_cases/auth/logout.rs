@@ -0,0 +1,43 @@
+use flutteractixapp::features::auth::application::usecases::LogoutUseCase;
+
+use crate::helpers::{token_id_and_user_id, user_signs_up, InMemoryApp};
+
+fn logout_use_case(app: &InMemoryApp) -> LogoutUseCase {
+    LogoutUseCase::new(
+        app.token_repository(),
+        app.token_cache.clone(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+#[tokio::test]
+async fn logout_revokes_the_session() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    let (token_id, _) = token_id_and_user_id(&app, &signup.access_token);
+
+    logout_use_case(&app)
+        .execute(user_id, token_id)
+        .await
+        .unwrap();
+
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "SESSION_REVOKED"]
+    );
+}
+
+#[tokio::test]
+async fn session_is_kept_when_the_event_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    let (token_id, _) = token_id_and_user_id(&app, &signup.access_token);
+    app.database.reject_writes_to("outbox_events");
+
+    let result = logout_use_case(&app).execute(user_id, token_id).await;
+
+    assert!(result.is_err());
+    assert_eq!(app.session_count(user_id), 1);
+}
@@ -0,0 +1,307 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::{now, override_now};
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::{ValidateOtpRequest, VerifyOtpRequest};
+use flutteractixapp::features::auth::application::usecases::{
+    DisableOtpUseCase, GenerateOtpUseCase, ValidateOtpUseCase, VerifyOtpUseCase,
+};
+use flutteractixapp::features::auth::domain::entities::User;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+use totp_rs::{Algorithm, Secret, TOTP};
+use uuid::Uuid;
+
+use crate::auth::login::user_logs_in;
+use crate::helpers::{device_info, request_context, user_signs_up, InMemoryApp};
+
+// Codes are generated for the mocked time, so tests can move to another step
+pub fn otp_code(otp_base32: &str) -> String {
+    let totp = TOTP::new(
+        Algorithm::SHA1,
+        6,
+        1,
+        30,
+        Secret::Encoded(otp_base32.to_string()).to_bytes().unwrap(),
+    )
+    .unwrap();
+
+    totp.generate(now().timestamp() as u64)
+}
+
+// A code is only accepted once, the next one is generated in the following step
+pub fn wait_for_next_otp_step() {
+    override_now(Some((now() + Duration::seconds(30)).fixed_offset()));
+}
+
+fn generate_otp_use_case(app: &InMemoryApp) -> GenerateOtpUseCase {
+    GenerateOtpUseCase::new(app.user_repository(), app.otp_service())
+}
+
+fn verify_otp_use_case(app: &InMemoryApp) -> VerifyOtpUseCase {
+    VerifyOtpUseCase::new(
+        app.user_repository(),
+        app.otp_service(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn validate_otp_use_case(app: &InMemoryApp) -> ValidateOtpUseCase {
+    ValidateOtpUseCase::new(
+        app.user_repository(),
+        app.session_service(),
+        app.login_throttle_service(),
+        app.otp_service(),
+        app.mfa_challenge_service(),
+        app.audit_log(),
+        app.new_device_service(),
+        app.unit_of_work(),
+    )
+}
+
+fn find_user(app: &InMemoryApp, user_id: Uuid) -> User {
+    app.database
+        .tables()
+        .users
+        .iter()
+        .find(|user| user.id == user_id)
+        .cloned()
+        .unwrap()
+}
+
+/// Generates a secret without verifying it, as while 2FA is being set up.
+pub async fn user_generates_otp(app: &InMemoryApp, user_id: Uuid) -> String {
+    generate_otp_use_case(app)
+        .execute(user_id)
+        .await
+        .unwrap()
+        .otp_base32
+}
+
+/// Generates and verifies a secret, returns it so the test can compute the codes.
+pub async fn user_enables_otp(app: &InMemoryApp, user_id: Uuid) -> String {
+    let otp_base32 = user_generates_otp(app, user_id).await;
+    verify_otp_use_case(app)
+        .execute(
+            user_id,
+            VerifyOtpRequest {
+                code: otp_code(&otp_base32),
+            },
+            request_context(),
+        )
+        .await
+        .unwrap();
+
+    otp_base32
+}
+
+async fn user_logs_in_with_otp_enabled(app: &InMemoryApp) -> String {
+    user_logs_in(app, "password1_", device_info())
+        .await
+        .unwrap()
+        .unwrap_err()
+        .mfa_token
+}
+
+#[tokio::test]
+async fn generated_secret_is_not_verified_yet() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = generate_otp_use_case(&app).execute(user_id).await.unwrap();
+
+    assert_eq!(response.code, "OTP_GENERATED");
+    let user = find_user(&app, user_id);
+    assert_eq!(user.otp_base32, Some(response.otp_base32));
+    assert!(!user.otp_verified);
+}
+
+#[tokio::test]
+async fn verifying_a_code_enables_otp() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    user_enables_otp(&app, user_id).await;
+
+    assert!(find_user(&app, user_id).otp_verified);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "OTP_ENABLED"]
+    );
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(SecurityEventType::OtpEnabled, SecurityEventOutcome::Success)]
+    );
+}
+
+#[tokio::test]
+async fn verifying_a_wrong_code_fails() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    generate_otp_use_case(&app).execute(user_id).await.unwrap();
+
+    let result = verify_otp_use_case(&app)
+        .execute(
+            user_id,
+            VerifyOtpRequest {
+                code: "000000".to_string(),
+            },
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidOtp)));
+    assert!(!find_user(&app, user_id).otp_verified);
+}
+
+#[tokio::test]
+async fn verifying_without_a_secret_fails() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = verify_otp_use_case(&app)
+        .execute(
+            user_id,
+            VerifyOtpRequest {
+                code: "000000".to_string(),
+            },
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::OtpNotEnabled)));
+}
+
+#[tokio::test]
+async fn otp_stays_disabled_when_the_event_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let otp_base32 = generate_otp_use_case(&app)
+        .execute(user_id)
+        .await
+        .unwrap()
+        .otp_base32;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = verify_otp_use_case(&app)
+        .execute(
+            user_id,
+            VerifyOtpRequest {
+                code: otp_code(&otp_base32),
+            },
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    assert!(!find_user(&app, user_id).otp_verified);
+    assert!(app.security_events(user_id).is_empty());
+}
+
+#[tokio::test]
+async fn validating_a_code_completes_the_login() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let otp_base32 = user_enables_otp(&app, user_id).await;
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    wait_for_next_otp_step();
+
+    let response = validate_otp_use_case(&app)
+        .execute(
+            ValidateOtpRequest {
+                code: otp_code(&otp_base32),
+                mfa_token,
+            },
+            device_info(),
+            request_context(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "USER_LOGGED_IN_AFTER_OTP_VALIDATION");
+    assert_eq!(app.session_count(user_id), 2);
+    assert!(app.database.tables().mfa_challenges[0]
+        .consumed_at
+        .is_some());
+}
+
+#[tokio::test]
+async fn challenge_cannot_be_used_twice() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let otp_base32 = user_enables_otp(&app, user_id).await;
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+    let use_case = validate_otp_use_case(&app);
+    let validate = |code: String| {
+        use_case.execute(
+            ValidateOtpRequest {
+                code,
+                mfa_token: mfa_token.clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+    };
+    wait_for_next_otp_step();
+    validate(otp_code(&otp_base32)).await.unwrap();
+    wait_for_next_otp_step();
+
+    let result = validate(otp_code(&otp_base32)).await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidMfaChallenge)));
+}
+
+#[tokio::test]
+async fn validating_a_wrong_code_fails() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+    let mfa_token = user_logs_in_with_otp_enabled(&app).await;
+
+    let result = validate_otp_use_case(&app)
+        .execute(
+            ValidateOtpRequest {
+                code: "000000".to_string(),
+                mfa_token,
+            },
+            device_info(),
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidOtp)));
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.security_events(user_id).last(),
+        Some(&(
+            SecurityEventType::LoginFailed,
+            SecurityEventOutcome::Failure
+        ))
+    );
+}
+
+#[tokio::test]
+async fn disabling_otp_removes_the_secret() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let response = DisableOtpUseCase::new(
+        app.user_repository(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+    .execute(user_id, request_context())
+    .await
+    .unwrap();
+
+    assert_eq!(response.code, "OTP_DISABLED");
+    let user = find_user(&app, user_id);
+    assert!(!user.otp_verified);
+    assert!(user.otp_base32.is_none());
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "OTP_ENABLED", "OTP_DISABLED"]
+    );
+}
@@ -0,0 +1,283 @@
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::{
+    LoginResponse, RecoverAccountUsing2FARequest, RecoverAccountUsingPasswordRequest,
+    RecoverAccountWithout2FAEnabledRequest,
+};
+use flutteractixapp::features::auth::application::usecases::{
+    RecoverAccountUsing2FAUseCase, RecoverAccountUsingPasswordUseCase,
+    RecoverAccountWithout2FAEnabledUseCase,
+};
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::auth::otp::{otp_code, user_enables_otp, wait_for_next_otp_step};
+use crate::helpers::{device_info, request_context, user_signs_up, InMemoryApp};
+
+fn recover_account_without_2fa_enabled_use_case(
+    app: &InMemoryApp,
+) -> RecoverAccountWithout2FAEnabledUseCase {
+    RecoverAccountWithout2FAEnabledUseCase::new(
+        app.user_repository(),
+        app.token_repository(),
+        app.session_service(),
+        app.login_throttle_service(),
+        app.recovery_code_service(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn recover_account_using_password_use_case(
+    app: &InMemoryApp,
+) -> RecoverAccountUsingPasswordUseCase {
+    RecoverAccountUsingPasswordUseCase::new(
+        app.user_repository(),
+        app.token_repository(),
+        app.session_service(),
+        app.login_throttle_service(),
+        app.recovery_code_service(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn recover_account_using_2fa_use_case(app: &InMemoryApp) -> RecoverAccountUsing2FAUseCase {
+    RecoverAccountUsing2FAUseCase::new(
+        app.user_repository(),
+        app.token_repository(),
+        app.session_service(),
+        app.login_throttle_service(),
+        app.recovery_code_service(),
+        app.otp_service(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+async fn user_recovers_account_without_2fa_enabled(
+    app: &InMemoryApp,
+    recovery_code: &str,
+) -> Result<LoginResponse, AuthDomainError> {
+    recover_account_without_2fa_enabled_use_case(app)
+        .execute(
+            RecoverAccountWithout2FAEnabledRequest {
+                username: "testusername".to_string(),
+                recovery_code: recovery_code.to_string(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await
+}
+
+fn unused_recovery_codes(app: &InMemoryApp) -> usize {
+    app.database
+        .tables()
+        .recovery_codes
+        .iter()
+        .filter(|code| code.used_at.is_none())
+        .count()
+}
+
+#[tokio::test]
+async fn recovery_code_signs_the_user_out_of_the_other_sessions() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+
+    let response = user_recovers_account_without_2fa_enabled(&app, &signup.recovery_codes[0])
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "USER_LOGGED_IN_AFTER_ACCOUNT_RECOVERY");
+    assert_eq!(app.session_count(user_id), 1);
+    assert!(app.database.tables().users[0].password_is_expired);
+    assert_eq!(unused_recovery_codes(&app), 4);
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "ACCOUNT_RECOVERED", "SESSION_REVOKED"]
+    );
+}
+
+#[tokio::test]
+async fn recovery_code_cannot_be_used_twice() {
+    let app = InMemoryApp::new();
+    let (_, signup) = user_signs_up(&app).await;
+    user_recovers_account_without_2fa_enabled(&app, &signup.recovery_codes[0])
+        .await
+        .unwrap();
+
+    let result = user_recovers_account_without_2fa_enabled(&app, &signup.recovery_codes[0]).await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::InvalidUsernameOrRecoveryCode)
+    ));
+}
+
+#[tokio::test]
+async fn failed_recovery_is_recorded() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_recovers_account_without_2fa_enabled(&app, "wrong_code").await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::InvalidUsernameOrRecoveryCode)
+    ));
+    // Recorded in the unit of work that was rolled back, but the audit log doesn't take part
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::RecoveryCodeUsed,
+            SecurityEventOutcome::Failure
+        )]
+    );
+}
+
+#[tokio::test]
+async fn recovery_is_rolled_back_when_the_user_cannot_be_updated() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    app.database.reject_writes_to("users");
+
+    let result = user_recovers_account_without_2fa_enabled(&app, &signup.recovery_codes[0]).await;
+
+    assert!(result.is_err());
+    // The session was deleted before the user update failed, and is back
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(unused_recovery_codes(&app), 5);
+    assert!(!app.database.tables().users[0].password_is_expired);
+    assert_eq!(app.published_events(), vec!["USER_SIGNED_UP"]);
+
+    app.database.accept_writes_to("users");
+    user_recovers_account_without_2fa_enabled(&app, &signup.recovery_codes[0])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn recovery_using_the_password_fails_with_a_wrong_password() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let result = recover_account_using_password_use_case(&app)
+        .execute(
+            RecoverAccountUsingPasswordRequest {
+                username: "testusername".to_string(),
+                password: "wrong_password1".to_string(),
+                recovery_code: signup.recovery_codes[0].clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::InvalidUsernameOrPasswordOrRecoveryCode)
+    ));
+    assert_eq!(unused_recovery_codes(&app), 5);
+}
+
+#[tokio::test]
+async fn recovery_using_the_password_disables_2fa() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let response = recover_account_using_password_use_case(&app)
+        .execute(
+            RecoverAccountUsingPasswordRequest {
+                username: "testusername".to_string(),
+                password: "password1_".to_string(),
+                recovery_code: signup.recovery_codes[0].clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "USER_LOGGED_IN_AFTER_ACCOUNT_RECOVERY");
+    let tables = app.database.tables();
+    assert!(!tables.users[0].otp_verified);
+    assert!(tables.users[0].otp_base32.is_none());
+}
+
+#[tokio::test]
+async fn recovery_using_the_password_requires_2fa() {
+    let app = InMemoryApp::new();
+    let (_, signup) = user_signs_up(&app).await;
+
+    let result = recover_account_using_password_use_case(&app)
+        .execute(
+            RecoverAccountUsingPasswordRequest {
+                username: "testusername".to_string(),
+                password: "password1_".to_string(),
+                recovery_code: signup.recovery_codes[0].clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::TwoFactorAuthenticationNotEnabled)
+    ));
+}
+
+#[tokio::test]
+async fn recovery_using_2fa_keeps_the_password() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    let otp_base32 = user_enables_otp(&app, user_id).await;
+    wait_for_next_otp_step();
+
+    let response = recover_account_using_2fa_use_case(&app)
+        .execute(
+            RecoverAccountUsing2FARequest {
+                username: "testusername".to_string(),
+                code: otp_code(&otp_base32),
+                recovery_code: signup.recovery_codes[0].clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "USER_LOGGED_IN_AFTER_ACCOUNT_RECOVERY");
+    assert!(!app.database.tables().users[0].password_is_expired);
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(unused_recovery_codes(&app), 4);
+}
+
+#[tokio::test]
+async fn recovery_using_2fa_fails_with_a_wrong_code() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    user_enables_otp(&app, user_id).await;
+
+    let result = recover_account_using_2fa_use_case(&app)
+        .execute(
+            RecoverAccountUsing2FARequest {
+                username: "testusername".to_string(),
+                code: "000000".to_string(),
+                recovery_code: signup.recovery_codes[0].clone(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::InvalidUsernameOrCodeOrRecoveryCode)
+    ));
+    assert_eq!(unused_recovery_codes(&app), 5);
+}
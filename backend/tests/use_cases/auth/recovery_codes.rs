@@ -0,0 +1,79 @@
+use flutteractixapp::features::auth::application::dto::RegenerateRecoveryCodesRequest;
+use flutteractixapp::features::auth::application::usecases::{
+    GetRemainingRecoveryCodesUseCase, RegenerateRecoveryCodesUseCase,
+};
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::helpers::{request_context, user_signs_up, InMemoryApp};
+
+fn regenerate_recovery_codes_use_case(app: &InMemoryApp) -> RegenerateRecoveryCodesUseCase {
+    RegenerateRecoveryCodesUseCase::new(
+        app.user_repository(),
+        app.login_throttle_service(),
+        app.recovery_code_service(),
+        app.audit_log(),
+    )
+}
+
+async fn remaining_recovery_codes(app: &InMemoryApp, user_id: uuid::Uuid) -> i64 {
+    GetRemainingRecoveryCodesUseCase::new(app.recovery_code_service())
+        .execute(user_id)
+        .await
+        .unwrap()
+        .remaining_recovery_codes
+}
+
+#[tokio::test]
+async fn regenerated_codes_replace_the_previous_ones() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    app.database.tables().recovery_codes[0].used_at = Some(chrono::Utc::now());
+    assert_eq!(remaining_recovery_codes(&app, user_id).await, 4);
+
+    let response = regenerate_recovery_codes_use_case(&app)
+        .execute(
+            user_id,
+            RegenerateRecoveryCodesRequest {
+                password: "password1_".to_string(),
+            },
+            request_context(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "RECOVERY_CODES_REGENERATED");
+    assert_eq!(response.recovery_codes.len(), 5);
+    assert!(response
+        .recovery_codes
+        .iter()
+        .all(|code| !signup.recovery_codes.contains(code)));
+    assert_eq!(remaining_recovery_codes(&app, user_id).await, 5);
+}
+
+#[tokio::test]
+async fn codes_are_not_regenerated_with_a_wrong_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let code_hashes = |app: &InMemoryApp| {
+        app.database
+            .tables()
+            .recovery_codes
+            .iter()
+            .map(|code| code.code_hash.clone())
+            .collect::<Vec<_>>()
+    };
+    let previous_code_hashes = code_hashes(&app);
+
+    let result = regenerate_recovery_codes_use_case(&app)
+        .execute(
+            user_id,
+            RegenerateRecoveryCodesRequest {
+                password: "wrong_password1".to_string(),
+            },
+            request_context(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::InvalidCredentials)));
+    assert_eq!(code_hashes(&app), previous_code_hashes);
+}
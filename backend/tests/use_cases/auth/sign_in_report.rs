@@ -0,0 +1,91 @@
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::ReportSignInRequest;
+use flutteractixapp::features::auth::application::usecases::ReportSignInUseCase;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::auth::login::user_logs_in;
+use crate::helpers::{other_device_info, request_context, user_signs_up, InMemoryApp};
+
+fn report_sign_in_use_case(app: &InMemoryApp) -> ReportSignInUseCase {
+    ReportSignInUseCase::new(
+        app.token_repository(),
+        Box::new(app.token_service.clone()),
+        app.token_cache.clone(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+async fn user_reports_sign_in(app: &InMemoryApp, token: &str) -> Result<(), AuthDomainError> {
+    report_sign_in_use_case(app)
+        .execute(
+            ReportSignInRequest {
+                token: token.to_string(),
+            },
+            request_context(),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn reported_sign_in_is_revoked() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    user_logs_in(&app, "password1_", other_device_info())
+        .await
+        .unwrap()
+        .unwrap();
+    let report_token = app.notifier.sent()[0].report_token.clone();
+
+    user_reports_sign_in(&app, &report_token).await.unwrap();
+
+    // Only the session started from the new device is revoked
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.security_events(user_id).last(),
+        Some(&(
+            SecurityEventType::SignInReported,
+            SecurityEventOutcome::Success
+        ))
+    );
+
+    assert_eq!(app.published_events().last(), Some(&"SESSION_REVOKED"));
+
+    // Reporting it again does nothing
+    user_reports_sign_in(&app, &report_token).await.unwrap();
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn reported_sign_in_is_kept_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    user_logs_in(&app, "password1_", other_device_info())
+        .await
+        .unwrap()
+        .unwrap();
+    let report_token = app.notifier.sent()[0].report_token.clone();
+    let published_events = app.published_events();
+    app.database.reject_writes_to("outbox_events");
+
+    let result = user_reports_sign_in(&app, &report_token).await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    assert_eq!(app.session_count(user_id), 2);
+    assert_eq!(app.published_events(), published_events);
+}
+
+#[tokio::test]
+async fn sign_in_cannot_be_reported_with_another_token() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+
+    let result = user_reports_sign_in(&app, &signup.refresh_token).await;
+
+    assert!(matches!(
+        result,
+        Err(AuthDomainError::InvalidSignInReportLink)
+    ));
+    assert_eq!(app.session_count(user_id), 1);
+}
@@ -0,0 +1,62 @@
+use flutteractixapp::features::auth::application::dto::SignupRequest;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::helpers::{device_info, signup_use_case, user_signs_up, InMemoryApp};
+
+#[tokio::test]
+async fn signup_creates_the_user_with_a_session_and_recovery_codes() {
+    let app = InMemoryApp::new();
+
+    let (user_id, response) = user_signs_up(&app).await;
+
+    assert_eq!(response.code, "USER_SIGNED_UP");
+    assert_eq!(response.recovery_codes.len(), 5);
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(app.published_events(), vec!["USER_SIGNED_UP"]);
+}
+
+#[tokio::test]
+async fn signup_fails_when_the_username_is_taken() {
+    let app = InMemoryApp::new();
+    user_signs_up(&app).await;
+
+    let result = signup_use_case(&app)
+        .execute(
+            SignupRequest {
+                // Usernames are not case sensitive
+                username: "TestUsername".to_string(),
+                password: "password2_".to_string(),
+                locale: "fr".to_string(),
+                theme: "light".to_string(),
+            },
+            device_info(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::UserAlreadyExists)));
+    assert_eq!(app.database.tables().users.len(), 1);
+}
+
+#[tokio::test]
+async fn signup_is_rolled_back_when_the_session_cannot_be_saved() {
+    let app = InMemoryApp::new();
+    app.database.reject_writes_to("user_tokens");
+
+    let result = signup_use_case(&app)
+        .execute(
+            SignupRequest {
+                username: "testusername".to_string(),
+                password: "password1_".to_string(),
+                locale: "en".to_string(),
+                theme: "dark".to_string(),
+            },
+            device_info(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    let tables = app.database.tables();
+    assert!(tables.users.is_empty());
+    assert!(tables.recovery_codes.is_empty());
+    assert!(tables.outbox_events.is_empty());
+}
@@ -0,0 +1,151 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::{now, override_now};
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::auth::application::dto::refresh_token_response::RefreshTokenResponse;
+use flutteractixapp::features::auth::application::dto::RefreshTokenRequest;
+use flutteractixapp::features::auth::application::usecases::RefreshTokenUseCase;
+use flutteractixapp::features::auth::domain::entities::AccountStatus;
+use flutteractixapp::features::auth::domain::errors::AuthDomainError;
+
+use crate::helpers::{
+    device_info, request_context, token_id_and_user_id, user_signs_up, InMemoryApp,
+};
+
+fn refresh_token_use_case(app: &InMemoryApp) -> RefreshTokenUseCase {
+    RefreshTokenUseCase::new(
+        app.user_repository(),
+        app.token_repository(),
+        Box::new(app.token_service.clone()),
+        app.audit_log(),
+        app.session_service(),
+        app.token_cache.clone(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+async fn user_refreshes_token(
+    app: &InMemoryApp,
+    refresh_token: &str,
+) -> Result<RefreshTokenResponse, AuthDomainError> {
+    refresh_token_use_case(app)
+        .execute(
+            RefreshTokenRequest {
+                refresh_token: refresh_token.to_string(),
+            },
+            device_info(),
+            request_context(),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn refreshing_a_token_rotates_the_session() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    let (token_id, _) = token_id_and_user_id(&app, &signup.access_token);
+
+    let response = user_refreshes_token(&app, &signup.refresh_token)
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "TOKEN_REFRESHED");
+    let (new_token_id, _) = token_id_and_user_id(&app, &response.access_token);
+    let tables = app.database.tables();
+    let old_token = tables
+        .user_tokens
+        .iter()
+        .find(|token| token.token_id == token_id)
+        .unwrap();
+    let new_token = tables
+        .user_tokens
+        .iter()
+        .find(|token| token.token_id == new_token_id)
+        .unwrap();
+    assert!(old_token.rotated_at.is_some());
+    assert_eq!(new_token.family_id, old_token.family_id);
+    assert_eq!(new_token.parent_token_id, Some(token_id));
+    assert_eq!(new_token.user_id, user_id);
+}
+
+#[tokio::test]
+async fn replaying_a_rotated_token_revokes_the_session() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    user_refreshes_token(&app, &signup.refresh_token)
+        .await
+        .unwrap();
+
+    let result = user_refreshes_token(&app, &signup.refresh_token).await;
+
+    assert!(matches!(result, Err(AuthDomainError::RefreshTokenReused)));
+    assert_eq!(app.session_count(user_id), 0);
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::RefreshTokenReuseDetected,
+            SecurityEventOutcome::Failure
+        )]
+    );
+    // The rotated token and the one replacing it
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "SESSION_REVOKED", "SESSION_REVOKED"]
+    );
+}
+
+#[tokio::test]
+async fn session_is_kept_when_the_revocation_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    user_refreshes_token(&app, &signup.refresh_token)
+        .await
+        .unwrap();
+    let session_count = app.session_count(user_id);
+    app.database.reject_writes_to("outbox_events");
+
+    let result = user_refreshes_token(&app, &signup.refresh_token).await;
+
+    assert!(matches!(result, Err(AuthDomainError::DatabaseError)));
+    assert_eq!(app.session_count(user_id), session_count);
+    assert_eq!(app.published_events(), vec!["USER_SIGNED_UP"]);
+}
+
+#[tokio::test]
+async fn expired_token_is_removed() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    let refresh_token_lifetime =
+        Duration::days(app.configuration.application.refresh_token_lifetime_days);
+    // The signature is still valid, only the session has expired
+    override_now(Some(
+        (now() + refresh_token_lifetime + Duration::seconds(1)).fixed_offset(),
+    ));
+
+    let result = user_refreshes_token(&app, &signup.refresh_token).await;
+
+    assert!(matches!(result, Err(AuthDomainError::TokenExpired)));
+    assert_eq!(app.session_count(user_id), 0);
+}
+
+#[tokio::test]
+async fn disabled_account_cannot_refresh_its_token() {
+    let app = InMemoryApp::new();
+    let (user_id, signup) = user_signs_up(&app).await;
+    app.database.tables().users[0].status = AccountStatus::Banned;
+
+    let result = user_refreshes_token(&app, &signup.refresh_token).await;
+
+    assert!(matches!(result, Err(AuthDomainError::AccountDisabled)));
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn access_token_cannot_be_used_as_a_refresh_token() {
+    let app = InMemoryApp::new();
+    let (_, signup) = user_signs_up(&app).await;
+
+    let result = user_refreshes_token(&app, &signup.access_token).await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,291 @@
+use std::sync::Arc;
+
+use chrono::Duration;
+use flutteractixapp::configuration::{get_configuration, Settings};
+use flutteractixapp::core::structs::in_memory_database::{InMemoryDatabase, InMemoryUnitOfWork};
+use flutteractixapp::core::structs::unit_of_work::UnitOfWork;
+use flutteractixapp::features::audit::domain::entities::{
+    RequestContext, SecurityEventOutcome, SecurityEventType,
+};
+use flutteractixapp::features::audit::domain::services::AuditLog;
+use flutteractixapp::features::audit::infrastructure::repositories::in_memory::InMemorySecurityEventRepository;
+use flutteractixapp::features::auth::application::dto::{SignupRequest, SignupResponse};
+use flutteractixapp::features::auth::application::usecases::SignupUseCase;
+use flutteractixapp::features::auth::domain::entities::{DeviceInfo, TokenType};
+use flutteractixapp::features::auth::domain::repositories::TokenService;
+use flutteractixapp::features::auth::domain::services::{
+    LoginThrottleService, LoginThrottleSettings, MfaChallengeService, MfaChallengeSettings,
+    NewDeviceService, NewDeviceSettings, OtpService, RecoveryCodeService, SessionService,
+    SessionSettings, TotpSettings,
+};
+use flutteractixapp::features::auth::infrastructure::keys::JwtKeySet;
+use flutteractixapp::features::auth::infrastructure::notifiers::InMemoryNotifier;
+use flutteractixapp::features::auth::infrastructure::repositories::in_memory::{
+    InMemoryLoginAttemptRepository, InMemoryMfaChallengeRepository, InMemoryPermissionRepository,
+    InMemoryRecoveryCodeRepository, InMemoryTokenRepository, InMemoryUserRepository,
+};
+use flutteractixapp::features::auth::infrastructure::repositories::TokenServiceImpl;
+use flutteractixapp::features::auth::infrastructure::session_activity::SessionActivityWriter;
+use flutteractixapp::features::auth::structs::models::{LockoutCache, TokenCache};
+use flutteractixapp::features::events::domain::services::Outbox;
+use flutteractixapp::features::events::infrastructure::repositories::in_memory::InMemoryOutboxRepository;
+use flutteractixapp::features::profile::structs::models::ParsedDeviceInfo;
+use sqlx::postgres::PgPoolOptions;
+use uuid::Uuid;
+
+/// The services of the app, wired like in `create_app` but on an in-memory database.
+pub struct InMemoryApp {
+    pub database: InMemoryDatabase,
+    pub configuration: Settings,
+    pub token_service: TokenServiceImpl,
+    pub token_cache: TokenCache,
+    pub lockout_cache: LockoutCache,
+    pub notifier: Arc<InMemoryNotifier>,
+    // Never flushed, the pool doesn't connect until it is used
+    pub session_activity_writer: SessionActivityWriter,
+}
+
+impl Default for InMemoryApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryApp {
+    pub fn new() -> Self {
+        let configuration = get_configuration().expect("Failed to read configuration.");
+        let key_set = JwtKeySet::load(&configuration.jwt).expect("Failed to load JWT keys");
+        let token_service = TokenServiceImpl::new(
+            key_set,
+            configuration.application.token_issuer.clone(),
+            configuration.application.token_audience.clone(),
+        );
+        let pool = PgPoolOptions::new().connect_lazy_with(configuration.database.with_db());
+        let session_activity_writer =
+            SessionActivityWriter::new(pool, configuration.session_activity.clone());
+
+        Self {
+            database: InMemoryDatabase::new(),
+            configuration,
+            token_service,
+            token_cache: TokenCache::default(),
+            lockout_cache: LockoutCache::default(),
+            notifier: Arc::new(InMemoryNotifier::default()),
+            session_activity_writer,
+        }
+    }
+
+    pub fn unit_of_work(&self) -> Box<dyn UnitOfWork> {
+        Box::new(InMemoryUnitOfWork::new(self.database.clone()))
+    }
+
+    pub fn user_repository(&self) -> Box<InMemoryUserRepository> {
+        Box::new(InMemoryUserRepository::new(self.database.clone()))
+    }
+
+    pub fn token_repository(&self) -> Box<InMemoryTokenRepository> {
+        Box::new(InMemoryTokenRepository::new(self.database.clone()))
+    }
+
+    pub fn session_service(&self) -> SessionService {
+        let application = &self.configuration.application;
+
+        SessionService::new(
+            self.token_repository(),
+            Box::new(self.token_service.clone()),
+            Box::new(InMemoryPermissionRepository::new(self.database.clone())),
+            SessionSettings {
+                access_token_lifetime: Duration::minutes(application.access_token_lifetime_minutes),
+                refresh_token_lifetime: Duration::days(application.refresh_token_lifetime_days),
+                issuer: application.token_issuer.clone(),
+                audience: application.token_audience.clone(),
+            },
+        )
+    }
+
+    pub fn login_throttle_service(&self) -> LoginThrottleService {
+        let login_throttling = &self.configuration.login_throttling;
+
+        LoginThrottleService::new(
+            Box::new(InMemoryLoginAttemptRepository::new(self.database.clone())),
+            self.lockout_cache.clone(),
+            LoginThrottleSettings {
+                account_max_attempts: login_throttling.account_max_attempts,
+                ip_max_attempts: login_throttling.ip_max_attempts,
+                window: Duration::seconds(login_throttling.window_seconds),
+                lockout: Duration::seconds(login_throttling.lockout_seconds),
+            },
+        )
+    }
+
+    pub fn mfa_challenge_service(&self) -> MfaChallengeService {
+        let application = &self.configuration.application;
+
+        MfaChallengeService::new(
+            Box::new(InMemoryMfaChallengeRepository::new(self.database.clone())),
+            Box::new(self.token_service.clone()),
+            MfaChallengeSettings {
+                lifetime: Duration::seconds(application.mfa_challenge_lifetime_seconds),
+                issuer: application.token_issuer.clone(),
+                audience: application.token_audience.clone(),
+            },
+        )
+    }
+
+    pub fn otp_service(&self) -> OtpService {
+        let otp = &self.configuration.otp;
+
+        OtpService::new(
+            self.user_repository(),
+            TotpSettings {
+                algorithm: otp.algorithm.into(),
+                digits: otp.digits,
+                skew: otp.skew,
+                step_seconds: otp.step_seconds,
+            },
+        )
+    }
+
+    pub fn recovery_code_service(&self) -> RecoveryCodeService {
+        RecoveryCodeService::new(Box::new(InMemoryRecoveryCodeRepository::new(
+            self.database.clone(),
+        )))
+    }
+
+    pub fn new_device_service(&self) -> NewDeviceService {
+        NewDeviceService::new(
+            self.token_repository(),
+            Box::new(self.token_service.clone()),
+            self.notifier.clone(),
+            NewDeviceSettings {
+                report_link_lifetime: Duration::hours(
+                    self.configuration
+                        .new_device_alerts
+                        .report_link_lifetime_hours,
+                ),
+                issuer: self.configuration.application.token_issuer.clone(),
+                audience: self.configuration.application.token_audience.clone(),
+            },
+        )
+    }
+
+    pub fn audit_log(&self) -> AuditLog {
+        AuditLog::new(Box::new(InMemorySecurityEventRepository::new(
+            self.database.clone(),
+        )))
+    }
+
+    pub fn outbox(&self) -> Outbox {
+        Outbox::new(Box::new(InMemoryOutboxRepository::new(
+            self.database.clone(),
+        )))
+    }
+
+    /// The events published so far, by name.
+    pub fn published_events(&self) -> Vec<&'static str> {
+        self.database
+            .tables()
+            .outbox_events
+            .iter()
+            .map(|row| row.message.event.name())
+            .collect()
+    }
+
+    /// The security events recorded for the user, oldest first.
+    pub fn security_events(&self, user_id: Uuid) -> Vec<(SecurityEventType, SecurityEventOutcome)> {
+        self.database
+            .tables()
+            .security_events
+            .iter()
+            .filter(|event| event.user_id == user_id)
+            .map(|event| (event.event_type, event.outcome))
+            .collect()
+    }
+
+    pub fn session_count(&self, user_id: Uuid) -> usize {
+        self.database
+            .tables()
+            .user_tokens
+            .iter()
+            .filter(|token| token.user_id == user_id)
+            .count()
+    }
+}
+
+pub fn device_info() -> DeviceInfo {
+    DeviceInfo {
+        os: Some("iOS".to_string()),
+        is_mobile: Some(true),
+        browser: None,
+        app_version: Some("1.0.0".to_string()),
+        model: Some("iPhone 13".to_string()),
+    }
+}
+
+pub fn other_device_info() -> DeviceInfo {
+    DeviceInfo {
+        os: Some("Windows".to_string()),
+        is_mobile: Some(false),
+        browser: Some("Firefox".to_string()),
+        app_version: None,
+        model: None,
+    }
+}
+
+pub fn request_context() -> RequestContext {
+    let device_info = device_info();
+
+    RequestContext {
+        ip: Some("203.0.113.1".to_string()),
+        device_info: ParsedDeviceInfo {
+            os: device_info.os,
+            is_mobile: device_info.is_mobile,
+            browser: device_info.browser,
+            app_version: device_info.app_version,
+            model: device_info.model,
+        },
+    }
+}
+
+pub fn signup_use_case(app: &InMemoryApp) -> SignupUseCase {
+    SignupUseCase::new(
+        app.user_repository(),
+        app.session_service(),
+        app.recovery_code_service(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+/// Signs up `testusername` with `password1_`, returns its id along with the response.
+pub async fn user_signs_up(app: &InMemoryApp) -> (Uuid, SignupResponse) {
+    user_signs_up_as(app, "testusername").await
+}
+
+pub async fn user_signs_up_as(app: &InMemoryApp, username: &str) -> (Uuid, SignupResponse) {
+    let response = signup_use_case(app)
+        .execute(
+            SignupRequest {
+                username: username.to_string(),
+                password: "password1_".to_string(),
+                locale: "en".to_string(),
+                theme: "dark".to_string(),
+            },
+            device_info(),
+        )
+        .await
+        .unwrap();
+    let user_id = token_id_and_user_id(app, &response.access_token).1;
+
+    (user_id, response)
+}
+
+/// The session of the token, and the user it belongs to.
+pub fn token_id_and_user_id(app: &InMemoryApp, access_token: &str) -> (Uuid, Uuid) {
+    let claims = app
+        .token_service
+        .decode_token(access_token, TokenType::Access)
+        .unwrap();
+
+    (claims.jti, claims.user_id)
+}
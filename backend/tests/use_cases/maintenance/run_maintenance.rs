@@ -0,0 +1,95 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::features::maintenance::application::usecases::{
+    PurgeSettings, RunMaintenanceUseCase,
+};
+use flutteractixapp::features::maintenance::domain::errors::MaintenanceDomainError;
+use flutteractixapp::features::maintenance::infrastructure::repositories::in_memory::InMemoryMaintenanceRepository;
+
+use crate::helpers::{user_signs_up, user_signs_up_as, InMemoryApp};
+
+fn run_maintenance_use_case(app: &InMemoryApp, batch_size: i64) -> RunMaintenanceUseCase {
+    RunMaintenanceUseCase::new(
+        Box::new(InMemoryMaintenanceRepository::new(app.database.clone())),
+        PurgeSettings {
+            batch_size,
+            login_attempt_window: Duration::seconds(
+                app.configuration.login_throttling.window_seconds,
+            ),
+        },
+    )
+}
+
+#[tokio::test]
+async fn expired_sessions_are_purged_in_batches() {
+    let app = InMemoryApp::new();
+    for username in ["alice", "bob", "carol"] {
+        user_signs_up_as(&app, username).await;
+    }
+    let (user_id, _) = user_signs_up(&app).await;
+    for token in app
+        .database
+        .tables()
+        .user_tokens
+        .iter_mut()
+        .filter(|token| token.user_id != user_id)
+    {
+        token.expires_at = now() - Duration::minutes(1);
+    }
+
+    let response = run_maintenance_use_case(&app, 2).execute().await.unwrap();
+
+    assert_eq!(response.code, "MAINTENANCE_COMPLETED");
+    assert_eq!(response.expired_tokens, 3);
+    assert_eq!(app.database.tables().user_tokens.len(), 1);
+    assert_eq!(app.session_count(user_id), 1);
+}
+
+#[tokio::test]
+async fn accounts_are_deleted_once_due() {
+    let app = InMemoryApp::new();
+    let (due_user_id, _) = user_signs_up_as(&app, "alice").await;
+    let (scheduled_user_id, _) = user_signs_up(&app).await;
+    for user in app.database.tables().users.iter_mut() {
+        user.deletion_scheduled_at = Some(if user.id == due_user_id {
+            now() - Duration::minutes(1)
+        } else {
+            now() + Duration::days(1)
+        });
+    }
+
+    let response = run_maintenance_use_case(&app, 100).execute().await.unwrap();
+
+    assert_eq!(response.deleted_accounts, 1);
+    let tables = app.database.tables();
+    assert_eq!(tables.users.len(), 1);
+    assert_eq!(tables.users[0].id, scheduled_user_id);
+    assert!(tables
+        .user_tokens
+        .iter()
+        .all(|token| token.user_id == scheduled_user_id));
+}
+
+#[tokio::test]
+async fn nothing_is_purged_when_nothing_expired() {
+    let app = InMemoryApp::new();
+    user_signs_up(&app).await;
+
+    let response = run_maintenance_use_case(&app, 100).execute().await.unwrap();
+
+    assert_eq!(response.expired_tokens, 0);
+    assert_eq!(response.deleted_accounts, 0);
+    // The event of the signup isn't delivered yet
+    assert_eq!(response.delivered_events, 0);
+    assert_eq!(app.database.tables().outbox_events.len(), 1);
+}
+
+#[tokio::test]
+async fn maintenance_fails_when_the_database_does() {
+    let app = InMemoryApp::new();
+    app.database.reject_writes_to("user_tokens");
+
+    let result = run_maintenance_use_case(&app, 100).execute().await;
+
+    assert!(matches!(result, Err(MaintenanceDomainError::DatabaseError)));
+}
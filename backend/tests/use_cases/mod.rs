@@ -0,0 +1,36 @@
+pub mod admin {
+    pub mod account_status;
+    pub mod users;
+}
+
+pub mod audit {
+    pub mod security_events;
+}
+
+pub mod auth {
+    pub mod account_deletion;
+    pub mod jwks;
+    pub mod login;
+    pub mod logout;
+    pub mod otp;
+    pub mod recovery;
+    pub mod recovery_codes;
+    pub mod sign_in_report;
+    pub mod signup;
+    pub mod token;
+}
+
+pub mod profile {
+    pub mod data_export;
+    pub mod devices;
+    #[allow(clippy::module_inception)]
+    pub mod profile;
+    pub mod set_password;
+    pub mod update_password;
+}
+
+pub mod maintenance {
+    pub mod run_maintenance;
+}
+
+pub mod helpers;
@@ -0,0 +1,197 @@
+use chrono::Duration;
+use flutteractixapp::core::helpers::mock_now::now;
+use flutteractixapp::core::structs::in_memory_database::DataExportRow;
+use flutteractixapp::features::audit::infrastructure::repositories::in_memory::InMemorySecurityEventRepository;
+use flutteractixapp::features::profile::application::dto::DataArchive;
+use flutteractixapp::features::profile::application::usecases::{
+    DataExportLinkSettings, DownloadDataExportUseCase, RequestDataExportUseCase,
+};
+use flutteractixapp::features::profile::domain::entities::{DataExport, DataExportStatus};
+use flutteractixapp::features::profile::domain::errors::ProfileDomainError;
+use flutteractixapp::features::profile::infrastructure::repositories::in_memory::{
+    InMemoryDataExportRepository, InMemoryDeviceRepository, InMemoryUserRepository,
+};
+use uuid::Uuid;
+
+use crate::auth::otp::user_generates_otp;
+use crate::helpers::{user_signs_up, InMemoryApp};
+
+fn request_data_export_use_case(app: &InMemoryApp) -> RequestDataExportUseCase {
+    RequestDataExportUseCase::new(
+        Box::new(InMemoryDataExportRepository::new(app.database.clone())),
+        Box::new(InMemoryUserRepository::new(app.database.clone())),
+        Box::new(InMemoryDeviceRepository::new(app.database.clone())),
+        Box::new(InMemorySecurityEventRepository::new(app.database.clone())),
+        app.session_activity_writer.clone(),
+        Box::new(app.token_service.clone()),
+        DataExportLinkSettings {
+            lifetime: Duration::hours(app.configuration.data_export.lifetime_hours),
+            build_timeout: Duration::minutes(app.configuration.data_export.build_timeout_minutes),
+            issuer: app.configuration.application.token_issuer.clone(),
+            audience: app.configuration.application.token_audience.clone(),
+        },
+    )
+}
+
+fn download_data_export_use_case(app: &InMemoryApp) -> DownloadDataExportUseCase {
+    DownloadDataExportUseCase::new(
+        Box::new(InMemoryDataExportRepository::new(app.database.clone())),
+        Box::new(app.token_service.clone()),
+    )
+}
+
+// The archive is built in a spawned task, so the user asks again until it is ready
+async fn user_gets_data_export_token(app: &InMemoryApp, user_id: Uuid) -> String {
+    let use_case = request_data_export_use_case(app);
+
+    for _ in 0..50 {
+        let response = use_case.execute(user_id).await.unwrap();
+
+        if let Some(download_token) = response.download_token {
+            assert_eq!(response.code, "DATA_EXPORT_READY");
+            return download_token;
+        }
+
+        assert!(["DATA_EXPORT_STARTED", "DATA_EXPORT_PENDING"].contains(&response.code.as_str()));
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    panic!("The data export is still pending");
+}
+
+#[tokio::test]
+async fn export_is_pending_until_the_archive_is_built() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = request_data_export_use_case(&app)
+        .execute(user_id)
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "DATA_EXPORT_STARTED");
+    assert!(response.download_token.is_none());
+    let lifetime = Duration::hours(app.configuration.data_export.lifetime_hours);
+    assert!(response.expires_at > now() + lifetime - Duration::minutes(1));
+    assert_eq!(app.database.tables().data_exports.len(), 1);
+}
+
+#[tokio::test]
+async fn user_downloads_their_archive() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let token = user_gets_data_export_token(&app, user_id).await;
+
+    let archive = download_data_export_use_case(&app)
+        .execute(&token)
+        .await
+        .unwrap();
+
+    let archive: DataArchive = serde_json::from_str(&archive).unwrap();
+    assert_eq!(archive.profile.id, user_id);
+    assert_eq!(archive.preferences.theme, "dark");
+    assert_eq!(archive.devices.len(), 1);
+}
+
+#[tokio::test]
+async fn archive_leaves_the_otp_secret_out() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let otp_base32 = user_generates_otp(&app, user_id).await;
+    let token = user_gets_data_export_token(&app, user_id).await;
+
+    let archive = download_data_export_use_case(&app)
+        .execute(&token)
+        .await
+        .unwrap();
+
+    assert!(!archive.contains(&otp_base32));
+    assert!(!archive.contains("otp_auth_url"));
+}
+
+#[tokio::test]
+async fn archive_can_only_be_downloaded_once() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    let token = user_gets_data_export_token(&app, user_id).await;
+
+    download_data_export_use_case(&app)
+        .execute(&token)
+        .await
+        .unwrap();
+    let result = download_data_export_use_case(&app).execute(&token).await;
+
+    assert!(matches!(
+        result,
+        Err(ProfileDomainError::InvalidDataExportLink)
+    ));
+    assert!(app.database.tables().data_exports[0].archive.is_none());
+}
+
+#[tokio::test]
+async fn tampered_link_is_rejected() {
+    let app = InMemoryApp::new();
+
+    let result = download_data_export_use_case(&app)
+        .execute("not_a_token")
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(ProfileDomainError::InvalidDataExportLink)
+    ));
+}
+
+#[tokio::test]
+async fn export_fails_when_the_archive_cannot_be_built() {
+    let app = InMemoryApp::new();
+    // The export of an unknown user has nothing to build its archive from
+    let user_id = Uuid::new_v4();
+
+    request_data_export_use_case(&app)
+        .execute(user_id)
+        .await
+        .unwrap();
+
+    for _ in 0..50 {
+        if app.database.tables().data_exports[0].export.status == DataExportStatus::Failed {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    panic!("The data export is still pending");
+}
+
+#[tokio::test]
+async fn export_whose_build_timed_out_is_replaced() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    // As left behind by a restart while the archive was built
+    let created_at =
+        now() - Duration::minutes(app.configuration.data_export.build_timeout_minutes + 1);
+    app.database.tables().data_exports.push(DataExportRow {
+        export: DataExport {
+            id: Uuid::new_v4(),
+            user_id,
+            status: DataExportStatus::Pending,
+            created_at,
+            expires_at: created_at + Duration::hours(app.configuration.data_export.lifetime_hours),
+            downloaded_at: None,
+        },
+        archive: None,
+    });
+
+    let response = request_data_export_use_case(&app)
+        .execute(user_id)
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "DATA_EXPORT_STARTED");
+    let tables = app.database.tables();
+    assert_eq!(tables.data_exports.len(), 2);
+    assert_eq!(
+        tables.data_exports[0].export.status,
+        DataExportStatus::Failed
+    );
+}
@@ -0,0 +1,143 @@
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::profile::application::usecases::{
+    DeleteDeviceUseCase, DeleteOtherDevicesUseCase, GetDevicesUseCase,
+};
+use flutteractixapp::features::profile::domain::errors::ProfileDomainError;
+use flutteractixapp::features::profile::infrastructure::repositories::in_memory::InMemoryDeviceRepository;
+use uuid::Uuid;
+
+use crate::auth::login::user_logs_in;
+use crate::helpers::{
+    other_device_info, request_context, token_id_and_user_id, user_signs_up, InMemoryApp,
+};
+
+fn device_repository(app: &InMemoryApp) -> Box<InMemoryDeviceRepository> {
+    Box::new(InMemoryDeviceRepository::new(app.database.clone()))
+}
+
+fn get_devices_use_case(app: &InMemoryApp) -> GetDevicesUseCase {
+    GetDevicesUseCase::new(device_repository(app), app.session_activity_writer.clone())
+}
+
+fn delete_device_use_case(app: &InMemoryApp) -> DeleteDeviceUseCase {
+    DeleteDeviceUseCase::new(
+        device_repository(app),
+        app.token_cache.clone(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+fn delete_other_devices_use_case(app: &InMemoryApp) -> DeleteOtherDevicesUseCase {
+    DeleteOtherDevicesUseCase::new(
+        device_repository(app),
+        app.token_cache.clone(),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+/// Signs up on a first device and logs in on another one, returns the session of each.
+async fn user_signs_in_on_two_devices(app: &InMemoryApp) -> (Uuid, Uuid, Uuid) {
+    let (user_id, signup) = user_signs_up(app).await;
+    let (first_token_id, _) = token_id_and_user_id(app, &signup.access_token);
+    let login = user_logs_in(app, "password1_", other_device_info())
+        .await
+        .unwrap()
+        .unwrap();
+    let (second_token_id, _) = token_id_and_user_id(app, &login.access_token);
+
+    (user_id, first_token_id, second_token_id)
+}
+
+#[tokio::test]
+async fn user_lists_their_devices() {
+    let app = InMemoryApp::new();
+    let (user_id, first_token_id, second_token_id) = user_signs_in_on_two_devices(&app).await;
+
+    let response = get_devices_use_case(&app).execute(user_id).await.unwrap();
+
+    assert_eq!(response.code, "DEVICES_FETCHED");
+    let token_ids: Vec<Uuid> = response
+        .devices
+        .iter()
+        .map(|device| device.token_id)
+        .collect();
+    assert_eq!(token_ids, vec![first_token_id, second_token_id]);
+    assert_eq!(
+        response.devices[1].parsed_device_info.os,
+        Some("Windows".to_string())
+    );
+}
+
+#[tokio::test]
+async fn user_deletes_a_device() {
+    let app = InMemoryApp::new();
+    let (user_id, first_token_id, second_token_id) = user_signs_in_on_two_devices(&app).await;
+
+    let response = delete_device_use_case(&app)
+        .execute(user_id, second_token_id, request_context())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "DEVICE_DELETED");
+    let devices = get_devices_use_case(&app)
+        .execute(user_id)
+        .await
+        .unwrap()
+        .devices;
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].token_id, first_token_id);
+    assert!(app.security_events(user_id).contains(&(
+        SecurityEventType::DeviceRevoked,
+        SecurityEventOutcome::Success
+    )));
+}
+
+#[tokio::test]
+async fn device_of_another_user_is_not_found() {
+    let app = InMemoryApp::new();
+    let (_, _, second_token_id) = user_signs_in_on_two_devices(&app).await;
+
+    let result = delete_device_use_case(&app)
+        .execute(Uuid::new_v4(), second_token_id, request_context())
+        .await;
+
+    assert!(matches!(result, Err(ProfileDomainError::DeviceNotFound)));
+    assert_eq!(app.database.tables().user_tokens.len(), 2);
+}
+
+#[tokio::test]
+async fn user_deletes_every_other_device() {
+    let app = InMemoryApp::new();
+    let (user_id, first_token_id, _) = user_signs_in_on_two_devices(&app).await;
+
+    let response = delete_other_devices_use_case(&app)
+        .execute(user_id, first_token_id, request_context())
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "OTHER_DEVICES_DELETED");
+    assert_eq!(app.session_count(user_id), 1);
+    assert_eq!(
+        app.database.tables().user_tokens[0].token_id,
+        first_token_id
+    );
+    assert!(app.published_events().contains(&"SESSION_REVOKED"));
+}
+
+#[tokio::test]
+async fn devices_are_kept_when_the_event_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, first_token_id, _) = user_signs_in_on_two_devices(&app).await;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = delete_other_devices_use_case(&app)
+        .execute(user_id, first_token_id, request_context())
+        .await;
+
+    assert!(matches!(result, Err(ProfileDomainError::DatabaseError)));
+    assert_eq!(app.session_count(user_id), 2);
+}
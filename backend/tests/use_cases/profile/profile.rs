@@ -0,0 +1,96 @@
+use flutteractixapp::features::profile::application::dto::{
+    IsOtpEnabledRequest, UpdateProfileRequest,
+};
+use flutteractixapp::features::profile::application::usecases::{
+    GetProfileUseCase, IsOtpEnabledUseCase, UpdateProfileUseCase,
+};
+use flutteractixapp::features::profile::domain::errors::ProfileDomainError;
+use flutteractixapp::features::profile::infrastructure::repositories::in_memory::InMemoryUserRepository;
+use uuid::Uuid;
+
+use crate::auth::otp::user_enables_otp;
+use crate::helpers::{user_signs_up, InMemoryApp};
+
+fn user_repository(app: &InMemoryApp) -> Box<InMemoryUserRepository> {
+    Box::new(InMemoryUserRepository::new(app.database.clone()))
+}
+
+async fn user_checks_otp_status(app: &InMemoryApp, username: &str) -> bool {
+    IsOtpEnabledUseCase::new(user_repository(app))
+        .execute(IsOtpEnabledRequest {
+            username: username.to_string(),
+        })
+        .await
+        .unwrap()
+        .otp_enabled
+}
+
+#[tokio::test]
+async fn user_gets_their_profile() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = GetProfileUseCase::new(user_repository(&app))
+        .execute(user_id)
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "PROFILE_FETCHED");
+    assert_eq!(response.user.username, "testusername");
+    assert_eq!(response.user.locale, "en");
+    assert!(!response.user.otp_verified);
+}
+
+#[tokio::test]
+async fn profile_of_an_unknown_user_is_not_found() {
+    let app = InMemoryApp::new();
+
+    let result = GetProfileUseCase::new(user_repository(&app))
+        .execute(Uuid::new_v4())
+        .await;
+
+    assert!(matches!(result, Err(ProfileDomainError::UserNotFound)));
+}
+
+#[tokio::test]
+async fn user_updates_their_profile() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = UpdateProfileUseCase::new(user_repository(&app))
+        .execute(
+            user_id,
+            UpdateProfileRequest {
+                username: "newusername".to_string(),
+                locale: "fr".to_string(),
+                theme: "light".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "PROFILE_UPDATED");
+    let user = &app.database.tables().users[0];
+    assert_eq!(user.username, "newusername");
+    assert_eq!(user.locale, "fr");
+    assert_eq!(user.theme, "light");
+}
+
+#[tokio::test]
+async fn otp_status_follows_the_verification_of_the_secret() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    assert!(!user_checks_otp_status(&app, "testusername").await);
+
+    user_enables_otp(&app, user_id).await;
+
+    assert!(user_checks_otp_status(&app, "testusername").await);
+}
+
+#[tokio::test]
+async fn otp_status_of_an_unknown_user_is_disabled() {
+    let app = InMemoryApp::new();
+
+    assert!(!user_checks_otp_status(&app, "unknownusername").await);
+}
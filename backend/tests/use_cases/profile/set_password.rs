@@ -0,0 +1,71 @@
+use flutteractixapp::features::profile::application::dto::{ProfileResponse, SetPasswordRequest};
+use flutteractixapp::features::profile::application::usecases::SetPasswordUseCase;
+use flutteractixapp::features::profile::domain::errors::ProfileDomainError;
+use flutteractixapp::features::profile::infrastructure::repositories::in_memory::InMemoryUserRepository;
+use uuid::Uuid;
+
+use crate::helpers::{request_context, user_signs_up, InMemoryApp};
+use crate::profile::update_password::password_matches;
+
+async fn user_sets_password(
+    app: &InMemoryApp,
+    user_id: Uuid,
+    new_password: &str,
+) -> Result<ProfileResponse, ProfileDomainError> {
+    SetPasswordUseCase::new(
+        Box::new(InMemoryUserRepository::new(app.database.clone())),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+    .execute(
+        user_id,
+        SetPasswordRequest {
+            new_password: new_password.to_string(),
+        },
+        request_context(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn user_sets_a_new_password_once_it_expired() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.tables().users[0].password_is_expired = true;
+
+    let response = user_sets_password(&app, user_id, "new_password1_")
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "PASSWORD_CHANGED");
+    assert!(!response.user.password_is_expired);
+    assert!(!app.database.tables().users[0].password_is_expired);
+    assert!(password_matches(&app, "new_password1_"));
+}
+
+#[tokio::test]
+async fn password_cannot_be_set_before_it_expires() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_sets_password(&app, user_id, "new_password1_").await;
+
+    assert!(matches!(
+        result,
+        Err(ProfileDomainError::PasswordNotExpired)
+    ));
+    assert!(password_matches(&app, "password1_"));
+}
+
+#[tokio::test]
+async fn new_password_must_be_strong_enough() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.tables().users[0].password_is_expired = true;
+
+    let result = user_sets_password(&app, user_id, "short").await;
+
+    assert!(matches!(result, Err(ProfileDomainError::InvalidPassword)));
+    assert!(app.database.tables().users[0].password_is_expired);
+}
@@ -0,0 +1,118 @@
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use flutteractixapp::features::audit::domain::entities::{SecurityEventOutcome, SecurityEventType};
+use flutteractixapp::features::profile::application::dto::{
+    ProfileResponse, UpdatePasswordRequest,
+};
+use flutteractixapp::features::profile::application::usecases::UpdatePasswordUseCase;
+use flutteractixapp::features::profile::domain::errors::ProfileDomainError;
+use flutteractixapp::features::profile::infrastructure::repositories::in_memory::InMemoryUserRepository;
+use uuid::Uuid;
+
+use crate::helpers::{request_context, user_signs_up, InMemoryApp};
+
+fn update_password_use_case(app: &InMemoryApp) -> UpdatePasswordUseCase {
+    UpdatePasswordUseCase::new(
+        Box::new(InMemoryUserRepository::new(app.database.clone())),
+        app.audit_log(),
+        app.unit_of_work(),
+        app.outbox(),
+    )
+}
+
+async fn user_updates_password(
+    app: &InMemoryApp,
+    user_id: Uuid,
+    current_password: &str,
+    new_password: &str,
+) -> Result<ProfileResponse, ProfileDomainError> {
+    update_password_use_case(app)
+        .execute(
+            user_id,
+            UpdatePasswordRequest {
+                current_password: current_password.to_string(),
+                new_password: new_password.to_string(),
+            },
+            request_context(),
+        )
+        .await
+}
+
+pub fn password_matches(app: &InMemoryApp, password: &str) -> bool {
+    let password_hash = app.database.tables().users[0].password_hash.clone();
+
+    Argon2::default()
+        .verify_password(
+            password.as_bytes(),
+            &PasswordHash::new(&password_hash).unwrap(),
+        )
+        .is_ok()
+}
+
+#[tokio::test]
+async fn user_updates_their_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let response = user_updates_password(&app, user_id, "password1_", "new_password1_")
+        .await
+        .unwrap();
+
+    assert_eq!(response.code, "PASSWORD_CHANGED");
+    assert!(password_matches(&app, "new_password1_"));
+    assert_eq!(
+        app.published_events(),
+        vec!["USER_SIGNED_UP", "PASSWORD_CHANGED"]
+    );
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::PasswordChanged,
+            SecurityEventOutcome::Success
+        )]
+    );
+}
+
+#[tokio::test]
+async fn password_is_not_updated_with_a_wrong_current_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_updates_password(&app, user_id, "wrong_password1", "new_password1_").await;
+
+    assert!(matches!(result, Err(ProfileDomainError::InvalidPassword)));
+    assert!(password_matches(&app, "password1_"));
+    assert_eq!(
+        app.security_events(user_id),
+        vec![(
+            SecurityEventType::PasswordChanged,
+            SecurityEventOutcome::Failure
+        )]
+    );
+}
+
+#[tokio::test]
+async fn password_is_not_updated_with_a_weak_new_password() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+
+    let result = user_updates_password(&app, user_id, "password1_", "short").await;
+    assert!(matches!(result, Err(ProfileDomainError::InvalidPassword)));
+
+    let result = user_updates_password(&app, user_id, "password1_", "onlyletters").await;
+    assert!(matches!(result, Err(ProfileDomainError::InvalidPassword)));
+
+    assert!(password_matches(&app, "password1_"));
+}
+
+#[tokio::test]
+async fn password_is_kept_when_the_event_cannot_be_published() {
+    let app = InMemoryApp::new();
+    let (user_id, _) = user_signs_up(&app).await;
+    app.database.reject_writes_to("outbox_events");
+
+    let result = user_updates_password(&app, user_id, "password1_", "new_password1_").await;
+
+    assert!(matches!(result, Err(ProfileDomainError::DatabaseError)));
+    assert!(password_matches(&app, "password1_"));
+    assert!(app.security_events(user_id).is_empty());
+}
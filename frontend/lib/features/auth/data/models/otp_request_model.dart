@@ -13,17 +13,17 @@ class VerifyOneTimePasswordRequestModel {
 }
 
 class ValidateOneTimePasswordRequestModel {
-  final String userId;
+  final String mfaToken;
   final String code;
 
   const ValidateOneTimePasswordRequestModel({
-    required this.userId,
+    required this.mfaToken,
     required this.code,
   });
 
   Map<String, dynamic> toJson() {
     return {
-      'user_id': userId,
+      'mfa_token': mfaToken,
       'code': code,
     };
   }
@@ -176,12 +176,12 @@ class AuthRepositoryImpl implements AuthRepository {
 
   @override
   Future<Either<DomainError, UserToken>> validateOneTimePassword({
-    required String userId,
+    required String mfaToken,
     required String code,
   }) async {
     try {
       final userTokenModel = await remoteDataSource.validateOneTimePassword(
-          ValidateOneTimePasswordRequestModel(userId: userId, code: code));
+          ValidateOneTimePasswordRequestModel(mfaToken: mfaToken, code: code));
 
       return Right(UserToken(
         accessToken: userTokenModel.accessToken,
@@ -83,7 +83,7 @@ class AuthRemoteDataSource {
         }
 
         if (responseCode == 'USER_LOGS_IN_WITH_OTP_ENABLED') {
-          return Right(jsonBody['user_id']);
+          return Right(jsonBody['mfa_token']);
         }
 
         throw ParsingError();
@@ -20,7 +20,7 @@ abstract class AuthRepository {
     required String code,
   });
   Future<Either<DomainError, UserToken>> validateOneTimePassword({
-    required String userId,
+    required String mfaToken,
     required String code,
   });
   Future<Either<DomainError, bool>> disableTwoFactorAuthentication();
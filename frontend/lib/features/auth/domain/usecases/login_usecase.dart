@@ -14,8 +14,8 @@ class LoginUseCase {
     final result =
         await authRepository.login(username: username, password: password);
 
-    await result.fold((_) async {}, (userTokenOrUserId) async {
-      await userTokenOrUserId.fold((userToken) async {
+    await result.fold((_) async {}, (userTokenOrMfaToken) async {
+      await userTokenOrMfaToken.fold((userToken) async {
         // Store tokens securely after successful login
         await TokenStorage().saveTokens(
           userToken.accessToken,
@@ -11,9 +11,9 @@ class ValidateOneTimePasswordUseCase {
 
   /// Validates the OTP provided by the user. It's for login.
   Future<Either<DomainError, UserToken>> call(
-      String userId, String code) async {
+      String mfaToken, String code) async {
     final result = await authRepository.validateOneTimePassword(
-        userId: userId, code: code);
+        mfaToken: mfaToken, code: code);
 
     await result.fold((_) async {}, (userToken) async {
       // Store tokens securely after successful login
@@ -181,8 +181,8 @@ class AuthBloc extends Bloc<AuthEvent, AuthState> {
           message: ErrorMessage(error.messageKey),
         ),
       ),
-      (userTokenOrUserId) {
-        userTokenOrUserId.fold(
+      (userTokenOrMfaToken) {
+        userTokenOrMfaToken.fold(
           (userToken) {
             emit(
               AuthAuthenticatedAfterLoginState(
@@ -191,8 +191,8 @@ class AuthBloc extends Bloc<AuthEvent, AuthState> {
               ),
             );
           },
-          (userId) => emit(
-            AuthValidateOneTimePasswordState(userId: userId),
+          (mfaToken) => emit(
+            AuthValidateOneTimePasswordState(mfaToken: mfaToken),
           ),
         );
       },
@@ -204,7 +204,7 @@ class AuthBloc extends Bloc<AuthEvent, AuthState> {
     emit(AuthLoadingState());
 
     final result = await validateOneTimePasswordUseCase.call(
-      event.userId,
+      event.mfaToken,
       event.code,
     );
 
@@ -212,7 +212,7 @@ class AuthBloc extends Bloc<AuthEvent, AuthState> {
       (error) => emit(
         AuthValidateOneTimePasswordState(
           message: ErrorMessage(error.messageKey),
-          userId: event.userId,
+          mfaToken: event.mfaToken,
         ),
       ),
       (userToken) async {
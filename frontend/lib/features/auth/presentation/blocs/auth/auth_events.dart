@@ -56,14 +56,14 @@ class AuthVerifyOneTimePasswordEvent extends AuthEvent {
 }
 
 class AuthValidateOneTimePasswordEvent extends AuthEvent {
-  final String userId;
+  final String mfaToken;
   final String code;
 
   const AuthValidateOneTimePasswordEvent(
-      {required this.userId, required this.code});
+      {required this.mfaToken, required this.code});
 
   @override
-  List<Object> get props => [userId, code];
+  List<Object> get props => [mfaToken, code];
 }
 
 class AuthRecoverAccountForUsernameEvent extends AuthEvent {
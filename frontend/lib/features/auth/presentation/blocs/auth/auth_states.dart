@@ -69,12 +69,13 @@ class AuthVerifyOneTimePasswordState
 }
 
 class AuthValidateOneTimePasswordState extends AuthState {
-  final String userId;
+  final String mfaToken;
 
-  const AuthValidateOneTimePasswordState({super.message, required this.userId});
+  const AuthValidateOneTimePasswordState(
+      {super.message, required this.mfaToken});
 
   @override
-  List<Object?> get props => [message, userId];
+  List<Object?> get props => [message, mfaToken];
 }
 
 class AuthRecoverAccountUsernameStepState extends AuthUnauthenticatedState {
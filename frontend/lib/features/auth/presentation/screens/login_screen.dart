@@ -102,7 +102,7 @@ class LoginScreenState extends State<LoginScreen>
     void triggerLogin() {
       BlocProvider.of<AuthBloc>(context).add(
         AuthValidateOneTimePasswordEvent(
-          userId: state.userId,
+          mfaToken: state.mfaToken,
           code: _codeController.text,
         ),
       );